@@ -1,7 +1,9 @@
 use termion;
 use termion::input::TermRead;
 
-mod gen;
+mod gen {
+    ice_rs::include_slice!();
+}
 use crate::gen::demo::{Context, ContextPrx};
 
 fn menu() {
@@ -1,15 +1,17 @@
 use ice_rs::communicator::Communicator;
-use std::collections::HashMap;
+use ice_rs::current::Current;
 use async_trait::async_trait;
 
-mod gen;
+mod gen {
+    ice_rs::include_slice!();
+}
 use crate::gen::demo::{HelloServer, HelloI};
 
 struct HelloImpl {}
 
 #[async_trait]
 impl HelloI for HelloImpl {
-    async fn say_hello(&mut self, _context: Option<HashMap<String, String>>) -> ()
+    async fn say_hello(&mut self, _current: &Current) -> ()
     {
         println!("Hello World!");
         ()
@@ -19,12 +21,13 @@ impl HelloI for HelloImpl {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let comm = Communicator::new().await?;
-    let mut adapter = comm.create_object_adapter_with_endpoint("hello", "tcp -h localhost -p 10000").await?;
+    let adapter = comm.create_object_adapter_with_endpoint("hello", "tcp -h localhost -p 10000").await?;
 
     let hello_server = HelloServer::new(Box::new(HelloImpl{}));
 
     adapter.add("hello", Box::new(hello_server));
     adapter.activate().await?;
+    adapter.wait_for_deactivate().await;
     
     Ok(())
 }
\ No newline at end of file
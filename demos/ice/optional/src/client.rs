@@ -1,6 +1,8 @@
 use ice_rs::communicator::Communicator;
 
-mod gen;
+mod gen {
+    ice_rs::include_slice!();
+}
 use crate::gen::demo::{ContactDB,ContactDBPrx,NumberType};
 
 
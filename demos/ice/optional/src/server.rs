@@ -1,8 +1,11 @@
 use ice_rs::communicator::Communicator;
+use ice_rs::current::Current;
 use std::collections::HashMap;
 use async_trait::async_trait;
 
-mod gen;
+mod gen {
+    ice_rs::include_slice!();
+}
 use crate::gen::demo::{Contact, ContactDBServer, ContactDBI, NumberType};
 
 struct ContactDBImpl {
@@ -11,7 +14,7 @@ struct ContactDBImpl {
 
 #[async_trait]
 impl ContactDBI for ContactDBImpl {
-    async fn add_contact(&mut self, name: &String, r#type: Option<NumberType>, number: Option<String>, dial_group: Option<i32>, _context: Option<HashMap<String, String>>) {        
+    async fn add_contact(&mut self, name: &String, r#type: Option<NumberType>, number: Option<String>, dial_group: Option<i32>, _current: &Current) {        
         let contact = Contact {
             name: name.clone(),
             r#type: if let Some(_value) = r#type { r#type } else { Some(NumberType::HOME) },
@@ -21,7 +24,7 @@ impl ContactDBI for ContactDBImpl {
         self.data.insert(name.clone(), contact);
     }
 
-    async fn update_contact(&mut self, name: &String, r#type: Option<NumberType>, number: Option<String>, dial_group: Option<i32>, _context: Option<HashMap<String, String>>) {
+    async fn update_contact(&mut self, name: &String, r#type: Option<NumberType>, number: Option<String>, dial_group: Option<i32>, _current: &Current) {
         match self.data.get_mut(name) {
             Some(contact) => {
                 contact.r#type = if let Some(_value) = r#type { r#type } else { contact.r#type };
@@ -32,19 +35,19 @@ impl ContactDBI for ContactDBImpl {
         }
     }
 
-    async fn query(&mut self, name: &String, _context: Option<HashMap<String, String>>) -> Contact {
+    async fn query(&mut self, name: &String, _current: &Current) -> Contact {
         self.data.get(name).unwrap().clone()
     }
 
-    async fn query_number(&mut self, name: &String, _context: Option<HashMap<String, String>>) -> Option<String> {
+    async fn query_number(&mut self, name: &String, _current: &Current) -> Option<String> {
         self.data.get(name).unwrap().number.clone()
     }
 
-    async fn query_dialgroup(&mut self, name: &String, dial_group: &mut Option<i32>, _context: Option<HashMap<String, String>>) {
+    async fn query_dialgroup(&mut self, name: &String, dial_group: &mut Option<i32>, _current: &Current) {
         *dial_group = self.data.get(name).unwrap().dial_group.clone();
     }
 
-    async fn shutdown(&mut self, _context: Option<HashMap<String, String>>) {
+    async fn shutdown(&mut self, _current: &Current) {
         // todo!("impl shutdown")
         // current.adapter.getCommunicator().shutdown()
     }
@@ -53,12 +56,13 @@ impl ContactDBI for ContactDBImpl {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let comm = Communicator::new().await?;
-    let mut adapter = comm.create_object_adapter_with_endpoint("contactdb", "tcp -h localhost -p 10000").await?;
+    let adapter = comm.create_object_adapter_with_endpoint("contactdb", "tcp -h localhost -p 10000").await?;
 
     let server = ContactDBServer::new(Box::new(ContactDBImpl{data: HashMap::new()}));
 
     adapter.add("contactdb", Box::new(server));
     adapter.activate().await?;
+    adapter.wait_for_deactivate().await;
 
     // comm.wait_for_shutdown().await?;
 
@@ -4,7 +4,9 @@ use std::time;
 use termion;
 use termion::input::TermRead;
 
-mod gen;
+mod gen {
+    ice_rs::include_slice!();
+}
 use crate::gen::demo::{Hello,HelloPrx};
 
 fn menu() {
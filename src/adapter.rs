@@ -3,19 +3,267 @@ use crate::proxy_parser::*;
 use crate::iceobject::*;
 use crate::protocol::*;
 use crate::encoding::*;
+use crate::current::Current;
+use crate::proxy::Proxy;
+use crate::proxy_factory::ProxyFactory;
+use crate::initdata::InitializationData;
+use crate::interceptor::{DispatchInterceptor, Next};
+use crate::servant_locator::{Cookie, ServantLocator};
+use crate::mem::MemTransport;
+use crate::tcp::TcpTransport;
+use crate::ssl::SslTransport;
+use crate::transport::{ConnectionInfo, Transport};
+use crate::udp::{UdpTransport, udp_connection_info};
+use crate::ws::WsTransport;
+use futures::FutureExt;
+use std::any::Any;
 use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::net::TcpListener;
-use tokio::net::TcpStream;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
+/// The states of an adapter. Adapters are holding until they are activated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AdapterState {
+    Holding,
+    Active,
+    Deactivated,
+    Destroyed
+}
+
+/// Tracks the state of an adapter and its outstanding dispatches.
+struct Lifecycle {
+    status: Mutex<(AdapterState, usize)>,
+    changed: watch::Sender<()>,
+    // keeps the channel open, so sending never fails
+    receiver: watch::Receiver<()>
+}
+
+impl Lifecycle {
+    fn new() -> Lifecycle {
+        let (changed, receiver) = watch::channel(());
+        Lifecycle {
+            status: Mutex::new((AdapterState::Holding, 0)),
+            changed,
+            receiver
+        }
+    }
+
+    fn state(&self) -> AdapterState {
+        self.status.lock().unwrap().0
+    }
+
+    fn update(&self, update: impl FnOnce(&mut (AdapterState, usize))) {
+        update(&mut self.status.lock().unwrap());
+        let _ = self.changed.send(());
+    }
+
+    async fn wait_until(&self, condition: impl Fn(&(AdapterState, usize)) -> bool) {
+        let mut receiver = self.receiver.clone();
+        while !condition(&self.status.lock().unwrap()) {
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Waits while the adapter is holding and counts the dispatch as
+    /// outstanding until the returned guard is dropped.
+    async fn begin_dispatch(&self, name: &str) -> Result<DispatchGuard<'_>, ObjectAdapterDeactivatedException> {
+        self.wait_until(|status| status.0 != AdapterState::Holding).await;
+        let mut status = self.status.lock().unwrap();
+        match status.0 {
+            AdapterState::Active => {
+                status.1 += 1;
+                Ok(DispatchGuard { lifecycle: self })
+            }
+            _ => Err(ObjectAdapterDeactivatedException::new(name))
+        }
+    }
+}
+
+struct DispatchGuard<'a> {
+    lifecycle: &'a Lifecycle
+}
+
+impl Drop for DispatchGuard<'_> {
+    fn drop(&mut self) {
+        self.lifecycle.update(|status| status.1 -= 1);
+    }
+}
+
+pub type Servant = Arc<tokio::sync::Mutex<Box<dyn IceObjectServer + Send + Sync>>>;
+
+/// Wraps a servant so it can be shared between the adapter and a `ServantLocator`.
+pub fn make_servant(object: Box<dyn IceObjectServer + Send + Sync>) -> Servant {
+    Arc::new(tokio::sync::Mutex::new(object))
+}
+
+#[derive(Default)]
+struct ServantMap {
+    objects: BTreeMap<Identity, BTreeMap<String, Servant>>,
+    default_servants: BTreeMap<String, Servant>,
+    locators: BTreeMap<String, Arc<dyn ServantLocator>>
+}
+
+enum Located {
+    Servant(Servant, Option<(Arc<dyn ServantLocator>, Cookie)>),
+    ObjectNotExist,
+    FacetNotExist
+}
+
+enum Candidate {
+    Servant(Servant),
+    Locator(Arc<dyn ServantLocator>)
+}
+
+/// The `Dispatcher` holds the servants of an adapter. It is shared between
+/// the adapter and collocated proxies, which dispatch without a transport.
+#[derive(Clone)]
+pub struct Dispatcher {
+    name: String,
+    servants: Arc<Mutex<ServantMap>>,
+    interceptors: Arc<Mutex<Vec<Arc<dyn DispatchInterceptor>>>>,
+    lifecycle: Arc<Lifecycle>
+}
+
+impl Dispatcher {
+    fn new(name: &str) -> Dispatcher {
+        Dispatcher {
+            name: String::from(name),
+            servants: Arc::new(Mutex::new(ServantMap::default())),
+            interceptors: Arc::new(Mutex::new(Vec::new())),
+            lifecycle: Arc::new(Lifecycle::new())
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub (crate) fn ptr_eq(&self, other: &Dispatcher) -> bool {
+        Arc::ptr_eq(&self.servants, &other.servants)
+    }
 
+    /// Finds the servant for `current.id`. Like in Ice, the servant map is
+    /// checked first, then the default servant and servant locator of the
+    /// identity category, then those of the empty category.
+    async fn locate(&self, current: &Current) -> Result<Located, Box<dyn std::error::Error + Sync + Send>> {
+        let candidates = {
+            let servants = self.servants.lock().unwrap();
+            if let Some(facets) = servants.objects.get(&current.id) {
+                return Ok(match facets.get(&current.facet) {
+                    Some(servant) => Located::Servant(servant.clone(), None),
+                    None => Located::FacetNotExist
+                });
+            }
+            let mut categories = vec![current.id.category.as_str()];
+            if !current.id.category.is_empty() {
+                categories.push("");
+            }
+            let mut candidates = vec![];
+            for category in categories {
+                if let Some(servant) = servants.default_servants.get(category) {
+                    candidates.push(Candidate::Servant(servant.clone()));
+                }
+                if let Some(locator) = servants.locators.get(category) {
+                    candidates.push(Candidate::Locator(locator.clone()));
+                }
+            }
+            candidates
+        };
+
+        for candidate in candidates {
+            match candidate {
+                Candidate::Servant(servant) => return Ok(Located::Servant(servant, None)),
+                Candidate::Locator(locator) => {
+                    if let Some((servant, cookie)) = locator.locate(current).await? {
+                        return Ok(Located::Servant(servant, Some((locator, cookie))));
+                    }
+                }
+            }
+        }
+        Ok(Located::ObjectNotExist)
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.lifecycle.state() >= AdapterState::Deactivated
+    }
+
+    /// Dispatches a request to its servant. Requests are held while the
+    /// adapter is holding and fail once it is deactivated. Errors and panics
+    /// of interceptors and servants are replied as unknown exception.
+    pub async fn dispatch(&self, req: &RequestData, connection: &ConnectionInfo) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        let _guard = self.lifecycle.begin_dispatch(&self.name).await?;
+        let current = Current::new(&self.name, connection, req);
+        let result = match self.locate(&current).await {
+            Ok(Located::Servant(servant, located)) => {
+                let interceptors = self.interceptors.lock().unwrap().clone();
+                let result = AssertUnwindSafe(Next::new(&interceptors, &servant).run(req, &current)).catch_unwind().await
+                    .unwrap_or_else(|panic| Err(format!("Dispatch panicked: {}", panic_message(panic.as_ref())).into()));
+                match located {
+                    Some((locator, cookie)) => locator.finished(&current, &servant, cookie).await.and(result),
+                    None => result
+                }
+            }
+            Ok(Located::ObjectNotExist) => ReplyData::request_failed(req, 2),
+            Ok(Located::FacetNotExist) => ReplyData::request_failed(req, 3),
+            Err(e) => Err(e)
+        };
+        match result {
+            Ok(reply) => Ok(reply),
+            Err(e) => ReplyData::unknown_exception(req.request_id, &e.to_string())
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    match panic.downcast_ref::<&str>() {
+        Some(message) => String::from(*message),
+        None => panic.downcast_ref::<String>().cloned().unwrap_or_else(|| String::from("unknown panic"))
+    }
+}
+
+fn generate_uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    // version 4, variant 1
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+fn encode_reply(reply: &ReplyData) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+    let reply_bytes = reply.to_bytes()?;
+    let mut bytes = Header::new(2, 14 + reply_bytes.len() as i32).to_bytes()?;
+    bytes.extend(reply_bytes);
+    Ok(bytes)
+}
+
+struct AdapterInner {
+    name: String,
+    endpoint: Mutex<EndPointType>,
+    init_data: InitializationData,
+    dispatcher: Dispatcher,
+    listening: AtomicBool,
+    listener: Mutex<Option<JoinHandle<()>>>
+}
+
+/// The `Adapter` is a handle to an object adapter. Clones share the same
+/// adapter, so servants can be added and removed while it is active.
+#[derive(Clone)]
 pub struct Adapter {
-    endpoint: DirectProxyData,
-    objects: BTreeMap<String, Box<dyn IceObjectServer + Send + Sync>>
+    inner: Arc<AdapterInner>
 }
 
 impl Adapter {
-    pub fn with_endpoint(name: &str, endpoint: &str) -> Result<Adapter, Box<dyn std::error::Error + Sync + Send>> {
+    pub fn with_endpoint(name: &str, endpoint: &str, init_data: &InitializationData) -> Result<Adapter, Box<dyn std::error::Error + Sync + Send>> {
         let endpoint = parse_proxy_string(&format!("{}:{}", name, endpoint))?;
         let endpoint = match endpoint {
             ProxyStringType::DirectProxy(endpoint) => {
@@ -26,77 +274,1097 @@ impl Adapter {
             }
         };
 
-        Ok(Adapter{
-            endpoint,
-            objects: BTreeMap::new()
+        Ok(Adapter {
+            inner: Arc::new(AdapterInner {
+                name: String::from(name),
+                endpoint: Mutex::new(endpoint.endpoint),
+                init_data: init_data.clone(),
+                dispatcher: Dispatcher::new(name),
+                listening: AtomicBool::new(false),
+                listener: Mutex::new(None)
+            })
         })
     }
 
-    pub fn add(&mut self, ident: &str, object: Box<dyn IceObjectServer + Send + Sync>) {
-        self.objects.insert(String::from(ident), object);
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Returns the adapter endpoint. Once the adapter is activated, a
+    /// port 0 is replaced by the port the endpoint is bound to.
+    pub fn endpoint(&self) -> EndPointType {
+        self.inner.endpoint.lock().unwrap().clone()
+    }
+
+    fn set_bound_port(&self, port: u16) {
+        self.inner.endpoint.lock().unwrap().set_port(port as i32);
+    }
+
+    pub fn dispatcher(&self) -> Dispatcher {
+        self.inner.dispatcher.clone()
+    }
+
+    pub (crate) fn init_data(&self) -> &InitializationData {
+        &self.inner.init_data
+    }
+
+    pub fn add(&self, ident: &str, object: Box<dyn IceObjectServer + Send + Sync>) {
+        self.add_facet(ident, "", object);
+    }
+
+    /// Adds a servant under a new UUID identity and returns a proxy for it.
+    pub async fn add_with_uuid(&self, object: Box<dyn IceObjectServer + Send + Sync>) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        let ident = generate_uuid();
+        self.add(&ident, object);
+        self.create_proxy(&ident).await
     }
 
-    pub async fn activate(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
-        let listener = match &self.endpoint.endpoint {
+    /// Removes the object `ident` with all its facets and returns the
+    /// servant of the default facet.
+    pub fn remove(&self, ident: &str) -> Option<Servant> {
+        let mut servants = self.inner.dispatcher.servants.lock().unwrap();
+        servants.objects.remove(&Identity::new(ident)).and_then(|mut facets| facets.remove(""))
+    }
+
+    /// Returns the servant of the default facet of the object `ident`.
+    pub fn find(&self, ident: &str) -> Option<Servant> {
+        let servants = self.inner.dispatcher.servants.lock().unwrap();
+        servants.objects.get(&Identity::new(ident)).and_then(|facets| facets.get("").cloned())
+    }
+
+    /// Returns the servant for the object `proxy` points to.
+    pub fn find_by_proxy(&self, proxy: &Proxy) -> Option<Servant> {
+        self.find(&proxy.ident)
+    }
+
+    /// Creates a proxy for the object `ident` using the adapter endpoint.
+    pub async fn create_proxy(&self, ident: &str) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        if self.is_deactivated() {
+            return Err(Box::new(ObjectAdapterDeactivatedException::new(&self.inner.name)));
+        }
+        let data = DirectProxyData {
+            ident: String::from(ident),
+            endpoint: self.endpoint()
+        };
+        ProxyFactory::create_proxy(data, &self.inner.init_data, None).await
+    }
+
+    /// Adds a servant for `facet` of the object `ident`. The default facet is
+    /// the empty string.
+    pub fn add_facet(&self, ident: &str, facet: &str, object: Box<dyn IceObjectServer + Send + Sync>) {
+        let mut servants = self.inner.dispatcher.servants.lock().unwrap();
+        servants.objects.entry(Identity::new(ident)).or_default().insert(String::from(facet), make_servant(object));
+    }
+
+    /// Removes the servant for `facet` of the object `ident`. The object is
+    /// removed once it has no facets left.
+    pub fn remove_facet(&self, ident: &str, facet: &str) -> Option<Servant> {
+        let mut servants = self.inner.dispatcher.servants.lock().unwrap();
+        let id = Identity::new(ident);
+        let facets = servants.objects.get_mut(&id)?;
+        let servant = facets.remove(facet);
+        if facets.is_empty() {
+            servants.objects.remove(&id);
+        }
+        servant
+    }
+
+    /// Returns the servants of all facets of the object `ident`.
+    pub fn find_all_facets(&self, ident: &str) -> BTreeMap<String, Servant> {
+        self.inner.dispatcher.servants.lock().unwrap().objects.get(&Identity::new(ident)).cloned().unwrap_or_default()
+    }
+
+    /// Adds a servant for all identities of `category` that have no servant
+    /// of their own. The empty category matches any identity.
+    pub fn add_default_servant(&self, category: &str, object: Box<dyn IceObjectServer + Send + Sync>) {
+        self.inner.dispatcher.servants.lock().unwrap().default_servants.insert(String::from(category), make_servant(object));
+    }
+
+    pub fn remove_default_servant(&self, category: &str) -> Option<Servant> {
+        self.inner.dispatcher.servants.lock().unwrap().default_servants.remove(category)
+    }
+
+    /// Adds a locator that is asked for the servants of `category` that are
+    /// neither in the servant map nor served by a default servant.
+    pub fn add_servant_locator(&self, category: &str, locator: Arc<dyn ServantLocator>) {
+        self.inner.dispatcher.servants.lock().unwrap().locators.insert(String::from(category), locator);
+    }
+
+    pub fn remove_servant_locator(&self, category: &str) -> Option<Arc<dyn ServantLocator>> {
+        self.inner.dispatcher.servants.lock().unwrap().locators.remove(category)
+    }
+
+    /// Adds an interceptor that runs around every dispatch of this adapter.
+    /// Interceptors are called in the order they were added.
+    pub fn add_dispatch_interceptor(&self, interceptor: Arc<dyn DispatchInterceptor>) {
+        self.inner.dispatcher.interceptors.lock().unwrap().push(interceptor);
+    }
+
+    /// Starts accepting connections on the adapter endpoint and dispatching
+    /// requests. Returns once the endpoint is bound.
+    pub async fn activate(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        if self.is_deactivated() {
+            return Err(Box::new(ObjectAdapterDeactivatedException::new(&self.inner.name)));
+        }
+        if !self.inner.listening.swap(true, Ordering::SeqCst) {
+            match self.listen().await {
+                Ok(handle) => {
+                    *self.inner.listener.lock().unwrap() = Some(handle);
+                    crate::collocation::update_endpoint(&self.inner.dispatcher, self.endpoint());
+                }
+                Err(e) => {
+                    self.inner.listening.store(false, Ordering::SeqCst);
+                    return Err(e);
+                }
+            }
+        }
+        self.inner.dispatcher.lifecycle.update(|status| {
+            if status.0 == AdapterState::Holding {
+                status.0 = AdapterState::Active;
+            }
+        });
+        Ok(())
+    }
+
+    /// Holds all requests until the adapter is activated again.
+    pub fn hold(&self) {
+        self.inner.dispatcher.lifecycle.update(|status| {
+            if status.0 == AdapterState::Active {
+                status.0 = AdapterState::Holding;
+            }
+        });
+    }
+
+    /// Stops accepting connections, closes the open ones and waits for
+    /// the outstanding dispatches. Requests that arrive later fail with
+    /// an `ObjectAdapterDeactivatedException`.
+    pub async fn deactivate(&self) {
+        self.inner.dispatcher.lifecycle.update(|status| {
+            if status.0 < AdapterState::Deactivated {
+                status.0 = AdapterState::Deactivated;
+            }
+        });
+        crate::collocation::unregister(&self.inner.dispatcher);
+        let listener = self.inner.listener.lock().unwrap().take();
+        if let Some(listener) = listener {
+            let _ = listener.await;
+        }
+        self.wait_for_deactivate().await;
+    }
+
+    /// Waits until the adapter is deactivated and all outstanding
+    /// dispatches are finished.
+    pub async fn wait_for_deactivate(&self) {
+        self.inner.dispatcher.lifecycle.wait_until(|status| status.0 >= AdapterState::Deactivated && status.1 == 0).await;
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.inner.dispatcher.is_deactivated()
+    }
+
+    /// Deactivates the adapter and releases all its servants.
+    pub async fn destroy(&self) {
+        self.deactivate().await;
+        self.inner.dispatcher.lifecycle.update(|status| status.0 = AdapterState::Destroyed);
+        *self.inner.dispatcher.servants.lock().unwrap() = ServantMap::default();
+        self.inner.dispatcher.interceptors.lock().unwrap().clear();
+    }
+
+    /// Resolves to `None` instead of the result of `future` once the
+    /// adapter is deactivated.
+    async fn until_deactivated<T>(&self, future: impl Future<Output = T>) -> Option<T> {
+        tokio::select! {
+            result = future => Some(result),
+            _ = self.inner.dispatcher.lifecycle.wait_until(|status| status.0 >= AdapterState::Deactivated) => None
+        }
+    }
+
+    async fn listen(&self) -> Result<JoinHandle<()>, Box<dyn std::error::Error + Sync + Send>> {
+        let adapter = self.clone();
+        let handle = match &self.endpoint() {
             EndPointType::TCP(data) => {
-                TcpListener::bind(format!("{}:{}", data.host, data.port)).await?
+                let listener = TcpListener::bind(format!("{}:{}", data.host, data.port)).await?;
+                self.set_bound_port(listener.local_addr()?.port());
+                tokio::spawn(async move {
+                    while let Some(Ok((socket, _))) = adapter.until_deactivated(listener.accept()).await {
+                        let adapter = adapter.clone();
+                        tokio::spawn(async move {
+                            let mut transport = TcpTransport::from(socket);
+                            adapter.handle_socket(&mut transport).await
+                        });
+                    }
+                })
+            },
+            EndPointType::SSL(data) => {
+                let acceptor = Arc::new(SslTransport::acceptor(self.inner.init_data.properties())?);
+                let listener = TcpListener::bind(format!("{}:{}", data.host, data.port)).await?;
+                self.set_bound_port(listener.local_addr()?.port());
+                tokio::spawn(async move {
+                    while let Some(Ok((socket, _))) = adapter.until_deactivated(listener.accept()).await {
+                        let adapter = adapter.clone();
+                        let acceptor = acceptor.clone();
+                        tokio::spawn(async move {
+                            let mut transport = SslTransport::accept(&acceptor, socket, &adapter.inner.name, &adapter.inner.init_data).await?;
+                            adapter.handle_socket(&mut transport).await
+                        });
+                    }
+                })
+            },
+            EndPointType::WS(data) => {
+                let listener = TcpListener::bind(format!("{}:{}", data.host, data.port)).await?;
+                self.set_bound_port(listener.local_addr()?.port());
+                tokio::spawn(async move {
+                    while let Some(Ok((socket, _))) = adapter.until_deactivated(listener.accept()).await {
+                        let adapter = adapter.clone();
+                        tokio::spawn(async move {
+                            let mut transport = WsTransport::accept(TcpTransport::from(socket), &adapter.inner.init_data).await?;
+                            adapter.handle_socket(&mut transport).await
+                        });
+                    }
+                })
+            },
+            EndPointType::WSS(data) => {
+                let acceptor = Arc::new(SslTransport::acceptor(self.inner.init_data.properties())?);
+                let listener = TcpListener::bind(format!("{}:{}", data.host, data.port)).await?;
+                self.set_bound_port(listener.local_addr()?.port());
+                tokio::spawn(async move {
+                    while let Some(Ok((socket, _))) = adapter.until_deactivated(listener.accept()).await {
+                        let adapter = adapter.clone();
+                        let acceptor = acceptor.clone();
+                        tokio::spawn(async move {
+                            let transport = SslTransport::accept(&acceptor, socket, &adapter.inner.name, &adapter.inner.init_data).await?;
+                            let mut transport = WsTransport::accept(transport, &adapter.inner.init_data).await?;
+                            adapter.handle_socket(&mut transport).await
+                        });
+                    }
+                })
+            },
+            EndPointType::MEM(data) => {
+                let mut listener = MemTransport::bind(&data.name)?;
+                tokio::spawn(async move {
+                    while let Some(Ok(mut transport)) = adapter.until_deactivated(listener.accept()).await {
+                        let adapter = adapter.clone();
+                        tokio::spawn(async move {
+                            adapter.handle_socket(&mut transport).await
+                        });
+                    }
+                })
+            },
+            EndPointType::UDP(data) => {
+                let socket = UdpTransport::bind(data).await?;
+                self.set_bound_port(socket.local_addr()?.port());
+                tokio::spawn(async move {
+                    let mut buffer = vec![0u8; 65535];
+                    while let Some(Ok((size, remote))) = adapter.until_deactivated(socket.recv_from(&mut buffer)).await {
+                        let connection = udp_connection_info(&socket, remote, true);
+                        // a malformed datagram must not stop the adapter
+                        let _ = adapter.handle_datagram(&buffer[0..size], &connection).await;
+                    }
+                })
             },
             _ => {
                 return Err(Box::new(ProtocolError::new("Direct proxy required for endpoint")))
             }
         };
-
-        loop {
-            let (mut socket, _) = listener.accept().await?;
-            self.handle_socket(&mut socket).await?;
-        }
+        Ok(handle)
     }
 
-    pub async fn handle_socket(&mut self, stream: &mut TcpStream) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    /// Serves the requests of one connection until the peer closes it or
+    /// the adapter is deactivated. Every request is dispatched by its own
+    /// task, so replies are written in the order they complete.
+    pub async fn handle_socket<T: Transport + Unpin>(&self, stream: &mut T) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
         let mut buffer = [0u8; 4096];
+        let connection = stream.connection_info();
 
         let header = Header::new(3, 14);
-        let mut bytes = header.to_bytes()?;
-        stream.write(&mut bytes).await?;
+        let bytes = header.to_bytes()?;
+        stream.write_all(&bytes).await?;
+
+        // the dispatch tasks send back the encoded reply, or `None` if the
+        // request failed because the adapter was deactivated meanwhile
+        let (replies, mut pending) = unbounded_channel::<Option<Vec<u8>>>();
+        let mut outstanding = 0;
+        let mut reading = true;
+        let mut received = Vec::new();
         loop {
-            let bytes = stream.read(&mut buffer).await?;
-            let mut read = 0;
-            let header = Header::from_bytes(&buffer[0..bytes], &mut read)?;
-            match header.message_type {
-                0 => {
-                    let req = RequestData::from_bytes(&buffer[read as usize..bytes], &mut read)?;
-                    let reply = if let Some(object) = self.objects.get_mut(&req.id.name) {
-                        match object.handle_request(&req).await {
-                            Ok(reply) => reply,
-                            Err(e) => {
-                                ReplyData {
-                                    request_id: req.request_id,
-                                    status: 1,
-                                    body: Encapsulation::from(e.to_string().as_bytes().to_vec())
-                                }
-                            }
-                        }
-                    } else {
-                        ReplyData {
-                            request_id: req.request_id,
-                            status: 1,
-                            body: Encapsulation::from(String::from("Object not found").as_bytes().to_vec())
+            if !reading && outstanding == 0 {
+                return self.close_connection(stream).await;
+            }
+            tokio::select! {
+                Some(reply) = pending.recv(), if outstanding > 0 => {
+                    outstanding -= 1;
+                    match reply {
+                        Some(bytes) => stream.write_all(&bytes).await?,
+                        None => reading = false
+                    }
+                }
+                read = self.until_deactivated(stream.read(&mut buffer)), if reading => {
+                    let bytes = match read {
+                        Some(bytes) => bytes?,
+                        None => {
+                            reading = false;
+                            continue;
                         }
                     };
-        
-                    let header = Header::new(2, reply.body.size + 19);
-                    let mut return_buffer = header.to_bytes()?;
-                    return_buffer.extend(reply.to_bytes()?);
-                    stream.write(&mut return_buffer).await?;                
+                    if bytes == 0 {
+                        return Ok(())
+                    }
+                    // a read may end within a message or contain several of them
+                    received.extend(&buffer[0..bytes]);
+                    while received.len() >= 14 {
+                        let mut read = 0;
+                        let header = Header::from_bytes(&received, &mut read)?;
+                        let size = header.message_size as usize;
+                        if size < 14 {
+                            return Err(Box::new(ProtocolError::new("Invalid message size")))
+                        }
+                        if received.len() < size {
+                            break;
+                        }
+                        let message = received.drain(0..size).collect::<Vec<u8>>();
+                        match header.message_type {
+                            0 => {
+                                let req = RequestData::from_bytes(&message[read as usize..], &mut read)?;
+                                let request_id = req.request_id;
+                                let adapter = self.clone();
+                                let connection = connection.clone();
+                                let replies = replies.clone();
+                                outstanding += 1;
+                                let dispatch = tokio::spawn(async move {
+                                    adapter.dispatch(&req, &connection).await
+                                });
+                                // every dispatch is answered, even if its task panicked
+                                tokio::spawn(async move {
+                                    let reply = match dispatch.await {
+                                        Ok(Ok(reply)) => encode_reply(&reply).ok(),
+                                        Ok(Err(_)) => None,
+                                        Err(e) => ReplyData::unknown_exception(request_id, &format!("Dispatch failed: {}", e))
+                                            .and_then(|reply| encode_reply(&reply)).ok()
+                                    };
+                                    let _ = replies.send(reply);
+                                });
+                            }
+                            4 => {
+                                return Ok(())
+                            }
+                            _ => {
+                                return Err(Box::new(ProtocolError::new("Unsupported message type")))
+                            }
+                        }
+                    }
                 }
-                4 => {
-                    return Ok(())
+            }
+        }
+    }
+
+    async fn close_connection<T: Transport + Unpin>(&self, stream: &mut T) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        let bytes = Header::new(4, 14).to_bytes()?;
+        stream.write_all(&bytes).await?;
+        Ok(())
+    }
+
+    /// Dispatches the requests of a datagram. Datagrams are never answered.
+    pub async fn handle_datagram(&self, bytes: &[u8], connection: &ConnectionInfo) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        let mut read = 0;
+        let header = Header::from_bytes(bytes, &mut read)?;
+        match header.message_type {
+            0 => {
+                let req = RequestData::from_bytes(&bytes[read as usize..], &mut read)?;
+                self.dispatch(&req, connection).await?;
+            }
+            1 => {
+                let count = i32::from_bytes(&bytes[read as usize..], &mut read)?;
+                for _ in 0..count {
+                    let req = RequestData::from_batch_bytes(&bytes[read as usize..], &mut read)?;
+                    self.dispatch(&req, connection).await?;
                 }
+            }
+            _ => {
+                return Err(Box::new(ProtocolError::new("Unsupported datagram message type")))
+            }
+        }
+        Ok(())
+    }
+
+    async fn dispatch(&self, req: &RequestData, connection: &ConnectionInfo) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        self.inner.dispatcher.dispatch(req, connection).await
+    }
+}
+
+impl Drop for AdapterInner {
+    fn drop(&mut self) {
+        crate::collocation::unregister(&self.dispatcher);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::communicator::Communicator;
+    use crate::proxy::TypedProxy;
+    use crate::interceptor::ClientInterceptor;
+    use std::collections::HashMap;
+    use crate::properties::Properties;
+    use crate::ssl::CertificateVerifier;
+
+    struct ConnectionServer {}
+
+    #[async_trait]
+    impl IceObjectServer for ConnectionServer {
+        async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            let mut bytes = current.connection.transport.to_bytes()?;
+            bytes.extend((current.connection.peer_certificates.len() as i32).to_bytes()?);
+            Ok(ReplyData {
+                request_id: request.request_id,
+                status: 0,
+                body: Encapsulation::from(bytes)
+            })
+        }
+    }
+
+    struct RecordingServer {
+        operations: Arc<std::sync::Mutex<Vec<String>>>
+    }
+
+    #[async_trait]
+    impl IceObjectServer for RecordingServer {
+        async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            self.operations.lock().unwrap().push(format!("{}:{}", current.connection.transport, current.operation));
+            Ok(ReplyData {
+                request_id: request.request_id,
+                status: 0,
+                body: Encapsulation::empty()
+            })
+        }
+    }
+
+    struct ContextServer {}
+
+    #[async_trait]
+    impl IceObjectServer for ContextServer {
+        async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            let value = current.context.get("trace").cloned().unwrap_or_default();
+            Ok(ReplyData {
+                request_id: request.request_id,
+                status: 0,
+                body: Encapsulation::from(value.to_bytes()?)
+            })
+        }
+    }
+
+    struct TraceInterceptor {
+        calls: Arc<std::sync::Mutex<Vec<String>>>
+    }
+
+    #[async_trait]
+    impl ClientInterceptor for TraceInterceptor {
+        async fn before_request(&self, request: &mut RequestData) -> Result<Option<ReplyData>, Box<dyn std::error::Error + Sync + Send>> {
+            match request.operation.as_str() {
+                "cached" => Ok(Some(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from("cache").to_bytes()?)
+                })),
+                "denied" => Err(Box::new(ProtocolError::new("Access denied"))),
                 _ => {
-                    return Err(Box::new(ProtocolError::new("Unsupported message type")))
+                    request.context.insert(String::from("trace"), String::from("trace-id"));
+                    Ok(None)
                 }
             }
-            
         }
+
+        async fn after_reply(&self, request: &RequestData, result: Result<&ReplyData, &(dyn std::error::Error + Sync + Send)>, _elapsed: std::time::Duration) {
+            self.calls.lock().unwrap().push(format!("{}:{}", request.operation, result.is_ok()));
+        }
+    }
+
+    struct IdentityServer {}
+
+    #[async_trait]
+    impl IceObjectServer for IdentityServer {
+        async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            Ok(ReplyData {
+                request_id: request.request_id,
+                status: 0,
+                body: Encapsulation::from(current.id.to_string().to_bytes()?)
+            })
+        }
+    }
+
+    struct DatabaseLocator {
+        finished: Arc<std::sync::Mutex<Vec<String>>>
+    }
+
+    #[async_trait]
+    impl ServantLocator for DatabaseLocator {
+        async fn locate(&self, current: &Current) -> Result<Option<(Servant, Cookie)>, Box<dyn std::error::Error + Sync + Send>> {
+            if current.id.name.starts_with("row") {
+                Ok(Some((make_servant(Box::new(IdentityServer {})), Box::new(current.id.name.clone()))))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn finished(&self, _current: &Current, _servant: &Servant, cookie: Cookie) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+            self.finished.lock().unwrap().push(*cookie.downcast::<String>().expect("Unexpected cookie"));
+            Ok(())
+        }
+    }
+
+    struct AmdServer {}
+
+    #[async_trait]
+    impl IceObjectServer for AmdServer {
+        async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            Ok(ReplyData {
+                request_id: request.request_id,
+                status: 0,
+                body: Encapsulation::from(current.operation.to_bytes()?)
+            })
+        }
+
+        async fn handle_request_deferred(&mut self, request: &RequestData, current: &Current) -> Result<Option<DeferredReply>, Box<dyn std::error::Error + Sync + Send>> {
+            let request_id = request.request_id;
+            match current.operation.as_str() {
+                "slow" => {},
+                "broken" => return Ok(Some(Box::pin(async move { Err("deferred failure".into()) }))),
+                _ => return Ok(None)
+            }
+            Ok(Some(Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                Ok(ReplyData {
+                    request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from("slow").to_bytes()?)
+                })
+            })))
+        }
+    }
+
+    struct AuthInterceptor {}
+
+    #[async_trait]
+    impl DispatchInterceptor for AuthInterceptor {
+        async fn dispatch(&self, request: &RequestData, current: &Current, next: Next<'_>) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            match current.context.get("token") {
+                Some(token) if token == "secret" => next.run(request, current).await,
+                _ => Err(Box::new(ProtocolError::new("Not authenticated")))
+            }
+        }
+    }
+
+    struct PanicInterceptor {}
+
+    #[async_trait]
+    impl DispatchInterceptor for PanicInterceptor {
+        async fn dispatch(&self, request: &RequestData, current: &Current, next: Next<'_>) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            if request.operation == "panic" {
+                panic!("interceptor failed");
+            }
+            next.run(request, current).await
+        }
+    }
+
+    struct PanicServer {}
+
+    #[async_trait]
+    impl IceObjectServer for PanicServer {
+        async fn handle_request(&mut self, _request: &RequestData, _current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            panic!("servant failed");
+        }
+    }
+
+    struct PanicLocator {}
+
+    #[async_trait]
+    impl ServantLocator for PanicLocator {
+        async fn locate(&self, _current: &Current) -> Result<Option<(Servant, Cookie)>, Box<dyn std::error::Error + Sync + Send>> {
+            panic!("locator failed");
+        }
+    }
+
+    struct UppercaseInterceptor {}
+
+    #[async_trait]
+    impl DispatchInterceptor for UppercaseInterceptor {
+        async fn dispatch(&self, request: &RequestData, current: &Current, next: Next<'_>) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+            let mut reply = next.run(request, current).await?;
+            let mut read = 0;
+            let value = String::from_bytes(&reply.body.data, &mut read)?;
+            reply.body = Encapsulation::from(value.to_uppercase().to_bytes()?);
+            Ok(reply)
+        }
+    }
+
+    struct RejectVerifier {}
+
+    impl CertificateVerifier for RejectVerifier {
+        fn verify(&self, _info: &ConnectionInfo) -> bool {
+            false
+        }
+    }
+
+    fn ssl_properties(cert_file: &str) -> Properties {
+        let mut properties = Properties::new();
+        properties.set("IceSSL.DefaultDir", "tests/certs");
+        properties.set("IceSSL.CAs", "cacert.pem");
+        properties.set("IceSSL.CertFile", cert_file);
+        properties.set("IceSSL.Password", "password");
+        properties
+    }
+
+    async fn start_adapter(endpoint: &str, properties: &Properties) -> Adapter {
+        let adapter = Adapter::with_endpoint("test", endpoint, &InitializationData::with_properties(properties)).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+        adapter
+    }
+
+    async fn connect(proxy_string: &str, init_data: &InitializationData) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        let data = match parse_proxy_string(proxy_string)? {
+            ProxyStringType::DirectProxy(data) => data,
+            _ => panic!("Expected direct proxy")
+        };
+        ProxyFactory::create_proxy(data, init_data, None).await
+    }
+
+    /// Connects to the object `test` on the bound endpoint of `adapter`.
+    async fn connect_to(adapter: &Adapter, init_data: &InitializationData) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        let data = DirectProxyData {
+            ident: String::from("test"),
+            endpoint: adapter.endpoint()
+        };
+        ProxyFactory::create_proxy(data, init_data, None).await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_ssl_endpoint() {
+        let adapter = start_adapter("ssl -h 127.0.0.1 -p 0", &ssl_properties("server.p12")).await;
+        assert_ne!(0, adapter.endpoint().port());
+
+        let mut client_properties = ssl_properties("client.p12");
+        client_properties.set("IceSSL.CheckCertName", "1");
+        let mut proxy = connect_to(&adapter, &InitializationData::with_properties(&client_properties)).await.expect("Cannot connect to adapter");
+
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        let transport = String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport");
+        let certificates = i32::from_bytes(&reply.body.data[read as usize..], &mut read).expect("Cannot decode certificates");
+        assert_eq!("ssl", transport);
+        assert!(certificates > 0);
     }
-}
\ No newline at end of file
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_ws_endpoint() {
+        let adapter = start_adapter("ws -h 127.0.0.1 -p 0 -r /ice", &Properties::new()).await;
+
+        let mut proxy = connect_to(&adapter, &InitializationData::new()).await.expect("Cannot connect to adapter");
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("ws", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_wss_endpoint() {
+        let adapter = start_adapter("wss -h 127.0.0.1 -p 0", &ssl_properties("server.p12")).await;
+
+        let init_data = InitializationData::with_properties(&ssl_properties("client.p12"));
+        let mut proxy = connect_to(&adapter, &init_data).await.expect("Cannot connect to adapter");
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        let transport = String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport");
+        let certificates = i32::from_bytes(&reply.body.data[read as usize..], &mut read).expect("Cannot decode certificates");
+        assert_eq!("wss", transport);
+        assert!(certificates > 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mem_endpoint() {
+        assert!(connect("test:mem -n memtest", &InitializationData::new()).await.is_err());
+        let _adapter = start_adapter("mem -n memtest", &Properties::new()).await;
+
+        let mut proxy = connect("test:mem -n memtest", &InitializationData::new()).await.expect("Cannot connect to adapter");
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("mem", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_collocated_invocation() {
+        let adapter = Adapter::with_endpoint("collocated", "mem -n colloctest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut properties = Properties::new();
+        properties.set("Ice.Default.CollocationOptimized", "0");
+        let mut proxy = connect("test:mem -n colloctest", &InitializationData::with_properties(&properties)).await.expect("Cannot connect to adapter");
+        assert!(proxy.collocated.is_none());
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("mem", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport"));
+
+        // collocated requests are dispatched without any connection
+        let mut proxy = connect("test:mem -n colloctest", &InitializationData::new()).await.expect("Cannot create proxy");
+        assert!(proxy.collocated.is_some());
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport"));
+
+        let mut proxy = proxy.ice_collocation_optimized(false).await.expect("Cannot connect to adapter");
+        assert!(proxy.collocated.is_none());
+        let reply = proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("mem", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode transport"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_collocation_scope() {
+        crate::communicator::INITDATA.lock().unwrap().properties_as_mut().set("scoped.AdapterId", "ScopedAdapter");
+        let mut first = Communicator::new().await.expect("Cannot create communicator");
+        let mut second = Communicator::new().await.expect("Cannot create communicator");
+        let adapter = first.create_object_adapter_with_endpoint("scoped", "mem -n scopetest").await.expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        // indirect proxies are resolved by the configured adapter id, not the adapter name
+        let proxy = first.string_to_proxy("test@ScopedAdapter").await.expect("Cannot create proxy");
+        assert!(proxy.collocated.is_some());
+        assert!(first.string_to_proxy("test@scoped").await.is_err());
+        let proxy = first.string_to_proxy("test:mem -n scopetest").await.expect("Cannot create proxy");
+        assert!(proxy.collocated.is_some());
+
+        // adapters of another communicator are reached through their transport
+        assert!(second.string_to_proxy("test@ScopedAdapter").await.is_err());
+        let proxy = second.string_to_proxy("test:mem -n scopetest").await.expect("Cannot connect to adapter");
+        assert!(proxy.collocated.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_typed_proxy_connect() {
+        crate::communicator::INITDATA.lock().unwrap().properties_as_mut().set("typed.AdapterId", "TypedAdapter");
+        let mut communicator = Communicator::new().await.expect("Cannot create communicator");
+        let adapter = communicator.create_object_adapter_with_endpoint("typed", "tcp -h 127.0.0.1 -p 0").await.expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut data = LocatorResult::default();
+        assert!(TypedProxy::<Proxy>::new(data.clone()).connect(&mut communicator).await.is_err());
+
+        // endpoints are tried in order until one accepts the connection
+        data.proxy_data.id = Identity::new("test");
+        data.endpoints = vec![
+            EndPointType::TCP(EndpointData { host: String::from("127.0.0.1"), port: 1, timeout: 60000, compress: false }),
+            adapter.endpoint()
+        ];
+        let proxy: Proxy = TypedProxy::new(data.clone()).connect(&mut communicator).await.expect("Cannot connect to endpoints");
+        assert_eq!("test", proxy.ident);
+
+        // a proxy without endpoints is resolved by its adapter id
+        data.endpoints = vec![];
+        data.adapter_id = String::from("TypedAdapter");
+        let proxy: Proxy = TypedProxy::new(data).connect(&mut communicator).await.expect("Cannot connect to adapter");
+        assert!(proxy.collocated.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_client_interceptor() {
+        let adapter = Adapter::with_endpoint("intercepted", "mem -n intercepttest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ContextServer {}));
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let calls = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut init_data = InitializationData::new();
+        init_data.add_client_interceptor(Arc::new(TraceInterceptor { calls: calls.clone() }));
+        let mut proxy = connect("test:mem -n intercepttest", &init_data).await.expect("Cannot create proxy");
+
+        let mut read = 0;
+        let reply = proxy.dispatch::<ProtocolError>("echo", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        assert_eq!("trace-id", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode context"));
+
+        let mut read = 0;
+        let reply = proxy.dispatch::<ProtocolError>("cached", 0, &Encapsulation::empty(), None).await.expect("Request failed");
+        assert_eq!("cache", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode reply"));
+
+        assert!(proxy.dispatch::<ProtocolError>("denied", 0, &Encapsulation::empty(), None).await.is_err());
+        assert_eq!(vec!["echo:true", "cached:true", "denied:false"], *calls.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dispatch_interceptor() {
+        let adapter = Adapter::with_endpoint("dispatchintercepted", "mem -n dispatchintercepttest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ContextServer {}));
+        adapter.add_dispatch_interceptor(Arc::new(AuthInterceptor {}));
+        adapter.add_dispatch_interceptor(Arc::new(UppercaseInterceptor {}));
+        adapter.add_dispatch_interceptor(Arc::new(PanicInterceptor {}));
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = connect("test:mem -n dispatchintercepttest", &InitializationData::new()).await.expect("Cannot create proxy");
+        let error = proxy.dispatch::<ProtocolError>("echo", 0, &Encapsulation::empty(), None).await.expect_err("Request was not rejected");
+        let error = error.downcast_ref::<RemoteException>().expect("Expected a RemoteException");
+        assert_eq!("ProtocolError: Not authenticated", error.cause);
+
+        let mut context = HashMap::new();
+        context.insert(String::from("token"), String::from("secret"));
+        context.insert(String::from("trace"), String::from("trace-id"));
+        let reply = proxy.dispatch::<ProtocolError>("echo", 0, &Encapsulation::empty(), Some(context.clone())).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("TRACE-ID", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode context"));
+
+        // a panic in the dispatch chain is replied as unknown exception
+        let error = proxy.dispatch::<ProtocolError>("panic", 0, &Encapsulation::empty(), Some(context.clone())).await.expect_err("Panic was not reported");
+        let error = error.downcast_ref::<RemoteException>().expect("Expected a RemoteException");
+        assert_eq!("Dispatch panicked: interceptor failed", error.cause);
+        let reply = proxy.dispatch::<ProtocolError>("echo", 0, &Encapsulation::empty(), Some(context)).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("TRACE-ID", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode context"));
+    }
+
+    async fn call(proxy: &mut Proxy, ident: &str) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let request = proxy.create_request(ident, "info", 0, &Encapsulation::empty(), None);
+        let reply = proxy.make_request::<ProtocolError>(&request).await?;
+        let mut read = 0;
+        String::from_bytes(&reply.body.data, &mut read)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_servant_locator() {
+        let finished = Arc::new(std::sync::Mutex::new(vec![]));
+        let adapter = Adapter::with_endpoint("located", "mem -n locatortest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("users/admin", Box::new(ConnectionServer {}));
+        adapter.add_default_servant("users", Box::new(IdentityServer {}));
+        adapter.add_servant_locator("db", Arc::new(DatabaseLocator { finished: finished.clone() }));
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = connect("test:mem -n locatortest", &InitializationData::new()).await.expect("Cannot create proxy");
+
+        // the servant map matches name and category
+        assert_eq!("", call(&mut proxy, "users/admin").await.expect("Request failed"));
+        assert_eq!("users/guest", call(&mut proxy, "users/guest").await.expect("Request failed"));
+        assert_eq!("db/row1", call(&mut proxy, "db/row1").await.expect("Request failed"));
+        let error = call(&mut proxy, "db/other").await.err().expect("Unknown object found");
+        let error = error.downcast_ref::<ObjectNotExistException>().expect("Expected ObjectNotExistException");
+        assert_eq!("db", error.id.category);
+        assert!(call(&mut proxy, "admin").await.err().expect("Unknown object found").downcast_ref::<ObjectNotExistException>().is_some());
+        assert_eq!(vec!["row1"], *finished.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_facets() {
+        let adapter = Adapter::with_endpoint("faceted", "mem -n facettest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        adapter.add_facet("test", "admin", Box::new(IdentityServer {}));
+        assert_eq!(vec!["", "admin"], adapter.find_all_facets("test").keys().collect::<Vec<_>>());
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = connect("test:mem -n facettest", &InitializationData::new()).await.expect("Cannot create proxy");
+        let mut request = proxy.create_request("test", "info", 0, &Encapsulation::empty(), None);
+        request.facet = vec![String::from("admin")];
+        let reply = proxy.make_request::<ProtocolError>(&request).await.expect("Request failed");
+        let mut read = 0;
+        assert_eq!("test", String::from_bytes(&reply.body.data, &mut read).expect("Cannot decode identity"));
+
+        request.facet = vec![String::from("v2")];
+        let error = proxy.make_request::<ProtocolError>(&request).await.err().expect("Unknown facet found");
+        assert_eq!("v2", error.downcast_ref::<FacetNotExistException>().expect("Expected FacetNotExistException").facet);
+
+        assert!(adapter.remove_facet("test", "admin").is_some());
+        request.facet = vec![String::from("admin")];
+        assert!(proxy.make_request::<ProtocolError>(&request).await.err().expect("Removed facet found").downcast_ref::<FacetNotExistException>().is_some());
+        assert!(adapter.remove_facet("test", "").is_some());
+        request.facet = vec![];
+        assert!(proxy.make_request::<ProtocolError>(&request).await.err().expect("Removed object found").downcast_ref::<ObjectNotExistException>().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dynamic_servants() {
+        let adapter = Adapter::with_endpoint("dynamic", "mem -n dynamictest", &InitializationData::new()).expect("Cannot create adapter");
+        crate::collocation::register(&adapter);
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = adapter.add_with_uuid(Box::new(IdentityServer {})).await.expect("Cannot add servant");
+        assert!(adapter.find_by_proxy(&proxy).is_some());
+        let ident = proxy.ident.clone();
+        assert_eq!(ident, call(&mut proxy, &ident).await.expect("Request failed"));
+
+        assert!(adapter.remove(&ident).is_some());
+        assert!(adapter.find(&ident).is_none());
+        assert!(call(&mut proxy, &ident).await.err().expect("Removed object found").downcast_ref::<ObjectNotExistException>().is_some());
+
+        adapter.add("session", Box::new(IdentityServer {}));
+        let mut proxy = adapter.create_proxy("session").await.expect("Cannot create proxy");
+        assert_eq!("session", call(&mut proxy, "session").await.expect("Request failed"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_adapter_states() {
+        let adapter = Adapter::with_endpoint("lifecycle", "mem -n lifecycletest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(IdentityServer {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+        let mut proxy = connect("test:mem -n lifecycletest", &InitializationData::new()).await.expect("Cannot connect to adapter");
+        crate::collocation::register(&adapter);
+        let mut collocated = adapter.create_proxy("test").await.expect("Cannot create proxy");
+        assert!(collocated.collocated.is_some());
+
+        // held requests are dispatched once the adapter is active again
+        adapter.hold();
+        let mut request = tokio::spawn(async move { call(&mut proxy, "test").await.map(|ident| (ident, proxy)) });
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(100), &mut request).await.is_err());
+        adapter.activate().await.expect("Cannot activate adapter");
+        let (ident, _proxy) = request.await.expect("Request task failed").expect("Request failed");
+        assert_eq!("test", ident);
+        adapter.deactivate().await;
+        assert!(adapter.is_deactivated());
+        adapter.wait_for_deactivate().await;
+        let error = call(&mut collocated, "test").await.err().expect("Deactivated adapter dispatched request");
+        assert!(error.downcast_ref::<ObjectAdapterDeactivatedException>().is_some());
+        assert!(adapter.activate().await.err().expect("Deactivated adapter activated").downcast_ref::<ObjectAdapterDeactivatedException>().is_some());
+        assert!(connect("test:mem -n lifecycletest", &InitializationData::new()).await.is_err());
+        adapter.destroy().await;
+        assert!(adapter.find("test").is_none());
+    }
+
+    async fn read_message(stream: &mut MemTransport) -> (Header, Vec<u8>) {
+        let mut bytes = vec![0u8; 14];
+        stream.read_exact(&mut bytes).await.expect("Cannot read header");
+        let header = Header::from_bytes(&bytes, &mut 0).expect("Cannot decode header");
+        let mut body = vec![0u8; header.message_size as usize - 14];
+        stream.read_exact(&mut body).await.expect("Cannot read message");
+        (header, body)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_amd_replies_out_of_order() {
+        let adapter = Adapter::with_endpoint("amd", "mem -n amdtest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(AmdServer {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut stream = MemTransport::new("amdtest").await.expect("Cannot connect to adapter");
+        assert_eq!(3, read_message(&mut stream).await.0.message_type);
+        for (request_id, operation) in vec![(1, "slow"), (2, "fast"), (3, "broken")] {
+            let request = RequestData {
+                request_id,
+                id: Identity::new("test"),
+                facet: vec![],
+                operation: String::from(operation),
+                mode: 0,
+                context: std::collections::HashMap::new(),
+                params: Encapsulation::empty()
+            };
+            let body = request.to_bytes().expect("Cannot encode request");
+            let mut bytes = Header::new(0, 14 + body.len() as i32).to_bytes().expect("Cannot encode header");
+            bytes.extend(body);
+            stream.write_all(&bytes).await.expect("Cannot send request");
+        }
+
+        let mut replies = vec![];
+        for _ in 0..3 {
+            let (header, body) = read_message(&mut stream).await;
+            assert_eq!(2, header.message_type);
+            let reply = ReplyData::from_bytes(&body, &mut 0).expect("Cannot decode reply");
+            if let Some(error) = reply.error().expect("Cannot decode error") {
+                replies.push((reply.request_id, error.to_string()));
+                continue;
+            }
+            replies.push((reply.request_id, String::from_bytes(&reply.body.data, &mut 0).expect("Cannot decode operation")));
+        }
+        assert_eq!((1, String::from("slow")), replies[2]);
+        // a failed deferred reply is answered as unknown exception
+        replies.sort();
+        assert_eq!(vec![
+            (1, String::from("slow")),
+            (2, String::from("fast")),
+            (3, String::from("RemoteException: deferred failure"))
+        ], replies);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_panicking_servant() {
+        let adapter = Adapter::with_endpoint("panicking", "mem -n panictest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(ConnectionServer {}));
+        adapter.add("panic", Box::new(PanicServer {}));
+        adapter.add_servant_locator("broken", Arc::new(PanicLocator {}));
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = connect("test:mem -n panictest", &InitializationData::new()).await.expect("Cannot connect to adapter");
+        let error = call(&mut proxy, "panic").await.expect_err("Panic was not reported");
+        let error = error.downcast_ref::<RemoteException>().expect("Expected a RemoteException");
+        assert_eq!("Dispatch panicked: servant failed", error.cause);
+
+        let error = call(&mut proxy, "broken/object").await.expect_err("Panic was not reported");
+        let error = error.downcast_ref::<RemoteException>().expect("Expected a RemoteException");
+        assert!(error.cause.starts_with("Dispatch failed:"));
+
+        // the connection is still usable and the adapter deactivates
+        assert_eq!("mem", call(&mut proxy, "test").await.expect("Request failed"));
+        tokio::time::timeout(std::time::Duration::from_secs(5), adapter.deactivate()).await.expect("Adapter did not deactivate");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_udp_endpoint() {
+        let operations = Arc::new(std::sync::Mutex::new(vec![]));
+        let adapter = Adapter::with_endpoint("test", "udp -h 127.0.0.1 -p 0", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("test", Box::new(RecordingServer { operations: operations.clone() }));
+        adapter.activate().await.expect("Cannot activate adapter");
+        assert_ne!(0, adapter.endpoint().port());
+
+        let mut proxy = connect_to(&adapter, &InitializationData::new()).await.expect("Cannot create proxy");
+        proxy.dispatch::<ProtocolError>("ping", 0, &Encapsulation::empty(), None).await.expect("Cannot send datagram");
+        wait_for_operations(&operations, 1).await;
+        assert_eq!("udp:ping", operations.lock().unwrap()[0]);
+        operations.lock().unwrap().clear();
+
+        let mut batch = proxy.ice_batch_datagram().await.expect("Cannot create batch datagram proxy");
+        batch.dispatch::<ProtocolError>("first", 0, &Encapsulation::empty(), None).await.expect("Cannot queue request");
+        batch.dispatch::<ProtocolError>("second", 0, &Encapsulation::empty(), None).await.expect("Cannot queue request");
+        assert!(operations.lock().unwrap().is_empty());
+        batch.ice_flush_batch_requests().await.expect("Cannot flush batch requests");
+        wait_for_operations(&operations, 2).await;
+        assert_eq!(vec!["udp:first", "udp:second"], *operations.lock().unwrap());
+    }
+
+    /// Waits until the datagrams of `count` operations are dispatched.
+    async fn wait_for_operations(operations: &Arc<std::sync::Mutex<Vec<String>>>, count: usize) {
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while operations.lock().unwrap().len() < count {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        }).await.expect("Datagrams were not dispatched");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_ssl_pem_certificates() {
+        let mut server_properties = ssl_properties("server_cert.pem");
+        server_properties.set("IceSSL.KeyFile", "server_key.pem");
+        server_properties.set("IceSSL.Protocols", "tls1_2");
+        let adapter = start_adapter("ssl -h 127.0.0.1 -p 0", &server_properties).await;
+
+        let mut client_properties = ssl_properties("client_cert.pem");
+        client_properties.set("IceSSL.KeyFile", "client_key.pem");
+        let mut proxy = connect_to(&adapter, &InitializationData::with_properties(&client_properties)).await.expect("Cannot connect to adapter");
+        assert!(proxy.dispatch::<ProtocolError>("info", 0, &Encapsulation::empty(), None).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_ssl_verification() {
+        let adapter = start_adapter("ssl -h 127.0.0.1 -p 0", &ssl_properties("server.p12")).await;
+
+        let mut client_properties = ssl_properties("client.p12");
+        client_properties.set("IceSSL.TrustOnly.Client", "CN=client");
+        let result = connect_to(&adapter, &InitializationData::with_properties(&client_properties)).await;
+        assert!(result.err().expect("Untrusted server accepted").downcast_ref::<SecurityException>().is_some());
+
+        client_properties.set("IceSSL.TrustOnly.Client", "CN=server, OU=Ice");
+        let mut init_data = InitializationData::with_properties(&client_properties);
+        assert!(connect_to(&adapter, &init_data).await.is_ok());
+
+        init_data.set_certificate_verifier(Arc::new(RejectVerifier {}));
+        let result = connect_to(&adapter, &init_data).await;
+        assert!(result.err().expect("Rejected server accepted").downcast_ref::<SecurityException>().is_some());
+    }
+}
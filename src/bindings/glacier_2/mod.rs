@@ -0,0 +1,2216 @@
+#[allow(dead_code)]
+#[allow(unused_imports)]
+use async_trait::async_trait;
+use ice_rs::current::Current;
+use ice_rs::encoding::*;
+use ice_rs::errors::*;
+use ice_rs::iceobject::*;
+use ice_rs::protocol::*;
+use ice_rs::proxy::Proxy;
+use std::collections::HashMap;
+#[allow(unused_imports)]
+use super::{self as slice_root};
+#[derive(Debug)]
+pub struct PermissionDeniedException {
+    pub reason: String,
+}
+impl Default for PermissionDeniedException {
+    fn default() -> Self {
+        PermissionDeniedException {
+            reason: Default::default(),
+        }
+    }
+}
+impl std::fmt::Display for PermissionDeniedException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PermissionDeniedException")
+    }
+}
+impl std::error::Error for PermissionDeniedException {}
+impl UserException for PermissionDeniedException {
+    const TYPE_ID: &'static str = "::Glacier2::PermissionDeniedException";
+}
+impl ToBytes for PermissionDeniedException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(self.reason.to_bytes()?);
+        Ok(bytes)
+    }
+}
+impl FromBytes for PermissionDeniedException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {
+            reason: <String>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?,
+        };
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct CannotCreateSessionException {
+    pub reason: String,
+}
+impl Default for CannotCreateSessionException {
+    fn default() -> Self {
+        CannotCreateSessionException {
+            reason: Default::default(),
+        }
+    }
+}
+impl std::fmt::Display for CannotCreateSessionException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CannotCreateSessionException")
+    }
+}
+impl std::error::Error for CannotCreateSessionException {}
+impl UserException for CannotCreateSessionException {
+    const TYPE_ID: &'static str = "::Glacier2::CannotCreateSessionException";
+}
+impl ToBytes for CannotCreateSessionException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(self.reason.to_bytes()?);
+        Ok(bytes)
+    }
+}
+impl FromBytes for CannotCreateSessionException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {
+            reason: <String>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?,
+        };
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct SessionNotExistException {}
+impl Default for SessionNotExistException {
+    fn default() -> Self {
+        SessionNotExistException {}
+    }
+}
+impl std::fmt::Display for SessionNotExistException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SessionNotExistException")
+    }
+}
+impl std::error::Error for SessionNotExistException {}
+impl UserException for SessionNotExistException {
+    const TYPE_ID: &'static str = "::Glacier2::SessionNotExistException";
+}
+impl ToBytes for SessionNotExistException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for SessionNotExistException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[async_trait]
+pub trait PermissionsVerifier: IceObject {
+    async fn check_permissions(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        reason: &mut String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait PermissionsVerifierI {
+    async fn check_permissions(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        reason: &mut String,
+        current: &Current,
+    ) -> bool;
+}
+pub struct PermissionsVerifierServer {
+    server_impl: Box<dyn PermissionsVerifierI + Send + Sync>,
+}
+impl PermissionsVerifierServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn PermissionsVerifierI + Send + Sync>,
+    ) -> PermissionsVerifierServer {
+        PermissionsVerifierServer {
+            server_impl,
+        }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::PermissionsVerifier";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::PermissionsVerifier",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for PermissionsVerifierServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "checkPermissions" => {
+                let mut read_bytes = 0;
+                let user_id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let password = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let mut reason = Default::default();
+                let result = self
+                    .server_impl
+                    .check_permissions(&user_id, &password, &mut reason, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(reason.to_bytes()?);
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct PermissionsVerifierPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for PermissionsVerifierPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(
+                    String::from("::Glacier2::PermissionsVerifier").to_bytes()?,
+                ),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl PermissionsVerifier for PermissionsVerifierPrx {
+    async fn check_permissions(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        reason: &mut String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(user_id.to_bytes()?);
+        bytes.extend(password.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::glacier_2::PermissionDeniedException,
+            >(
+                &String::from("checkPermissions"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        *reason = <String>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )?;
+        <bool>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for PermissionsVerifierPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl PermissionsVerifierPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Session: IceObject {
+    async fn destroy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait SessionI {
+    async fn destroy(&mut self, current: &Current) -> ();
+}
+pub struct SessionServer {
+    server_impl: Box<dyn SessionI + Send + Sync>,
+}
+impl SessionServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn SessionI + Send + Sync>) -> SessionServer {
+        SessionServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::Session";
+    const TYPE_IDS: &'static [&'static str] = &["::Glacier2::Session", "::Ice::Object"];
+}
+#[async_trait]
+impl IceObjectServer for SessionServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "destroy" => {
+                let result = self.server_impl.destroy(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct SessionPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for SessionPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Glacier2::Session").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Session for SessionPrx {
+    async fn destroy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("destroy"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+}
+impl From<Proxy> for SessionPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl SessionPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait StringSet: IceObject {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::StringSeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::StringSeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn get(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<slice_root::ice::StringSeq, Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait StringSetI {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::StringSeq,
+        current: &Current,
+    ) -> ();
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::StringSeq,
+        current: &Current,
+    ) -> ();
+    async fn get(&mut self, current: &Current) -> slice_root::ice::StringSeq;
+}
+pub struct StringSetServer {
+    server_impl: Box<dyn StringSetI + Send + Sync>,
+}
+impl StringSetServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn StringSetI + Send + Sync>) -> StringSetServer {
+        StringSetServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::StringSet";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::StringSet",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for StringSetServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "add" => {
+                let mut read_bytes = 0;
+                let additions = <slice_root::ice::StringSeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.add(&additions, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "remove" => {
+                let mut read_bytes = 0;
+                let deletions = <slice_root::ice::StringSeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.remove(&deletions, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "get" => {
+                let result = self.server_impl.get(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct StringSetPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for StringSetPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Glacier2::StringSet").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl StringSet for StringSetPrx {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::StringSeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(additions.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("add"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::StringSeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(deletions.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("remove"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn get(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<slice_root::ice::StringSeq, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("get"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::StringSeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for StringSetPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl StringSetPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait IdentitySet: IceObject {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::IdentitySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::IdentitySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn get(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<slice_root::ice::IdentitySeq, Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait IdentitySetI {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::IdentitySeq,
+        current: &Current,
+    ) -> ();
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::IdentitySeq,
+        current: &Current,
+    ) -> ();
+    async fn get(&mut self, current: &Current) -> slice_root::ice::IdentitySeq;
+}
+pub struct IdentitySetServer {
+    server_impl: Box<dyn IdentitySetI + Send + Sync>,
+}
+impl IdentitySetServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn IdentitySetI + Send + Sync>) -> IdentitySetServer {
+        IdentitySetServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::IdentitySet";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::IdentitySet",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for IdentitySetServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "add" => {
+                let mut read_bytes = 0;
+                let additions = <slice_root::ice::IdentitySeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.add(&additions, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "remove" => {
+                let mut read_bytes = 0;
+                let deletions = <slice_root::ice::IdentitySeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.remove(&deletions, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "get" => {
+                let result = self.server_impl.get(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct IdentitySetPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for IdentitySetPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(
+                    String::from("::Glacier2::IdentitySet").to_bytes()?,
+                ),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl IdentitySet for IdentitySetPrx {
+    async fn add(
+        &mut self,
+        additions: &slice_root::ice::IdentitySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(additions.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("add"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn remove(
+        &mut self,
+        deletions: &slice_root::ice::IdentitySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(deletions.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("remove"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn get(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<slice_root::ice::IdentitySeq, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("get"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::IdentitySeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for IdentitySetPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl IdentitySetPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait SessionControl: IceObject {
+    async fn categories(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn adapter_ids(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn identities(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::IdentitySetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>>;
+    async fn destroy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait SessionControlI {
+    async fn categories(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>;
+    async fn adapter_ids(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>;
+    async fn identities(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::IdentitySetPrx>;
+    async fn get_session_timeout(&mut self, current: &Current) -> i32;
+    async fn destroy(&mut self, current: &Current) -> ();
+}
+pub struct SessionControlServer {
+    server_impl: Box<dyn SessionControlI + Send + Sync>,
+}
+impl SessionControlServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn SessionControlI + Send + Sync>,
+    ) -> SessionControlServer {
+        SessionControlServer {
+            server_impl,
+        }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::SessionControl";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::SessionControl",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for SessionControlServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "categories" => {
+                let result = self.server_impl.categories(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "adapterIds" => {
+                let result = self.server_impl.adapter_ids(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "identities" => {
+                let result = self.server_impl.identities(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getSessionTimeout" => {
+                let result = self.server_impl.get_session_timeout(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "destroy" => {
+                let result = self.server_impl.destroy(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct SessionControlPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for SessionControlPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(
+                    String::from("::Glacier2::SessionControl").to_bytes()?,
+                ),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl SessionControl for SessionControlPrx {
+    async fn categories(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("categories"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::StringSetPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn adapter_ids(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::StringSetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("adapterIds"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::StringSetPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn identities(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::IdentitySetPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("identities"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::IdentitySetPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getSessionTimeout"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <i32>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn destroy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("destroy"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+}
+impl From<Proxy> for SessionControlPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl SessionControlPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait SessionManager: IceObject {
+    async fn create(
+        &mut self,
+        user_id: &String,
+        control: &ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionControlPrx>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait SessionManagerI {
+    async fn create(
+        &mut self,
+        user_id: &String,
+        control: &ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionControlPrx>,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>;
+}
+pub struct SessionManagerServer {
+    server_impl: Box<dyn SessionManagerI + Send + Sync>,
+}
+impl SessionManagerServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn SessionManagerI + Send + Sync>,
+    ) -> SessionManagerServer {
+        SessionManagerServer {
+            server_impl,
+        }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::SessionManager";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::SessionManager",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for SessionManagerServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "create" => {
+                let mut read_bytes = 0;
+                let user_id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let control = <ice_rs::proxy::TypedProxy<
+                    slice_root::glacier_2::SessionControlPrx,
+                >>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.create(&user_id, &control, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct SessionManagerPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for SessionManagerPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(
+                    String::from("::Glacier2::SessionManager").to_bytes()?,
+                ),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl SessionManager for SessionManagerPrx {
+    async fn create(
+        &mut self,
+        user_id: &String,
+        control: &ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionControlPrx>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(user_id.to_bytes()?);
+        bytes.extend(control.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::glacier_2::CannotCreateSessionException,
+            >(&String::from("create"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::SessionPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for SessionManagerPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl SessionManagerPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Router: IceObject + slice_root::ice::Router {
+    async fn get_category_for_client(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+    async fn create_session(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn create_session_from_secure_connection(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn refresh_session(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn destroy_session(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>>;
+    async fn get_acm_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait RouterI: slice_root::ice::RouterI {
+    async fn get_category_for_client(&mut self, current: &Current) -> String;
+    async fn create_session(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>;
+    async fn create_session_from_secure_connection(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>;
+    async fn refresh_session(&mut self, current: &Current) -> ();
+    async fn destroy_session(&mut self, current: &Current) -> ();
+    async fn get_session_timeout(&mut self, current: &Current) -> i64;
+    async fn get_acm_timeout(&mut self, current: &Current) -> i32;
+}
+pub struct RouterServer {
+    server_impl: Box<dyn RouterI + Send + Sync>,
+}
+impl RouterServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn RouterI + Send + Sync>) -> RouterServer {
+        RouterServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Glacier2::Router";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Glacier2::Router",
+        "::Ice::Object",
+        "::Ice::Router",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for RouterServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getCategoryForClient" => {
+                let result = self.server_impl.get_category_for_client(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "createSession" => {
+                let mut read_bytes = 0;
+                let user_id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let password = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .create_session(&user_id, &password, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "createSessionFromSecureConnection" => {
+                let result = self
+                    .server_impl
+                    .create_session_from_secure_connection(current)
+                    .await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "refreshSession" => {
+                let result = self.server_impl.refresh_session(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "destroySession" => {
+                let result = self.server_impl.destroy_session(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getSessionTimeout" => {
+                let result = self.server_impl.get_session_timeout(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getACMTimeout" => {
+                let result = self.server_impl.get_acm_timeout(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getClientProxy" => {
+                let mut has_routing_table = Default::default();
+                let result = self
+                    .server_impl
+                    .get_client_proxy(&mut has_routing_table, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(OptionalWrapper::new(1u8, has_routing_table).to_bytes()?);
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getServerProxy" => {
+                let result = self.server_impl.get_server_proxy(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "addProxies" => {
+                let mut read_bytes = 0;
+                let proxies = <slice_root::ice::ObjectProxySeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.add_proxies(&proxies, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct RouterPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for RouterPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Glacier2::Router").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Router for RouterPrx {
+    async fn get_category_for_client(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getCategoryForClient"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <String>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn create_session(
+        &mut self,
+        user_id: &String,
+        password: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(user_id.to_bytes()?);
+        bytes.extend(password.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                (
+                    slice_root::glacier_2::PermissionDeniedException,
+                    slice_root::glacier_2::CannotCreateSessionException,
+                ),
+            >(&String::from("createSession"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::SessionPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn create_session_from_secure_connection(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::glacier_2::SessionPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                (
+                    slice_root::glacier_2::PermissionDeniedException,
+                    slice_root::glacier_2::CannotCreateSessionException,
+                ),
+            >(
+                &String::from("createSessionFromSecureConnection"),
+                0u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::glacier_2::SessionPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn refresh_session(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        self.proxy
+            .dispatch::<
+                slice_root::glacier_2::SessionNotExistException,
+            >(&String::from("refreshSession"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn destroy_session(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        self.proxy
+            .dispatch::<
+                slice_root::glacier_2::SessionNotExistException,
+            >(&String::from("destroySession"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getSessionTimeout"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <i64>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_acm_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getACMTimeout"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <i32>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+#[async_trait]
+impl slice_root::ice::Router for RouterPrx {
+    async fn get_client_proxy(
+        &mut self,
+        has_routing_table: &mut Option<bool>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getClientProxy"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        *has_routing_table = <Option<
+            bool,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )?;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_server_proxy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getServerProxy"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn add_proxies(
+        &mut self,
+        proxies: &slice_root::ice::ObjectProxySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(proxies.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("addProxies"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::ObjectProxySeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<RouterPrx> for slice_root::ice::RouterPrx {
+    fn from(proxy: RouterPrx) -> slice_root::ice::RouterPrx {
+        slice_root::ice::RouterPrx {
+            proxy: proxy.proxy,
+        }
+    }
+}
+impl From<Proxy> for RouterPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl RouterPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
@@ -0,0 +1,2109 @@
+#[allow(dead_code)]
+#[allow(unused_imports)]
+use async_trait::async_trait;
+use ice_rs::current::Current;
+use ice_rs::encoding::*;
+use ice_rs::errors::*;
+use ice_rs::iceobject::*;
+use ice_rs::protocol::*;
+use ice_rs::proxy::Proxy;
+use std::collections::HashMap;
+#[allow(unused_imports)]
+use super::{self as slice_root};
+pub type BoolSeq = Vec<bool>;
+pub type ByteSeq = Vec<u8>;
+pub type ShortSeq = Vec<i16>;
+pub type IntSeq = Vec<i32>;
+pub type LongSeq = Vec<i64>;
+pub type FloatSeq = Vec<f32>;
+pub type DoubleSeq = Vec<f64>;
+pub type StringSeq = Vec<String>;
+pub type ObjectProxySeq = Vec<ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>>;
+pub type IdentitySeq = Vec<slice_root::ice::Identity>;
+pub type PropertyDict = HashMap<String, String>;
+#[derive(Debug, Clone, PartialEq, IceDerive)]
+pub struct Identity {
+    pub name: String,
+    pub category: String,
+}
+impl Default for Identity {
+    fn default() -> Self {
+        Identity {
+            name: Default::default(),
+            category: Default::default(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct AdapterNotFoundException {}
+impl Default for AdapterNotFoundException {
+    fn default() -> Self {
+        AdapterNotFoundException {}
+    }
+}
+impl std::fmt::Display for AdapterNotFoundException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AdapterNotFoundException")
+    }
+}
+impl std::error::Error for AdapterNotFoundException {}
+impl UserException for AdapterNotFoundException {
+    const TYPE_ID: &'static str = "::Ice::AdapterNotFoundException";
+}
+impl ToBytes for AdapterNotFoundException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for AdapterNotFoundException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct InvalidReplicaGroupIdException {}
+impl Default for InvalidReplicaGroupIdException {
+    fn default() -> Self {
+        InvalidReplicaGroupIdException {}
+    }
+}
+impl std::fmt::Display for InvalidReplicaGroupIdException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "InvalidReplicaGroupIdException")
+    }
+}
+impl std::error::Error for InvalidReplicaGroupIdException {}
+impl UserException for InvalidReplicaGroupIdException {
+    const TYPE_ID: &'static str = "::Ice::InvalidReplicaGroupIdException";
+}
+impl ToBytes for InvalidReplicaGroupIdException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for InvalidReplicaGroupIdException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct AdapterAlreadyActiveException {}
+impl Default for AdapterAlreadyActiveException {
+    fn default() -> Self {
+        AdapterAlreadyActiveException {}
+    }
+}
+impl std::fmt::Display for AdapterAlreadyActiveException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AdapterAlreadyActiveException")
+    }
+}
+impl std::error::Error for AdapterAlreadyActiveException {}
+impl UserException for AdapterAlreadyActiveException {
+    const TYPE_ID: &'static str = "::Ice::AdapterAlreadyActiveException";
+}
+impl ToBytes for AdapterAlreadyActiveException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for AdapterAlreadyActiveException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct ObjectNotFoundException {}
+impl Default for ObjectNotFoundException {
+    fn default() -> Self {
+        ObjectNotFoundException {}
+    }
+}
+impl std::fmt::Display for ObjectNotFoundException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ObjectNotFoundException")
+    }
+}
+impl std::error::Error for ObjectNotFoundException {}
+impl UserException for ObjectNotFoundException {
+    const TYPE_ID: &'static str = "::Ice::ObjectNotFoundException";
+}
+impl ToBytes for ObjectNotFoundException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for ObjectNotFoundException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[derive(Debug)]
+pub struct ServerNotFoundException {}
+impl Default for ServerNotFoundException {
+    fn default() -> Self {
+        ServerNotFoundException {}
+    }
+}
+impl std::fmt::Display for ServerNotFoundException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ServerNotFoundException")
+    }
+}
+impl std::error::Error for ServerNotFoundException {}
+impl UserException for ServerNotFoundException {
+    const TYPE_ID: &'static str = "::Ice::ServerNotFoundException";
+}
+impl ToBytes for ServerNotFoundException {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        Ok(bytes)
+    }
+}
+impl FromBytes for ServerNotFoundException {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let _flag = SliceFlags::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let _slice_name = String::from_bytes(
+            &bytes[read as usize..bytes.len()],
+            &mut read,
+        )?;
+        let obj = Self {};
+        *read_bytes = *read_bytes + read;
+        Ok(obj)
+    }
+}
+#[async_trait]
+pub trait Router: IceObject {
+    async fn get_client_proxy(
+        &mut self,
+        has_routing_table: &mut Option<bool>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn get_server_proxy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn add_proxies(
+        &mut self,
+        proxies: &slice_root::ice::ObjectProxySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait RouterI {
+    async fn get_client_proxy(
+        &mut self,
+        has_routing_table: &mut Option<bool>,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn get_server_proxy(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn add_proxies(
+        &mut self,
+        proxies: &slice_root::ice::ObjectProxySeq,
+        current: &Current,
+    ) -> slice_root::ice::ObjectProxySeq;
+}
+pub struct RouterServer {
+    server_impl: Box<dyn RouterI + Send + Sync>,
+}
+impl RouterServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn RouterI + Send + Sync>) -> RouterServer {
+        RouterServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Ice::Router";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Object", "::Ice::Router"];
+}
+#[async_trait]
+impl IceObjectServer for RouterServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getClientProxy" => {
+                let mut has_routing_table = Default::default();
+                let result = self
+                    .server_impl
+                    .get_client_proxy(&mut has_routing_table, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(OptionalWrapper::new(1u8, has_routing_table).to_bytes()?);
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getServerProxy" => {
+                let result = self.server_impl.get_server_proxy(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "addProxies" => {
+                let mut read_bytes = 0;
+                let proxies = <slice_root::ice::ObjectProxySeq>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.add_proxies(&proxies, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct RouterPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for RouterPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::Router").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Router for RouterPrx {
+    async fn get_client_proxy(
+        &mut self,
+        has_routing_table: &mut Option<bool>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getClientProxy"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        *has_routing_table = <Option<
+            bool,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )?;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_server_proxy(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getServerProxy"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn add_proxies(
+        &mut self,
+        proxies: &slice_root::ice::ObjectProxySeq,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(proxies.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("addProxies"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::ObjectProxySeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for RouterPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl RouterPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait RouterFinder: IceObject {
+    async fn get_router(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::RouterPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait RouterFinderI {
+    async fn get_router(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::ice::RouterPrx>;
+}
+pub struct RouterFinderServer {
+    server_impl: Box<dyn RouterFinderI + Send + Sync>,
+}
+impl RouterFinderServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn RouterFinderI + Send + Sync>) -> RouterFinderServer {
+        RouterFinderServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Ice::RouterFinder";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Object", "::Ice::RouterFinder"];
+}
+#[async_trait]
+impl IceObjectServer for RouterFinderServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getRouter" => {
+                let result = self.server_impl.get_router(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct RouterFinderPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for RouterFinderPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::RouterFinder").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl RouterFinder for RouterFinderPrx {
+    async fn get_router(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::RouterPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getRouter"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice::RouterPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for RouterFinderPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl RouterFinderPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Process: IceObject {
+    async fn shutdown(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn write_message(
+        &mut self,
+        message: &String,
+        fd: i32,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait ProcessI {
+    async fn shutdown(&mut self, current: &Current) -> ();
+    async fn write_message(
+        &mut self,
+        message: &String,
+        fd: i32,
+        current: &Current,
+    ) -> ();
+}
+pub struct ProcessServer {
+    server_impl: Box<dyn ProcessI + Send + Sync>,
+}
+impl ProcessServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn ProcessI + Send + Sync>) -> ProcessServer {
+        ProcessServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Ice::Process";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Object", "::Ice::Process"];
+}
+#[async_trait]
+impl IceObjectServer for ProcessServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "shutdown" => {
+                let result = self.server_impl.shutdown(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "writeMessage" => {
+                let mut read_bytes = 0;
+                let message = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let fd = <i32>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.write_message(&message, fd, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct ProcessPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for ProcessPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::Process").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Process for ProcessPrx {
+    async fn shutdown(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("shutdown"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+    async fn write_message(
+        &mut self,
+        message: &String,
+        fd: i32,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(message.to_bytes()?);
+        bytes.extend(fd.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("writeMessage"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+}
+impl From<Proxy> for ProcessPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl ProcessPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait LocatorRegistry: IceObject {
+    async fn set_adapter_direct_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn set_replicated_adapter_direct_proxy(
+        &mut self,
+        adapter_id: &String,
+        replica_group_id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn set_server_process_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<slice_root::ice::ProcessPrx>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait LocatorRegistryI {
+    async fn set_adapter_direct_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        current: &Current,
+    ) -> ();
+    async fn set_replicated_adapter_direct_proxy(
+        &mut self,
+        adapter_id: &String,
+        replica_group_id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        current: &Current,
+    ) -> ();
+    async fn set_server_process_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<slice_root::ice::ProcessPrx>,
+        current: &Current,
+    ) -> ();
+}
+pub struct LocatorRegistryServer {
+    server_impl: Box<dyn LocatorRegistryI + Send + Sync>,
+}
+impl LocatorRegistryServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn LocatorRegistryI + Send + Sync>,
+    ) -> LocatorRegistryServer {
+        LocatorRegistryServer {
+            server_impl,
+        }
+    }
+    const TYPE_ID: &'static str = "::Ice::LocatorRegistry";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Ice::LocatorRegistry",
+        "::Ice::Object",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for LocatorRegistryServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "setAdapterDirectProxy" => {
+                let mut read_bytes = 0;
+                let id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let proxy = <ice_rs::proxy::TypedProxy<
+                    ice_rs::proxy::Proxy,
+                >>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .set_adapter_direct_proxy(&id, &proxy, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "setReplicatedAdapterDirectProxy" => {
+                let mut read_bytes = 0;
+                let adapter_id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let replica_group_id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let proxy = <ice_rs::proxy::TypedProxy<
+                    ice_rs::proxy::Proxy,
+                >>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .set_replicated_adapter_direct_proxy(
+                        &adapter_id,
+                        &replica_group_id,
+                        &proxy,
+                        current,
+                    )
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "setServerProcessProxy" => {
+                let mut read_bytes = 0;
+                let id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let proxy = <ice_rs::proxy::TypedProxy<
+                    slice_root::ice::ProcessPrx,
+                >>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .set_server_process_proxy(&id, &proxy, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct LocatorRegistryPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for LocatorRegistryPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::LocatorRegistry").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl LocatorRegistry for LocatorRegistryPrx {
+    async fn set_adapter_direct_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        bytes.extend(proxy.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                (
+                    slice_root::ice::AdapterNotFoundException,
+                    slice_root::ice::AdapterAlreadyActiveException,
+                ),
+            >(
+                &String::from("setAdapterDirectProxy"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        Ok(())
+    }
+    async fn set_replicated_adapter_direct_proxy(
+        &mut self,
+        adapter_id: &String,
+        replica_group_id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(adapter_id.to_bytes()?);
+        bytes.extend(replica_group_id.to_bytes()?);
+        bytes.extend(proxy.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                (
+                    slice_root::ice::AdapterNotFoundException,
+                    slice_root::ice::AdapterAlreadyActiveException,
+                    slice_root::ice::InvalidReplicaGroupIdException,
+                ),
+            >(
+                &String::from("setReplicatedAdapterDirectProxy"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        Ok(())
+    }
+    async fn set_server_process_proxy(
+        &mut self,
+        id: &String,
+        proxy: &ice_rs::proxy::TypedProxy<slice_root::ice::ProcessPrx>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        bytes.extend(proxy.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                slice_root::ice::ServerNotFoundException,
+            >(
+                &String::from("setServerProcessProxy"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        Ok(())
+    }
+}
+impl From<Proxy> for LocatorRegistryPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl LocatorRegistryPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Locator: IceObject {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn find_adapter_by_id(
+        &mut self,
+        id: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn get_registry(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::LocatorRegistryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait LocatorI {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn find_adapter_by_id(
+        &mut self,
+        id: &String,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn get_registry(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::ice::LocatorRegistryPrx>;
+}
+pub struct LocatorServer {
+    server_impl: Box<dyn LocatorI + Send + Sync>,
+}
+impl LocatorServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn LocatorI + Send + Sync>) -> LocatorServer {
+        LocatorServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Ice::Locator";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Locator", "::Ice::Object"];
+}
+#[async_trait]
+impl IceObjectServer for LocatorServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "findObjectById" => {
+                let mut read_bytes = 0;
+                let id = <slice_root::ice::Identity>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_object_by_id(&id, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findAdapterById" => {
+                let mut read_bytes = 0;
+                let id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_adapter_by_id(&id, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getRegistry" => {
+                let result = self.server_impl.get_registry(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct LocatorPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for LocatorPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::Locator").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Locator for LocatorPrx {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::ice::ObjectNotFoundException,
+            >(&String::from("findObjectById"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_adapter_by_id(
+        &mut self,
+        id: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::ice::AdapterNotFoundException,
+            >(
+                &String::from("findAdapterById"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_registry(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::LocatorRegistryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getRegistry"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice::LocatorRegistryPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for LocatorPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl LocatorPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait LocatorFinder: IceObject {
+    async fn get_locator(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::LocatorPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait LocatorFinderI {
+    async fn get_locator(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::ice::LocatorPrx>;
+}
+pub struct LocatorFinderServer {
+    server_impl: Box<dyn LocatorFinderI + Send + Sync>,
+}
+impl LocatorFinderServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn LocatorFinderI + Send + Sync>,
+    ) -> LocatorFinderServer {
+        LocatorFinderServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::Ice::LocatorFinder";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::LocatorFinder", "::Ice::Object"];
+}
+#[async_trait]
+impl IceObjectServer for LocatorFinderServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getLocator" => {
+                let result = self.server_impl.get_locator(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct LocatorFinderPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for LocatorFinderPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::LocatorFinder").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl LocatorFinder for LocatorFinderPrx {
+    async fn get_locator(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::LocatorPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getLocator"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice::LocatorPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for LocatorFinderPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl LocatorFinderPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait PropertiesAdmin: IceObject {
+    async fn get_property(
+        &mut self,
+        key: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+    async fn get_properties_for_prefix(
+        &mut self,
+        prefix: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<slice_root::ice::PropertyDict, Box<dyn std::error::Error + Send + Sync>>;
+    async fn set_properties(
+        &mut self,
+        new_properties: &slice_root::ice::PropertyDict,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait PropertiesAdminI {
+    async fn get_property(&mut self, key: &String, current: &Current) -> String;
+    async fn get_properties_for_prefix(
+        &mut self,
+        prefix: &String,
+        current: &Current,
+    ) -> slice_root::ice::PropertyDict;
+    async fn set_properties(
+        &mut self,
+        new_properties: &slice_root::ice::PropertyDict,
+        current: &Current,
+    ) -> ();
+}
+pub struct PropertiesAdminServer {
+    server_impl: Box<dyn PropertiesAdminI + Send + Sync>,
+}
+impl PropertiesAdminServer {
+    #[allow(dead_code)]
+    pub fn new(
+        server_impl: Box<dyn PropertiesAdminI + Send + Sync>,
+    ) -> PropertiesAdminServer {
+        PropertiesAdminServer {
+            server_impl,
+        }
+    }
+    const TYPE_ID: &'static str = "::Ice::PropertiesAdmin";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Ice::Object",
+        "::Ice::PropertiesAdmin",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for PropertiesAdminServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getProperty" => {
+                let mut read_bytes = 0;
+                let key = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.get_property(&key, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getPropertiesForPrefix" => {
+                let mut read_bytes = 0;
+                let prefix = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .get_properties_for_prefix(&prefix, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "setProperties" => {
+                let mut read_bytes = 0;
+                let new_properties = <slice_root::ice::PropertyDict>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .set_properties(&new_properties, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct PropertiesAdminPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for PropertiesAdminPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::Ice::PropertiesAdmin").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl PropertiesAdmin for PropertiesAdminPrx {
+    async fn get_property(
+        &mut self,
+        key: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(key.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getProperty"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <String>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_properties_for_prefix(
+        &mut self,
+        prefix: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::PropertyDict,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(prefix.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getPropertiesForPrefix"),
+                0u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::PropertyDict>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn set_properties(
+        &mut self,
+        new_properties: &slice_root::ice::PropertyDict,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(new_properties.to_bytes()?);
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("setProperties"), 0u8, &Encapsulation::from(bytes), context)
+            .await?;
+        Ok(())
+    }
+}
+impl From<Proxy> for PropertiesAdminPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl PropertiesAdminPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
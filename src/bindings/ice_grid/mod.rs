@@ -0,0 +1,1102 @@
+#[allow(dead_code)]
+#[allow(unused_imports)]
+use async_trait::async_trait;
+use ice_rs::current::Current;
+use ice_rs::encoding::*;
+use ice_rs::errors::*;
+use ice_rs::iceobject::*;
+use ice_rs::protocol::*;
+use ice_rs::proxy::Proxy;
+use num_enum::TryFromPrimitive;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+#[allow(unused_imports)]
+use super::{self as slice_root};
+#[derive(Debug, Copy, Clone, TryFromPrimitive, PartialEq)]
+#[repr(i32)]
+pub enum LoadSample {
+    LoadSample1 = 0i32,
+    LoadSample5 = 1i32,
+    LoadSample15 = 2i32,
+}
+impl Default for LoadSample {
+    fn default() -> Self {
+        LoadSample::LoadSample1
+    }
+}
+impl OptionalType for LoadSample {
+    fn optional_type() -> u8 {
+        4
+    }
+}
+impl ToBytes for LoadSample {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut bytes = Vec::new();
+        bytes.extend(IceSize { size: *self as i32 }.to_bytes()?);
+        Ok(bytes)
+    }
+}
+impl FromBytes for LoadSample {
+    fn from_bytes(
+        bytes: &[u8],
+        read_bytes: &mut i32,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        Self: Sized,
+    {
+        let mut read = 0;
+        let enum_value = IceSize::from_bytes(
+                &bytes[read as usize..bytes.len()],
+                &mut read,
+            )?
+            .size;
+        *read_bytes = *read_bytes + read;
+        match LoadSample::try_from(enum_value) {
+            Ok(enum_type) => Ok(enum_type),
+            _ => {
+                Err(
+                    Box::new(
+                        ProtocolError::new(
+                            &format!("Cannot convert int {} to enum", enum_value),
+                        ),
+                    ),
+                )
+            }
+        }
+    }
+}
+#[async_trait]
+pub trait Query: IceObject {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn find_object_by_type(
+        &mut self,
+        r#type: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn find_object_by_type_on_least_loaded_node(
+        &mut self,
+        r#type: &String,
+        sample: &slice_root::ice_grid::LoadSample,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn find_all_objects_by_type(
+        &mut self,
+        r#type: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn find_all_replicas(
+        &mut self,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait QueryI {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn find_object_by_type(
+        &mut self,
+        r#type: &String,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn find_object_by_type_on_least_loaded_node(
+        &mut self,
+        r#type: &String,
+        sample: &slice_root::ice_grid::LoadSample,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>;
+    async fn find_all_objects_by_type(
+        &mut self,
+        r#type: &String,
+        current: &Current,
+    ) -> slice_root::ice::ObjectProxySeq;
+    async fn find_all_replicas(
+        &mut self,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        current: &Current,
+    ) -> slice_root::ice::ObjectProxySeq;
+}
+pub struct QueryServer {
+    server_impl: Box<dyn QueryI + Send + Sync>,
+}
+impl QueryServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn QueryI + Send + Sync>) -> QueryServer {
+        QueryServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::IceGrid::Query";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Object", "::IceGrid::Query"];
+}
+#[async_trait]
+impl IceObjectServer for QueryServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "findObjectById" => {
+                let mut read_bytes = 0;
+                let id = <slice_root::ice::Identity>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_object_by_id(&id, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findObjectByType" => {
+                let mut read_bytes = 0;
+                let r#type = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .find_object_by_type(&r#type, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findObjectByTypeOnLeastLoadedNode" => {
+                let mut read_bytes = 0;
+                let r#type = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let sample = <slice_root::ice_grid::LoadSample>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .find_object_by_type_on_least_loaded_node(&r#type, &sample, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findAllObjectsByType" => {
+                let mut read_bytes = 0;
+                let r#type = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self
+                    .server_impl
+                    .find_all_objects_by_type(&r#type, current)
+                    .await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findAllReplicas" => {
+                let mut read_bytes = 0;
+                let proxy = <ice_rs::proxy::TypedProxy<
+                    ice_rs::proxy::Proxy,
+                >>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_all_replicas(&proxy, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct QueryPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for QueryPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::IceGrid::Query").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Query for QueryPrx {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("findObjectById"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_object_by_type(
+        &mut self,
+        r#type: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(r#type.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("findObjectByType"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_object_by_type_on_least_loaded_node(
+        &mut self,
+        r#type: &String,
+        sample: &slice_root::ice_grid::LoadSample,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(r#type.to_bytes()?);
+        bytes.extend(sample.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("findObjectByTypeOnLeastLoadedNode"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_all_objects_by_type(
+        &mut self,
+        r#type: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(r#type.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("findAllObjectsByType"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::ObjectProxySeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_all_replicas(
+        &mut self,
+        proxy: &ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        slice_root::ice::ObjectProxySeq,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(proxy.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("findAllReplicas"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <slice_root::ice::ObjectProxySeq>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for QueryPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl QueryPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Registry: IceObject {
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>>;
+    async fn get_acm_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>>;
+}
+#[async_trait]
+pub trait RegistryI {
+    async fn get_session_timeout(&mut self, current: &Current) -> i32;
+    async fn get_acm_timeout(&mut self, current: &Current) -> i32;
+}
+pub struct RegistryServer {
+    server_impl: Box<dyn RegistryI + Send + Sync>,
+}
+impl RegistryServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn RegistryI + Send + Sync>) -> RegistryServer {
+        RegistryServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::IceGrid::Registry";
+    const TYPE_IDS: &'static [&'static str] = &["::Ice::Object", "::IceGrid::Registry"];
+}
+#[async_trait]
+impl IceObjectServer for RegistryServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getSessionTimeout" => {
+                let result = self.server_impl.get_session_timeout(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getACMTimeout" => {
+                let result = self.server_impl.get_acm_timeout(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct RegistryPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for RegistryPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::IceGrid::Registry").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Registry for RegistryPrx {
+    async fn get_session_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getSessionTimeout"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <i32>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_acm_timeout(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getACMTimeout"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <i32>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<Proxy> for RegistryPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl RegistryPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
+#[async_trait]
+pub trait Locator: IceObject + slice_root::ice::Locator {
+    async fn get_local_registry(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice_grid::RegistryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+    async fn get_local_query(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice_grid::QueryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    >;
+}
+#[async_trait]
+pub trait LocatorI: slice_root::ice::LocatorI {
+    async fn get_local_registry(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::ice_grid::RegistryPrx>;
+    async fn get_local_query(
+        &mut self,
+        current: &Current,
+    ) -> ice_rs::proxy::TypedProxy<slice_root::ice_grid::QueryPrx>;
+}
+pub struct LocatorServer {
+    server_impl: Box<dyn LocatorI + Send + Sync>,
+}
+impl LocatorServer {
+    #[allow(dead_code)]
+    pub fn new(server_impl: Box<dyn LocatorI + Send + Sync>) -> LocatorServer {
+        LocatorServer { server_impl }
+    }
+    const TYPE_ID: &'static str = "::IceGrid::Locator";
+    const TYPE_IDS: &'static [&'static str] = &[
+        "::Ice::Locator",
+        "::Ice::Object",
+        "::IceGrid::Locator",
+    ];
+}
+#[async_trait]
+impl IceObjectServer for LocatorServer {
+    async fn handle_request(
+        &mut self,
+        request: &RequestData,
+        current: &Current,
+    ) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match request.operation.as_ref() {
+            "ice_ping" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::empty(),
+                })
+            }
+            "ice_isA" => {
+                let mut read = 0;
+                let param = String::from_bytes(&request.params.data, &mut read)?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(
+                        Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?,
+                    ),
+                })
+            }
+            "ice_id" => {
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?),
+                })
+            }
+            "ice_ids" => {
+                let type_ids = Self::TYPE_IDS
+                    .iter()
+                    .map(|id| String::from(*id))
+                    .collect::<Vec<String>>();
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(type_ids.to_bytes()?),
+                })
+            }
+            "getLocalRegistry" => {
+                let result = self.server_impl.get_local_registry(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getLocalQuery" => {
+                let result = self.server_impl.get_local_query(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findObjectById" => {
+                let mut read_bytes = 0;
+                let id = <slice_root::ice::Identity>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_object_by_id(&id, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "findAdapterById" => {
+                let mut read_bytes = 0;
+                let id = <String>::from_bytes(
+                    &request.params.data[read_bytes as usize..request.params.data.len()],
+                    &mut read_bytes,
+                )?;
+                let result = self.server_impl.find_adapter_by_id(&id, current).await;
+                let wrapped_result = result;
+                let mut result = Vec::new();
+                result.extend(wrapped_result.to_bytes()?);
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            "getRegistry" => {
+                let result = self.server_impl.get_registry(current).await;
+                let wrapped_result = result;
+                let result = wrapped_result.to_bytes()?;
+                Ok(ReplyData {
+                    request_id: request.request_id,
+                    status: 0,
+                    body: Encapsulation::from(result),
+                })
+            }
+            _ => ReplyData::request_failed(request, 4),
+        }
+    }
+}
+pub struct LocatorPrx {
+    pub proxy: Proxy,
+}
+#[async_trait]
+impl IceObject for LocatorPrx {
+    async fn ice_ping(
+        &mut self,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ping"), 1, &Encapsulation::empty(), None)
+            .await?;
+        Ok(())
+    }
+    async fn ice_is_a(
+        &mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("ice_isA"),
+                1,
+                &Encapsulation::from(String::from("::IceGrid::Locator").to_bytes()?),
+                None,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        bool::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_id(
+        &mut self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_id"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        String::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+    async fn ice_ids(
+        &mut self,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Sync + Send>> {
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("ice_ids"), 1, &Encapsulation::empty(), None)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        Vec::from_bytes(&reply.body.data, &mut read_bytes)
+    }
+}
+#[async_trait]
+impl Locator for LocatorPrx {
+    async fn get_local_registry(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice_grid::RegistryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(
+                &String::from("getLocalRegistry"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice_grid::RegistryPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_local_query(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice_grid::QueryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getLocalQuery"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice_grid::QueryPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+#[async_trait]
+impl slice_root::ice::Locator for LocatorPrx {
+    async fn find_object_by_id(
+        &mut self,
+        id: &slice_root::ice::Identity,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::ice::ObjectNotFoundException,
+            >(&String::from("findObjectById"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn find_adapter_by_id(
+        &mut self,
+        id: &String,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let mut bytes = Vec::new();
+        bytes.extend(id.to_bytes()?);
+        let reply = self
+            .proxy
+            .dispatch::<
+                slice_root::ice::AdapterNotFoundException,
+            >(
+                &String::from("findAdapterById"),
+                1u8,
+                &Encapsulation::from(bytes),
+                context,
+            )
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            ice_rs::proxy::Proxy,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+    async fn get_registry(
+        &mut self,
+        context: Option<HashMap<String, String>>,
+    ) -> Result<
+        ice_rs::proxy::TypedProxy<slice_root::ice::LocatorRegistryPrx>,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let bytes = Vec::new();
+        let reply = self
+            .proxy
+            .dispatch::<
+                ProtocolError,
+            >(&String::from("getRegistry"), 1u8, &Encapsulation::from(bytes), context)
+            .await?;
+        let mut read_bytes: i32 = 0;
+        <ice_rs::proxy::TypedProxy<
+            slice_root::ice::LocatorRegistryPrx,
+        >>::from_bytes(
+            &reply.body.data[read_bytes as usize..reply.body.data.len()],
+            &mut read_bytes,
+        )
+    }
+}
+impl From<LocatorPrx> for slice_root::ice::LocatorPrx {
+    fn from(proxy: LocatorPrx) -> slice_root::ice::LocatorPrx {
+        slice_root::ice::LocatorPrx {
+            proxy: proxy.proxy,
+        }
+    }
+}
+impl From<Proxy> for LocatorPrx {
+    fn from(proxy: Proxy) -> Self {
+        Self { proxy: proxy }
+    }
+}
+impl LocatorPrx {
+    #[allow(dead_code)]
+    pub async fn unchecked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self { proxy: proxy })
+    }
+    #[allow(dead_code)]
+    pub async fn checked_cast(
+        proxy: Proxy,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut my_proxy = Self::unchecked_cast(proxy).await?;
+        if !my_proxy.ice_is_a().await? {
+            return Err(Box::new(ProtocolError::new("ice_is_a() failed")));
+        }
+        Ok(my_proxy)
+    }
+}
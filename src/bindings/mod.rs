@@ -0,0 +1,6 @@
+#[allow(dead_code)]
+#[allow(unused_imports)]
+use async_trait::async_trait;
+pub mod glacier_2;
+pub mod ice;
+pub mod ice_grid;
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+
+use crate::adapter::{Adapter, Dispatcher};
+use crate::protocol::EndPointType;
+
+struct CollocatedAdapter {
+    communicator_id: u64,
+    adapter_id: Option<String>,
+    endpoint: EndPointType,
+    dispatcher: Dispatcher
+}
+
+lazy_static! {
+    static ref ADAPTERS: Mutex<Vec<CollocatedAdapter>> = Mutex::new(Vec::new());
+}
+
+/// Makes the adapter available to collocated proxies of the same
+/// communicator. Adapters created by the communicator are registered
+/// automatically. Indirect proxies find the adapter by its
+/// `<name>.AdapterId` property.
+pub fn register(adapter: &Adapter) {
+    let init_data = adapter.init_data();
+    let communicator_id = init_data.communicator_id();
+    let mut adapters = ADAPTERS.lock().unwrap();
+    adapters.retain(|collocated| collocated.communicator_id != communicator_id || collocated.dispatcher.name() != adapter.name());
+    adapters.push(CollocatedAdapter {
+        communicator_id,
+        adapter_id: init_data.properties().get(&format!("{}.AdapterId", adapter.name())).cloned(),
+        endpoint: adapter.endpoint(),
+        dispatcher: adapter.dispatcher()
+    });
+}
+
+pub (crate) fn unregister(dispatcher: &Dispatcher) {
+    if let Ok(mut adapters) = ADAPTERS.lock() {
+        adapters.retain(|collocated| !collocated.dispatcher.ptr_eq(dispatcher));
+    }
+}
+
+/// Updates the endpoint of a registered adapter once it is bound.
+pub (crate) fn update_endpoint(dispatcher: &Dispatcher, endpoint: EndPointType) {
+    if let Some(collocated) = ADAPTERS.lock().unwrap().iter_mut().find(|collocated| collocated.dispatcher.ptr_eq(dispatcher)) {
+        collocated.endpoint = endpoint;
+    }
+}
+
+/// Returns the dispatcher of the adapter of communicator `communicator_id`
+/// listening on `endpoint`.
+pub fn find_by_endpoint(communicator_id: u64, endpoint: &EndPointType) -> Option<Dispatcher> {
+    ADAPTERS.lock().unwrap().iter()
+        .find(|collocated| collocated.communicator_id == communicator_id && same_endpoint(&collocated.endpoint, endpoint))
+        .map(|collocated| collocated.dispatcher.clone())
+}
+
+/// Returns the dispatcher and endpoint of the adapter of communicator
+/// `communicator_id` with the given adapter id.
+pub fn find_by_adapter_id(communicator_id: u64, adapter_id: &str) -> Option<(Dispatcher, EndPointType)> {
+    ADAPTERS.lock().unwrap().iter()
+        .find(|collocated| collocated.communicator_id == communicator_id && collocated.adapter_id.as_deref() == Some(adapter_id))
+        .map(|collocated| (collocated.dispatcher.clone(), collocated.endpoint.clone()))
+}
+
+fn same_endpoint(a: &EndPointType, b: &EndPointType) -> bool {
+    match (a, b) {
+        (EndPointType::MEM(a), EndPointType::MEM(b)) => a.name == b.name,
+        (EndPointType::WellKnownObject(_), _) | (_, EndPointType::WellKnownObject(_)) => false,
+        _ => {
+            a.protocol() == b.protocol() && a.port() == b.port() && a.host().eq_ignore_ascii_case(b.host())
+        }
+    }
+}
@@ -1,41 +1,58 @@
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::{proxy::Proxy, proxy_factory::ProxyFactory};
+use crate::{proxy::Proxy, proxy_factory::ProxyFactory, proxy_parser::DirectProxyData};
 use crate::initdata::InitializationData;
 use crate::errors::PropertyError;
 use crate::adapter::*;
+use crate::interceptor::ClientInterceptor;
+use crate::ssl::CertificateVerifier;
 use lazy_static::lazy_static;
 
 lazy_static! {
     pub static ref INITDATA: Mutex<InitializationData> = Mutex::new(InitializationData::new());
 }
 
+static NEXT_COMMUNICATOR_ID: AtomicU64 = AtomicU64::new(1);
+
 /// The Communicator is a basic object in ZeroC Ice. Currently
 /// this is more a stub that does dummy initialization.
 pub struct Communicator {
-    proxy_factory: ProxyFactory
+    id: u64,
+    proxy_factory: ProxyFactory,
+    certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+    client_interceptors: Vec<Arc<dyn ClientInterceptor>>
 }
 
 impl Communicator {
     pub async fn new() -> Result<Communicator, Box<dyn std::error::Error + Sync + Send>> {
-        let init_data = INITDATA.lock().unwrap();
-        let proxy_factory = ProxyFactory::new(init_data.properties()).await?;
+        let id = NEXT_COMMUNICATOR_ID.fetch_add(1, Ordering::SeqCst);
+        let proxy_factory = ProxyFactory::new(&communicator_init_data(id)).await?;
         Ok(Communicator {
-            proxy_factory
+            id,
+            proxy_factory,
+            certificate_verifier: None,
+            client_interceptors: vec![]
         })
     }
 
     pub async fn string_to_proxy(&mut self, proxy_string: &str) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
-        let init_data = INITDATA.lock().unwrap();
-        self.proxy_factory.create(proxy_string, init_data.properties()).await
+        let init_data = self.init_data();
+        self.proxy_factory.create(proxy_string, &init_data).await
+    }
+
+    /// Creates a proxy for the object `proxy_data.ident` at `proxy_data.endpoint`.
+    pub async fn create_proxy(&self, proxy_data: DirectProxyData) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        let init_data = self.init_data();
+        ProxyFactory::create_proxy(proxy_data, &init_data, None).await
     }
 
     pub async fn property_to_proxy(&mut self, property: &str) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
-        let init_data = INITDATA.lock().unwrap();
+        let init_data = self.init_data();
         let properties = init_data.properties();
         match properties.get(property) {
             Some(value) => {
-                self.proxy_factory.create(value, &properties).await
+                self.proxy_factory.create(value, &init_data).await
             }
             None => {
                 Err(Box::new(PropertyError::new(property)))
@@ -44,16 +61,120 @@ impl Communicator {
     }
 
     pub async fn create_object_adapter_with_endpoint(&self, name: &str, endpoint: &str) -> Result<Adapter, Box<dyn std::error::Error + Sync + Send>> {
-        Adapter::with_endpoint(name, endpoint)
+        let init_data = self.init_data();
+        let adapter = Adapter::with_endpoint(name, endpoint, &init_data)?;
+        crate::collocation::register(&adapter);
+        Ok(adapter)
+    }
+
+    /// Installs a verifier that is asked to accept every SSL connection
+    /// of this communicator once the handshake succeeded.
+    pub fn set_certificate_verifier(&mut self, verifier: Arc<dyn CertificateVerifier>) {
+        self.certificate_verifier = Some(verifier);
+    }
+
+    /// Installs an interceptor that runs around the requests of all
+    /// proxies this communicator creates afterwards.
+    pub fn add_client_interceptor(&mut self, interceptor: Arc<dyn ClientInterceptor>) {
+        self.client_interceptors.push(interceptor);
+    }
+
+    /// Returns the initialization data for adapters and proxies of this
+    /// communicator, the global settings with its own verifier and
+    /// interceptors added.
+    fn init_data(&self) -> InitializationData {
+        let mut init_data = communicator_init_data(self.id);
+        if let Some(verifier) = &self.certificate_verifier {
+            init_data.set_certificate_verifier(verifier.clone());
+        }
+        for interceptor in &self.client_interceptors {
+            init_data.add_client_interceptor(interceptor.clone());
+        }
+        init_data
     }
 }
 
 pub async fn initialize(config_file: &str) -> Result<Communicator, Box<dyn std::error::Error + Sync + Send>> {
-    let mut init_data = INITDATA.lock().unwrap();
-    let properties = init_data.properties_as_mut();
-    properties.load(config_file).unwrap();
-    let proxy_factory = ProxyFactory::new(&properties).await?;
-    Ok(Communicator {
-        proxy_factory
-    })
-}
\ No newline at end of file
+    {
+        let mut init_data = INITDATA.lock().unwrap();
+        let properties = init_data.properties_as_mut();
+        properties.load(config_file).unwrap();
+    }
+    Communicator::new().await
+}
+
+/// Returns the initialization data for adapters and proxies of the
+/// communicator `id`.
+fn communicator_init_data(id: u64) -> InitializationData {
+    let mut init_data = INITDATA.lock().unwrap().clone();
+    init_data.set_communicator_id(id);
+    init_data
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::bindings::ice::{ProcessI, ProcessServer};
+    use crate::current::Current;
+    use crate::errors::ProtocolError;
+    use crate::interceptor::ClientInterceptor;
+    use crate::protocol::{Encapsulation, ReplyData, RequestData};
+    use crate::transport::ConnectionInfo;
+
+    struct AcceptVerifier {}
+
+    impl CertificateVerifier for AcceptVerifier {
+        fn verify(&self, _info: &ConnectionInfo) -> bool {
+            true
+        }
+    }
+
+    struct BlockInterceptor {}
+
+    #[async_trait]
+    impl ClientInterceptor for BlockInterceptor {
+        async fn before_request(&self, _request: &mut RequestData) -> Result<Option<ReplyData>, Box<dyn std::error::Error + Sync + Send>> {
+            Err(Box::new(ProtocolError::new("blocked")))
+        }
+    }
+
+    struct ProcessImpl {}
+
+    #[async_trait]
+    impl ProcessI for ProcessImpl {
+        async fn shutdown(&mut self, _current: &Current) -> () {}
+
+        async fn write_message(&mut self, _message: &String, _fd: i32, _current: &Current) -> () {}
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_certificate_verifier_scope() {
+        let mut first = Communicator::new().await.expect("Cannot create communicator");
+        let second = Communicator::new().await.expect("Cannot create communicator");
+        first.set_certificate_verifier(Arc::new(AcceptVerifier {}));
+
+        assert!(first.init_data().certificate_verifier().is_some());
+        assert!(second.init_data().certificate_verifier().is_none());
+        let later = Communicator::new().await.expect("Cannot create communicator");
+        assert!(later.init_data().certificate_verifier().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_client_interceptor_scope() {
+        let mut first = Communicator::new().await.expect("Cannot create communicator");
+        let mut second = Communicator::new().await.expect("Cannot create communicator");
+        first.add_client_interceptor(Arc::new(BlockInterceptor {}));
+
+        let adapter = first.create_object_adapter_with_endpoint("interceptorscope", "mem -n interceptorscope").await.expect("Cannot create adapter");
+        adapter.add("process", Box::new(ProcessServer::new(Box::new(ProcessImpl {}))));
+        adapter.activate().await.expect("Cannot activate adapter");
+
+        let mut proxy = first.string_to_proxy("process:mem -n interceptorscope").await.expect("Cannot create proxy");
+        let error = proxy.dispatch::<ProtocolError>("ice_ping", 1, &Encapsulation::empty(), None).await.expect_err("Interceptor did not run");
+        assert_eq!("ProtocolError: blocked", error.to_string());
+
+        let mut proxy = second.string_to_proxy("process:mem -n interceptorscope").await.expect("Cannot create proxy");
+        proxy.dispatch::<ProtocolError>("ice_ping", 1, &Encapsulation::empty(), None).await.expect("Interceptor of another communicator ran");
+        assert!(Communicator::new().await.expect("Cannot create communicator").init_data().client_interceptors().is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use crate::protocol::{Identity, RequestData};
+use crate::transport::ConnectionInfo;
+
+/// The `Current` holds information about the request that is
+/// currently dispatched. It is passed to all servant operations.
+#[derive(Clone, Debug)]
+pub struct Current {
+    pub adapter: String,
+    pub connection: ConnectionInfo,
+    pub id: Identity,
+    pub facet: String,
+    pub operation: String,
+    pub mode: u8,
+    pub context: HashMap<String, String>,
+    pub request_id: i32
+}
+
+impl Current {
+    pub fn new(adapter: &str, connection: &ConnectionInfo, request: &RequestData) -> Current {
+        Current {
+            adapter: String::from(adapter),
+            connection: connection.clone(),
+            id: request.id.clone(),
+            facet: request.facet.first().cloned().unwrap_or_default(),
+            operation: request.operation.clone(),
+            mode: request.mode,
+            context: request.context.clone(),
+            request_id: request.request_id
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use crate::protocol::*;
 use crate::errors::*;
+use crate::proxy::TypedProxy;
 use std::convert::TryInto;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::hash::Hash;
 use num_enum::TryFromPrimitive;
 use std::convert::TryFrom;
 
 /// The `IceSize` is a special wrapper around i32
 /// and is encoded in one byte if smaller than 255.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IceSize {
     pub size: i32
 }
@@ -29,12 +30,48 @@ pub trait OptionalType {
     fn optional_type() -> u8;
 }
 
+impl OptionalType for bool {
+    fn optional_type() -> u8 {
+        0
+    }
+}
+
+impl OptionalType for u8 {
+    fn optional_type() -> u8 {
+        0
+    }
+}
+
+impl OptionalType for i16 {
+    fn optional_type() -> u8 {
+        1
+    }
+}
+
 impl OptionalType for i32 {
     fn optional_type() -> u8 {
         2
     }
 }
 
+impl OptionalType for f32 {
+    fn optional_type() -> u8 {
+        2
+    }
+}
+
+impl OptionalType for i64 {
+    fn optional_type() -> u8 {
+        3
+    }
+}
+
+impl OptionalType for f64 {
+    fn optional_type() -> u8 {
+        3
+    }
+}
+
 impl OptionalType for String {
     fn optional_type() -> u8 {
         5
@@ -248,6 +285,34 @@ impl<T: FromBytes + Eq + Hash, U: FromBytes> FromBytes for HashMap<T, U> {
     }
 }
 
+impl<T: ToBytes, U: ToBytes> ToBytes for BTreeMap<T, U> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut bytes = IceSize{size: self.len() as i32}.to_bytes()?;
+        for (key, value) in self {
+            bytes.extend(key.to_bytes()?);
+            bytes.extend(value.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<T: FromBytes + Ord, U: FromBytes> FromBytes for BTreeMap<T, U> {
+    fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>>
+    where Self: Sized {
+        let mut read = 0;
+        let size = IceSize::from_bytes(bytes, &mut read)?.size;
+        let mut dict: BTreeMap<T, U> = BTreeMap::new();
+
+        for _i in 0..size {
+            let key = T::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+            let value = U::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+            dict.insert(key, value);
+        }
+        *read_bytes += read;
+        Ok(dict)
+    }
+}
+
 impl<T: ToBytes> ToBytes for Vec<T>
 {
     fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
@@ -275,6 +340,25 @@ impl<T: FromBytes> FromBytes for Vec<T>
     }
 }
 
+impl<T: ToBytes> ToBytes for VecDeque<T>
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut bytes = IceSize{size: self.len() as i32}.to_bytes()?;
+        for item in self {
+            bytes.extend(item.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<T: FromBytes> FromBytes for VecDeque<T>
+{
+    fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>>
+    where Self: Sized {
+        Ok(VecDeque::from(Vec::<T>::from_bytes(bytes, read_bytes)?))
+    }
+}
+
 impl ToBytes for u8 {
     fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
         Ok(vec![*self])
@@ -463,10 +547,23 @@ impl FromBytes for Encapsulation {
     }
 }
 
+impl ToBytes for ReplyData {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let mut buffer = self.request_id.to_bytes()?;
+        buffer.extend(self.status.to_bytes()?);
+        match self.status {
+            0 | 1 => buffer.extend(self.body.to_bytes()?),
+            _ => buffer.extend(&self.body.data)
+        }
+        Ok(buffer)
+    }
+}
+
 impl FromBytes for ReplyData {
     fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
         let mut read: i32 = 0;
-        if bytes.len() < 11 {
+        if bytes.len() < 5 {
             return Err(Box::new(ProtocolError::new("Not enough bytes to read ReplyData")));
         }
 
@@ -482,21 +579,132 @@ impl FromBytes for ReplyData {
                     body: encapsulation
                 })
             }
-            // 1 => {
-            //     Err(Error::EncapsulatedUserException(Encapsulation::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?))
-            // }
-            7 => {
-                Err(Box::new(
-                    RemoteException {
-                        cause: String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
-                    }
-                ))
+            2..=7 => {
+                let body = Encapsulation::from(bytes[read as usize..bytes.len()].to_vec());
+                *read_bytes += bytes.len() as i32;
+                Ok(ReplyData {
+                    request_id,
+                    status,
+                    body
+                })
             }
             _ => Err(Box::new(ProtocolError::new(&format!("Unsupported ReplyData status: {}", status))))
         }
     }
 }
 
+impl ReplyData {
+    /// Creates the reply to a request whose object (status 2), facet
+    /// (status 3) or operation (status 4) does not exist.
+    pub fn request_failed(request: &RequestData, status: u8) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        let mut bytes = request.id.to_bytes()?;
+        bytes.extend(request.facet.to_bytes()?);
+        bytes.extend(request.operation.to_bytes()?);
+        Ok(ReplyData {
+            request_id: request.request_id,
+            status,
+            body: Encapsulation::from(bytes)
+        })
+    }
+
+    /// Creates the reply to a request that failed with an error other than
+    /// a user exception (status 7). Clients raise it as `RemoteException`.
+    pub fn unknown_exception(request_id: i32, cause: &str) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(ReplyData {
+            request_id,
+            status: 7,
+            body: Encapsulation::from(String::from(cause).to_bytes()?)
+        })
+    }
+
+    /// Returns the error carried by a reply with a status other than 0 or 1.
+    pub fn error(&self) -> Result<Option<Box<dyn std::error::Error + Sync + Send>>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut read = 0;
+        match self.status {
+            2..=4 => {
+                let id = Identity::from_bytes(&self.body.data, &mut read)?;
+                let facet = Vec::<String>::from_bytes(&self.body.data[read as usize..], &mut read)?;
+                let operation = String::from_bytes(&self.body.data[read as usize..], &mut read)?;
+                let facet = facet.first().cloned().unwrap_or_default();
+                Ok(Some(match self.status {
+                    2 => Box::new(ObjectNotExistException::new(id, &facet, &operation)),
+                    3 => Box::new(FacetNotExistException::new(id, &facet, &operation)),
+                    _ => Box::new(OperationNotExistException::new(id, &facet, &operation))
+                }))
+            }
+            5..=7 => {
+                Ok(Some(Box::new(RemoteException {
+                    cause: String::from_bytes(&self.body.data, &mut read)?
+                })))
+            }
+            _ => Ok(None)
+        }
+    }
+}
+
+impl RequestData {
+    /// Encodes the request as part of a batch request message, which
+    /// omits the request id.
+    pub fn to_batch_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(self.to_bytes()?[4..].to_vec())
+    }
+
+    /// Decodes a request of a batch request message. Batched requests
+    /// have no reply, so the request id is always 0.
+    pub fn from_batch_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let mut read: i32 = 0;
+        let id = Identity::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let facet = Vec::<String>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let operation = String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let mode = u8::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let context = HashMap::<String, String>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        // the encapsulation must not consume the following requests
+        let size = i32::from_bytes(&bytes[read as usize..bytes.len()], &mut 0)?;
+        if size < 6 || read as usize + size as usize > bytes.len() {
+            return Err(Box::new(ProtocolError::new("Not enough bytes to read batch request")));
+        }
+        let params = Encapsulation::from_bytes(&bytes[read as usize..read as usize + size as usize], &mut read)?;
+        *read_bytes += read;
+
+        Ok(RequestData {
+            request_id: 0,
+            id,
+            facet,
+            operation,
+            mode,
+            context,
+            params
+        })
+    }
+}
+
+impl ToBytes for UdpEndpointData {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut bytes = self.host.to_bytes()?;
+        bytes.extend(self.port.to_bytes()?);
+        bytes.extend(self.compress.to_bytes()?);
+        Ok(bytes)
+    }
+}
+
+impl FromBytes for UdpEndpointData {
+    fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let mut read: i32 = 0;
+        let host = String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let port = i32::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        let compress = bool::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+        *read_bytes += read;
+
+        Ok(UdpEndpointData {
+            host,
+            port,
+            compress,
+            interface: String::new(),
+            ttl: -1
+        })
+    }
+}
+
 impl ToBytes for Header {
     fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>>
     {
@@ -609,57 +817,112 @@ impl<T: FromBytes> FromBytes for OptionalWrapper<T> {
     }
 }
 
+impl ToBytes for LocatorResult {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        if self.is_nil() {
+            return Identity::default().to_bytes();
+        }
+
+        let mut bytes = self.proxy_data.to_bytes()?;
+        bytes.extend(IceSize { size: self.endpoints.len() as i32 }.to_bytes()?);
+        if self.endpoints.is_empty() {
+            bytes.extend(self.adapter_id.to_bytes()?);
+            return Ok(bytes);
+        }
+        for endpoint in &self.endpoints {
+            let (endpoint_type, data) = match endpoint {
+                EndPointType::TCP(data) => (1i16, data.to_bytes()?),
+                EndPointType::SSL(data) => (2i16, data.to_bytes()?),
+                EndPointType::UDP(data) => (3i16, data.to_bytes()?),
+                EndPointType::WS(data) => (4i16, data.to_bytes()?),
+                EndPointType::WSS(data) => (5i16, data.to_bytes()?),
+                EndPointType::MEM(_) => {
+                    return Err(Box::new(ProtocolError::new("A mem endpoint cannot be encoded")));
+                }
+                EndPointType::WellKnownObject(_) => {
+                    return Err(Box::new(ProtocolError::new("An adapter is not an endpoint, use adapter_id instead")));
+                }
+            };
+            bytes.extend(endpoint_type.to_bytes()?);
+            bytes.extend(Encapsulation {
+                size: data.len() as i32 + 6,
+                major: 1,
+                minor: 1,
+                data
+            }.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+}
+
 impl FromBytes for LocatorResult {
     fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
         let mut read = 0;
 
+        let mut identity_read = 0;
+        let identity = Identity::from_bytes(bytes, &mut identity_read)?;
+        if identity.name.is_empty() {
+            *read_bytes += identity_read;
+            return Ok(LocatorResult::default());
+        }
+
         let proxy_data = ProxyData::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
         let size = IceSize::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
 
         if size.size == 0 {
-            return Err(Box::new(ProtocolError::new("Error reading LocatorResult")));
+            let adapter_id = String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+            *read_bytes += read;
+            return Ok(LocatorResult {
+                proxy_data,
+                endpoints: vec![],
+                adapter_id
+            });
         }
 
-        match u8::from_bytes(&bytes[read as usize..bytes.len()], &mut read)? {
-            1 => {
-                let _unsued = i16::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
-                let encapsulation = Encapsulation::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
-                let tcp = EndpointData::from_bytes(&encapsulation.data[0..encapsulation.data.len()], &mut read)?;
-                *read_bytes = *read_bytes + read;
-                Ok(LocatorResult {
-                    proxy_data: proxy_data,
-                    size: size,
-                    endpoint: EndPointType::TCP(tcp)
-                })
-            },
-            2 => {
-                let _unsued = i16::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
-                let encapsulation = Encapsulation::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
-                let ssl = EndpointData::from_bytes(&encapsulation.data[0..encapsulation.data.len()], &mut read)?;
-                *read_bytes = *read_bytes + read;
-                Ok(LocatorResult {
-                    proxy_data: proxy_data,
-                    size: size,
-                    endpoint: EndPointType::SSL(ssl)
-                })
+        let mut endpoints = vec![];
+        for _ in 0..size.size {
+            let endpoint_type = i16::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
+            let encapsulation_size = i32::from_bytes(&bytes[read as usize..bytes.len()], &mut 0)?;
+            let end = read as usize + encapsulation_size.max(6) as usize;
+            if end > bytes.len() {
+                return Err(Box::new(ProtocolError::new("Not enough bytes to read endpoint")));
             }
-            0 => {
-                let object = String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
-                *read_bytes = *read_bytes + read;
-                Ok(LocatorResult {
-                    proxy_data: proxy_data,
-                    size: size,
-                    endpoint: EndPointType::WellKnownObject(object)
-                })
-            }
-            _ => {
-                Err(Box::new(ProtocolError::new("Unsupported endpoint type")))
+            let encapsulation = Encapsulation::from_bytes(&bytes[read as usize..end], &mut read)?;
+            let data = &encapsulation.data;
+            match endpoint_type {
+                1 => endpoints.push(EndPointType::TCP(EndpointData::from_bytes(data, &mut 0)?)),
+                2 => endpoints.push(EndPointType::SSL(EndpointData::from_bytes(data, &mut 0)?)),
+                3 => endpoints.push(EndPointType::UDP(UdpEndpointData::from_bytes(data, &mut 0)?)),
+                4 => endpoints.push(EndPointType::WS(WsEndpointData::from_bytes(data, &mut 0)?)),
+                5 => endpoints.push(EndPointType::WSS(WsEndpointData::from_bytes(data, &mut 0)?)),
+                // endpoints of unknown transports are skipped
+                _ => {}
             }
         }
+        *read_bytes += read;
+
+        if endpoints.is_empty() {
+            return Err(Box::new(ProtocolError::new("Unsupported endpoint type")));
+        }
+        Ok(LocatorResult {
+            proxy_data,
+            endpoints,
+            adapter_id: String::new()
+        })
     }
 }
 
+impl<T> ToBytes for TypedProxy<T> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
+        self.data.to_bytes()
+    }
+}
 
+impl<T> FromBytes for TypedProxy<T> {
+    fn from_bytes(bytes: &[u8], read_bytes: &mut i32) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(TypedProxy::from(LocatorResult::from_bytes(bytes, read_bytes)?))
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -711,6 +974,25 @@ mod test {
         assert_eq!(seq, decoded);
     }
 
+    #[test]
+    fn test_collection_encoding() {
+        let mut read_bytes = 0;
+        let seq: VecDeque<i32> = vec![1, 2, 3].into_iter().collect();
+        let encoded = seq.to_bytes().expect("Cannot encode test deque");
+        assert_eq!(vec![1, 2, 3].to_bytes().expect("Cannot encode test seq"), encoded);
+        let decoded = VecDeque::<i32>::from_bytes(&encoded, &mut read_bytes).expect("Cannot decode test deque");
+        assert_eq!(seq, decoded);
+        assert_eq!(encoded.len() as i32, read_bytes);
+
+        let mut read_bytes = 0;
+        let mut dict = BTreeMap::new();
+        dict.insert(2, String::from("two"));
+        dict.insert(1, String::from("one"));
+        let encoded = dict.to_bytes().expect("Cannot encode test dict");
+        let decoded = BTreeMap::<i32, String>::from_bytes(&encoded, &mut read_bytes).expect("Cannot decode test dict");
+        assert_eq!(dict, decoded);
+    }
+
     #[test]
     fn test_short_encoding() {
         let mut read_bytes = 0;
@@ -832,4 +1114,122 @@ mod test {
         assert_eq!(reply.request_id, decoded.request_id);
         assert_eq!(reply.status, decoded.status);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_object_not_exist_reply_encoding() {
+        let request = RequestData {
+            request_id: 3,
+            id: Identity::new("db/row1"),
+            facet: vec![],
+            operation: String::from("get"),
+            mode: 0,
+            context: HashMap::new(),
+            params: Encapsulation::empty()
+        };
+        let reply = ReplyData::request_failed(&request, 2).expect("Cannot create reply");
+        let bytes = reply.to_bytes().expect("Cannot encode test reply");
+        let mut read_bytes = 0;
+        let decoded = ReplyData::from_bytes(&bytes, &mut read_bytes).expect("Cannot decode test reply");
+        assert_eq!(bytes.len() as i32, read_bytes);
+        let error = decoded.error().expect("Cannot decode error").expect("Missing error");
+        let error = error.downcast_ref::<ObjectNotExistException>().expect("Expected ObjectNotExistException");
+        assert_eq!(request.id, error.id);
+        assert_eq!("get", error.operation);
+
+        let reply = ReplyData::request_failed(&request, 4).expect("Cannot create reply");
+        let error = reply.error().expect("Cannot decode error").expect("Missing error");
+        assert!(error.downcast_ref::<OperationNotExistException>().is_some());
+    }
+
+    #[test]
+    fn test_declared_exceptions() {
+        use crate::bindings::glacier_2::{CannotCreateSessionException, PermissionDeniedException};
+        use crate::bindings::ice::ObjectNotFoundException;
+
+        let mut bytes = SliceFlags {
+            type_id: SliceFlagsTypeEncoding::StringTypeId,
+            optional_members: false,
+            indirection_table: false,
+            slice_size: false,
+            last_slice: true
+        }.to_bytes().expect("Cannot encode slice flags");
+        bytes.extend(String::from("::Glacier2::CannotCreateSessionException").to_bytes().expect("Cannot encode type id"));
+        bytes.extend(String::from("no session").to_bytes().expect("Cannot encode reason"));
+
+        let error = <(PermissionDeniedException, CannotCreateSessionException)>::decode(&bytes).expect("Cannot decode exception");
+        let error = error.downcast_ref::<UserError<CannotCreateSessionException>>().expect("Expected CannotCreateSessionException");
+        assert_eq!("no session", error.exception.reason);
+
+        let error = <(PermissionDeniedException, ObjectNotFoundException)>::decode(&bytes).expect("Cannot decode exception");
+        let error = error.downcast_ref::<RemoteException>().expect("Expected RemoteException");
+        assert_eq!("Undeclared user exception ::Glacier2::CannotCreateSessionException", error.cause);
+    }
+
+    #[test]
+    fn test_proxy_encoding() {
+        let proxy = LocatorResult {
+            proxy_data: ProxyData {
+                id: Identity::new("hello"),
+                facet: vec![],
+                mode: 0,
+                secure: false,
+                protocol: Version { major: 1, minor: 0 },
+                encoding: Version { major: 1, minor: 1 }
+            },
+            endpoints: vec![
+                EndPointType::TCP(EndpointData {
+                    host: String::from("localhost"),
+                    port: 10000,
+                    timeout: 60000,
+                    compress: false
+                }),
+                EndPointType::WS(WsEndpointData {
+                    host: String::from("localhost"),
+                    port: 10001,
+                    timeout: 60000,
+                    compress: false,
+                    resource: String::from("/ice")
+                })
+            ],
+            adapter_id: String::new()
+        };
+        let mut bytes = proxy.to_bytes().expect("Cannot encode test proxy");
+        bytes.push(42);
+        let mut read_bytes = 0;
+        let decoded = LocatorResult::from_bytes(&bytes, &mut read_bytes).expect("Cannot decode test proxy");
+        assert_eq!(proxy, decoded);
+        assert_eq!(bytes.len() as i32 - 1, read_bytes);
+
+        // an endpoint of an unknown transport between the known ones is skipped
+        let mut bytes = proxy.proxy_data.to_bytes().expect("Cannot encode proxy data");
+        bytes.extend(IceSize { size: 3 }.to_bytes().unwrap());
+        for (endpoint_type, data) in [(1i16, proxy.endpoints[0].clone()), (99, proxy.endpoints[0].clone()), (4, proxy.endpoints[1].clone())] {
+            let data = match data {
+                EndPointType::TCP(data) => data.to_bytes().unwrap(),
+                EndPointType::WS(data) => data.to_bytes().unwrap(),
+                _ => unreachable!()
+            };
+            bytes.extend(endpoint_type.to_bytes().unwrap());
+            bytes.extend(Encapsulation { size: data.len() as i32 + 6, major: 1, minor: 1, data }.to_bytes().unwrap());
+        }
+        let mut read_bytes = 0;
+        assert_eq!(proxy, LocatorResult::from_bytes(&bytes, &mut read_bytes).expect("Cannot decode test proxy"));
+        assert_eq!(bytes.len() as i32, read_bytes);
+
+        let adapter = LocatorResult {
+            endpoints: vec![],
+            adapter_id: String::from("HelloAdapter"),
+            ..proxy
+        };
+        let bytes = adapter.to_bytes().expect("Cannot encode test proxy");
+        let mut read_bytes = 0;
+        assert_eq!(adapter, LocatorResult::from_bytes(&bytes, &mut read_bytes).expect("Cannot decode test proxy"));
+        assert_eq!(bytes.len() as i32, read_bytes);
+
+        let bytes = LocatorResult::default().to_bytes().expect("Cannot encode nil proxy");
+        assert_eq!(vec![0, 0], bytes);
+        let mut read_bytes = 0;
+        assert!(LocatorResult::from_bytes(&bytes, &mut read_bytes).expect("Cannot decode nil proxy").is_nil());
+        assert_eq!(2, read_bytes);
+    }
+}
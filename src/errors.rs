@@ -1,5 +1,6 @@
 use std::fmt::Display;
-use crate::encoding::FromBytes;
+use crate::encoding::{FromBytes, SliceFlags};
+use crate::protocol::Identity;
 
 /// A `ProtocolError` indicates a problem related to the
 /// ice protocol. It may be unexpected messages or problems
@@ -18,17 +19,100 @@ impl ProtocolError {
 }
 
 /// A `ParsingError` appears when a problem occurs parsing ice
-/// files.
+/// files. The error may know the byte offset in the Slice file it
+/// refers to, or the Slice text it is about.
 #[derive(Debug)]
 pub struct ParsingError {
-    detail: String
+    detail: String,
+    position: Option<usize>,
+    subject: Option<String>
 }
 
 impl ParsingError {
     pub fn new(detail: &str) -> ParsingError {
         ParsingError {
-            detail: String::from(detail)
+            detail: String::from(detail),
+            position: None,
+            subject: None
+        }
+    }
+
+    /// Creates an error at the byte offset `position` of the parsed file.
+    pub fn at(detail: &str, position: usize) -> ParsingError {
+        ParsingError {
+            detail: String::from(detail),
+            position: Some(position),
+            subject: None
+        }
+    }
+
+    /// Creates an error about the Slice text `subject`, like the name of
+    /// an unknown type. It is located once the enclosing definition is
+    /// known.
+    pub fn about(detail: &str, subject: &str) -> ParsingError {
+        ParsingError {
+            detail: String::from(detail),
+            position: None,
+            subject: Some(String::from(subject))
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+}
+
+/// A `SliceError` is a located `ParsingError`. It is displayed like a
+/// compiler diagnostic with the file, line, column and the source line.
+/// `Debug` shows the same text, so build scripts returning the error
+/// print a readable message.
+pub struct SliceError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String
+}
+
+impl SliceError {
+    /// Creates the diagnostic of `message` at the byte offset `position`
+    /// of `content`, the text of `file`.
+    pub fn new(file: &str, content: &str, position: usize, message: &str) -> SliceError {
+        let mut position = position.min(content.len());
+        while !content.is_char_boundary(position) {
+            position -= 1;
+        }
+        let line_start = content[..position].rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let line_end = content[position..].find('\n').map(|index| position + index).unwrap_or(content.len());
+        SliceError {
+            file: String::from(file),
+            line: content[..position].matches('\n').count() + 1,
+            column: content[line_start..position].chars().count() + 1,
+            snippet: String::from(content[line_start..line_end].trim_end()),
+            message: String::from(message)
+        }
+    }
+
+    /// Converts an error raised while processing `content`, the text of
+    /// `file`, into a `SliceError`. A `SliceError` is returned unchanged,
+    /// as it belongs to an included file.
+    pub fn from_error(file: &str, content: &str, error: Box<dyn std::error::Error>) -> Box<dyn std::error::Error> {
+        if error.is::<SliceError>() {
+            return error;
         }
+        let (message, position) = match error.downcast_ref::<ParsingError>() {
+            Some(parsing_error) => (String::from(parsing_error.detail()), parsing_error.position().unwrap_or(0)),
+            None => (error.to_string(), 0)
+        };
+        Box::new(SliceError::new(file, content, position, &message))
     }
 }
 
@@ -47,6 +131,21 @@ impl PropertyError {
     }
 }
 
+/// A `SecurityException` is raised when a secure connection
+/// cannot be established or the peer is not trusted.
+#[derive(Debug)]
+pub struct SecurityException {
+    pub reason: String
+}
+
+impl SecurityException {
+    pub fn new(reason: &str) -> SecurityException {
+        SecurityException {
+            reason: String::from(reason)
+        }
+    }
+}
+
 /// A `RemoteException` is raised when the remote application
 /// raises any error that is not an `UserError`.
 #[derive(Debug)]
@@ -54,6 +153,133 @@ pub struct RemoteException {
     pub cause: String
 }
 
+/// An `ObjectNotExistException` is raised when the target adapter
+/// has no servant for the identity of a request.
+#[derive(Debug)]
+pub struct ObjectNotExistException {
+    pub id: Identity,
+    pub facet: String,
+    pub operation: String
+}
+
+impl ObjectNotExistException {
+    pub fn new(id: Identity, facet: &str, operation: &str) -> ObjectNotExistException {
+        ObjectNotExistException {
+            id,
+            facet: String::from(facet),
+            operation: String::from(operation)
+        }
+    }
+}
+
+/// A `FacetNotExistException` is raised when the target object of a
+/// request exists, but not the requested facet.
+#[derive(Debug)]
+pub struct FacetNotExistException {
+    pub id: Identity,
+    pub facet: String,
+    pub operation: String
+}
+
+impl FacetNotExistException {
+    pub fn new(id: Identity, facet: &str, operation: &str) -> FacetNotExistException {
+        FacetNotExistException {
+            id,
+            facet: String::from(facet),
+            operation: String::from(operation)
+        }
+    }
+}
+
+/// An `OperationNotExistException` is raised when the target object of a
+/// request exists, but does not implement the requested operation.
+#[derive(Debug)]
+pub struct OperationNotExistException {
+    pub id: Identity,
+    pub facet: String,
+    pub operation: String
+}
+
+impl OperationNotExistException {
+    pub fn new(id: Identity, facet: &str, operation: &str) -> OperationNotExistException {
+        OperationNotExistException {
+            id,
+            facet: String::from(facet),
+            operation: String::from(operation)
+        }
+    }
+}
+
+/// An `ObjectAdapterDeactivatedException` is raised when a request
+/// reaches an adapter that has been deactivated.
+#[derive(Debug)]
+pub struct ObjectAdapterDeactivatedException {
+    pub name: String
+}
+
+impl ObjectAdapterDeactivatedException {
+    pub fn new(name: &str) -> ObjectAdapterDeactivatedException {
+        ObjectAdapterDeactivatedException {
+            name: String::from(name)
+        }
+    }
+}
+
+/// A user exception defined in ice files, identified on the wire by its
+/// Slice type id.
+pub trait UserException: std::fmt::Debug + Display + FromBytes + Send + Sync + 'static {
+    const TYPE_ID: &'static str;
+}
+
+/// The user exceptions an operation declares in its `throws` clause,
+/// either a single exception or a tuple of them. `decode` returns the
+/// exception of a reply with status 1 as a `UserError`.
+pub trait DeclaredExceptions {
+    fn decode(bytes: &[u8]) -> Result<Box<dyn std::error::Error + Sync + Send>, Box<dyn std::error::Error + Sync + Send>>;
+}
+
+impl<T: std::fmt::Debug + Display + FromBytes + Send + Sync + 'static> DeclaredExceptions for T {
+    fn decode(bytes: &[u8]) -> Result<Box<dyn std::error::Error + Sync + Send>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut read = 0;
+        Ok(Box::new(UserError {
+            exception: T::from_bytes(bytes, &mut read)?
+        }))
+    }
+}
+
+/// Returns the type id of the first slice of an encoded exception.
+fn exception_type_id(bytes: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    let mut read = 0;
+    SliceFlags::from_bytes(bytes, &mut read)?;
+    String::from_bytes(&bytes[read as usize..], &mut read)
+}
+
+macro_rules! declared_exceptions {
+    ($($name:ident),+) => {
+        impl<$($name: UserException),+> DeclaredExceptions for ($($name,)+) {
+            fn decode(bytes: &[u8]) -> Result<Box<dyn std::error::Error + Sync + Send>, Box<dyn std::error::Error + Sync + Send>> {
+                let type_id = exception_type_id(bytes)?;
+                $(
+                    if type_id == $name::TYPE_ID {
+                        return <$name as DeclaredExceptions>::decode(bytes);
+                    }
+                )+
+                Ok(Box::new(RemoteException {
+                    cause: format!("Undeclared user exception {}", type_id)
+                }))
+            }
+        }
+    };
+}
+
+declared_exceptions!(A, B);
+declared_exceptions!(A, B, C);
+declared_exceptions!(A, B, C, D);
+declared_exceptions!(A, B, C, D, E);
+declared_exceptions!(A, B, C, D, E, F);
+declared_exceptions!(A, B, C, D, E, F, G);
+declared_exceptions!(A, B, C, D, E, F, G, H);
+
 /// A `UserError` is an error that is defined in ice files.
 /// The generic type will be the defined error struct.
 #[derive(Debug)]
@@ -69,7 +295,29 @@ impl std::fmt::Display for ProtocolError {
 
 impl std::fmt::Display for ParsingError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ParsingError!")
+        write!(f, "ParsingError: {}", self.detail)
+    }
+}
+
+impl std::fmt::Display for SliceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line_number = self.line.to_string();
+        let margin = " ".repeat(line_number.len());
+        let indent = self.snippet.chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", margin, self.file, self.line, self.column)?;
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}", line_number, self.snippet)?;
+        write!(f, "{} | {}^", margin, indent)
+    }
+}
+
+impl std::fmt::Debug for SliceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
     }
 }
 
@@ -79,12 +327,42 @@ impl std::fmt::Display for PropertyError {
     }
 }
 
+impl std::fmt::Display for SecurityException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecurityException: {}", self.reason)
+    }
+}
+
 impl std::fmt::Display for RemoteException {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "RemoteException: {}", self.cause)
     }
 }
 
+impl std::fmt::Display for ObjectNotExistException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ObjectNotExistException: {}", self.id)
+    }
+}
+
+impl std::fmt::Display for FacetNotExistException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FacetNotExistException: {} -f {}", self.id, self.facet)
+    }
+}
+
+impl std::fmt::Display for OperationNotExistException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OperationNotExistException: {} ({})", self.operation, self.id)
+    }
+}
+
+impl std::fmt::Display for ObjectAdapterDeactivatedException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ObjectAdapterDeactivatedException: {}", self.name)
+    }
+}
+
 impl<T: Display> std::fmt::Display for UserError<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.exception)
@@ -94,7 +372,13 @@ impl<T: Display> std::fmt::Display for UserError<T> {
 
 impl std::error::Error for ProtocolError {}
 impl std::error::Error for ParsingError {}
+impl std::error::Error for SliceError {}
 impl std::error::Error for RemoteException {}
+impl std::error::Error for SecurityException {}
+impl std::error::Error for ObjectNotExistException {}
+impl std::error::Error for FacetNotExistException {}
+impl std::error::Error for OperationNotExistException {}
+impl std::error::Error for ObjectAdapterDeactivatedException {}
 impl std::error::Error for PropertyError {}
 impl<T: std::fmt::Debug + Display + FromBytes> std::error::Error for UserError<T> {}
 
@@ -1,5 +1,16 @@
 use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
 use crate::protocol::*;
+use crate::current::Current;
+
+/// The future returned by servant operations with `["amd"]` metadata.
+/// It completes the request after the servant has been released. An
+/// error fails the request, which the client raises as `RemoteException`.
+pub type AmdFuture<T> = Pin<Box<dyn Future<Output = Result<T, Box<dyn std::error::Error + Sync + Send>>> + Send>>;
+
+/// A reply that is completed after the servant has been released.
+pub type DeferredReply = AmdFuture<ReplyData>;
 
 /// The `IceObject` trait is a base trait for all
 /// ice interfaces. It implements functions that
@@ -14,5 +25,12 @@ pub trait IceObject {
 
 #[async_trait]
 pub trait IceObjectServer {
-    async fn handle_request(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>;
+    async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>;
+
+    /// Starts a request that is dispatched asynchronously and returns the
+    /// reply to await once the servant is released. Returns `None` for
+    /// requests that `handle_request` dispatches.
+    async fn handle_request_deferred(&mut self, _request: &RequestData, _current: &Current) -> Result<Option<DeferredReply>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(None)
+    }
 }
\ No newline at end of file
@@ -1,15 +1,34 @@
+use std::sync::Arc;
+
+use crate::interceptor::ClientInterceptor;
 use crate::properties::Properties;
+use crate::ssl::CertificateVerifier;
 
 
 #[derive(Clone)]
 pub struct InitializationData {
     properties: Properties,
+    certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+    client_interceptors: Vec<Arc<dyn ClientInterceptor>>,
+    communicator_id: u64
 }
 
 impl InitializationData {
     pub fn new() -> InitializationData {
         InitializationData {
-            properties: Properties::new()
+            properties: Properties::new(),
+            certificate_verifier: None,
+            client_interceptors: Vec::new(),
+            communicator_id: 0
+        }
+    }
+
+    pub fn with_properties(properties: &Properties) -> InitializationData {
+        InitializationData {
+            properties: properties.clone(),
+            certificate_verifier: None,
+            client_interceptors: Vec::new(),
+            communicator_id: 0
         }
     }
 
@@ -20,4 +39,31 @@ impl InitializationData {
     pub fn properties_as_mut(&mut self) -> &mut Properties {
         &mut self.properties
     }
+
+    pub fn certificate_verifier(&self) -> Option<&Arc<dyn CertificateVerifier>> {
+        self.certificate_verifier.as_ref()
+    }
+
+    pub fn set_certificate_verifier(&mut self, verifier: Arc<dyn CertificateVerifier>) {
+        self.certificate_verifier = Some(verifier);
+    }
+
+    pub fn client_interceptors(&self) -> &[Arc<dyn ClientInterceptor>] {
+        &self.client_interceptors
+    }
+
+    pub fn add_client_interceptor(&mut self, interceptor: Arc<dyn ClientInterceptor>) {
+        self.client_interceptors.push(interceptor);
+    }
+
+    /// Identifies the communicator adapters and proxies were created by.
+    /// Collocation is limited to adapters and proxies of the same
+    /// communicator, 0 is used outside of a communicator.
+    pub fn communicator_id(&self) -> u64 {
+        self.communicator_id
+    }
+
+    pub (crate) fn set_communicator_id(&mut self, communicator_id: u64) {
+        self.communicator_id = communicator_id;
+    }
 }
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::adapter::Servant;
+use crate::current::Current;
+use crate::protocol::{ReplyData, RequestData};
+
+/// A `ClientInterceptor` runs around every request a proxy sends.
+/// Interceptors are called in the order they were added before the request
+/// is sent, and in reverse order once the reply or error is known.
+#[async_trait]
+pub trait ClientInterceptor: Send + Sync {
+    /// Called before the request is sent. The request, including its
+    /// context, may be modified. Returning a reply short-circuits the
+    /// invocation, returning an error aborts it.
+    async fn before_request(&self, _request: &mut RequestData) -> Result<Option<ReplyData>, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(None)
+    }
+
+    /// Called with the outcome of the request and the time it took.
+    async fn after_reply(&self, _request: &RequestData, _result: Result<&ReplyData, &(dyn std::error::Error + Sync + Send)>, _elapsed: Duration) {}
+}
+
+/// A `DispatchInterceptor` runs around every request an adapter dispatches
+/// to a servant. It calls `next` to continue with the following interceptor
+/// or the servant, and may modify the request or reply. Returning an error
+/// without calling `next` rejects the request.
+#[async_trait]
+pub trait DispatchInterceptor: Send + Sync {
+    async fn dispatch(&self, request: &RequestData, current: &Current, next: Next<'_>) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>;
+}
+
+/// The remainder of the dispatch chain passed to a `DispatchInterceptor`.
+pub struct Next<'a> {
+    interceptors: &'a [Arc<dyn DispatchInterceptor>],
+    servant: &'a Servant
+}
+
+impl<'a> Next<'a> {
+    pub (crate) fn new(interceptors: &'a [Arc<dyn DispatchInterceptor>], servant: &'a Servant) -> Next<'a> {
+        Next {
+            interceptors,
+            servant
+        }
+    }
+
+    pub async fn run(self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        match self.interceptors.split_first() {
+            Some((interceptor, interceptors)) => {
+                interceptor.dispatch(request, current, Next::new(interceptors, self.servant)).await
+            }
+            None => {
+                let deferred = {
+                    let mut servant = self.servant.lock().await;
+                    match servant.handle_request_deferred(request, current).await? {
+                        Some(deferred) => deferred,
+                        None => return servant.handle_request(request, current).await
+                    }
+                };
+                deferred.await
+            }
+        }
+    }
+}
@@ -3,16 +3,8 @@
 //! This quick start guide will cover a client for the [ZeroC Ice Minimal Sample](https://github.com/zeroc-ice/ice-demos/tree/3.7/python/Ice/minimal). Create a binary application with `cargo new minimal-client` and add `ice-rs` to your `[build-dependencies]`and `[dependencies]`. Now add a `build.rs` file with the following content:
 //!
 //! ```Rust
-//! use ice_rs::slice::parser;
-//! use std::path::Path;
-//!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!   println!("cargo:rerun-if-changed=build.rs");
-//!   let ice_files = vec![
-//!       String::from("<path/to/Hello.ice>")
-//!   ];
-//!   let root_module = parser::parse_ice_files(&input, ".")?;
-//!   root_module.generate(Path::new("./src/gen"))
+//!   ice_rs::slice::build::compile(&["<path/to/Hello.ice>"], &["."])
 //! }
 //! ```
 //!
@@ -20,7 +12,9 @@
 //! ```Rust
 //! use ice_rs::communicator::Communicator;
 //! 
-//! mod gen;
+//! mod gen {
+//!     ice_rs::include_slice!();
+//! }
 //! use crate::gen::demo::{Hello,HelloPrx};
 //! 
 //! #[tokio::main]
@@ -39,12 +33,23 @@ extern crate pest_derive;
 #[macro_use]
 extern crate ice_derive;
 
+// the generated bindings refer to this crate as ice_rs
+extern crate self as ice_rs;
+
 pub mod errors;
 pub mod protocol;
 pub mod encoding;
+pub mod mem;
 pub mod tcp;
+pub mod udp;
+pub mod ws;
 pub mod ssl;
+#[cfg(feature = "openssl-tls")]
+pub mod ssl_openssl;
+#[cfg(feature = "rustls-tls")]
+pub mod ssl_rustls;
 pub mod ssltools;
+pub mod trust_manager;
 pub mod transport;
 pub mod proxy;
 pub mod proxy_parser;
@@ -55,4 +60,10 @@ pub mod slice;
 pub mod initdata;
 pub mod properties;
 pub mod locator;
-pub mod adapter;
\ No newline at end of file
+pub mod adapter;
+pub mod servant_locator;
+pub mod collocation;
+pub mod interceptor;
+pub mod current;
+#[allow(unused_mut, dead_code, clippy::all)]
+pub mod bindings;
\ No newline at end of file
@@ -1,86 +1,53 @@
-use std::collections::HashMap;
-
-
-use crate::{errors::ProtocolError, protocol::{Encapsulation, EndPointType, Identity, LocatorResult, RequestData}, proxy_parser::{DirectProxyData, IndirectProxyData}};
-use crate::encoding::{ToBytes,FromBytes};
+use crate::{bindings::ice::{self, Locator as _, LocatorPrx}, errors::ProtocolError, protocol::{Identity, LocatorResult}, proxy_parser::{DirectProxyData, IndirectProxyData}};
 use crate::proxy::Proxy;
 
 pub struct Locator {
-    proxy: Proxy,
-    request_id: i32
+    locator: LocatorPrx
 }
 
 impl Locator {
     pub fn from(proxy: Proxy) -> Locator {
         Locator {
-            proxy: proxy,
-            request_id: 0
+            locator: LocatorPrx { proxy }
         }
     }
 
     pub async fn locate(&mut self, proxy_data: IndirectProxyData) -> Result<DirectProxyData, Box<dyn std::error::Error + Sync + Send>> {
-        match proxy_data.adapter {
-            Some(adapter) => {
-                let result = self.find_adapter_by_id(&adapter).await?;
-                Ok(DirectProxyData {
-                    ident: result.proxy_data.id,
-                    endpoint: result.endpoint
-                })
-            }
+        let (ident, result) = match proxy_data.adapter {
+            Some(adapter) => (proxy_data.ident, self.find_adapter_by_id(&adapter).await?),
             None => {
                 let obj_result = self.find_object_by_id(&proxy_data.ident).await?;
-                match obj_result.endpoint {
-                    EndPointType::WellKnownObject(object) => {
-                        let adapter_result = self.find_adapter_by_id(&object).await?;
-                        Ok(DirectProxyData {
-                            ident: obj_result.proxy_data.id,
-                            endpoint: adapter_result.endpoint
-                        })
-                    }
-                    _ => Ok(DirectProxyData {
-                        ident: obj_result.proxy_data.id,
-                        endpoint: obj_result.endpoint
-                    })
+                let ident = obj_result.proxy_data.id.to_string();
+                if obj_result.endpoints.is_empty() {
+                    (ident, self.find_adapter_by_id(&obj_result.adapter_id).await?)
+                } else {
+                    (ident, obj_result)
                 }
-
             }
+        };
+        match result.endpoints.into_iter().next() {
+            Some(endpoint) => Ok(DirectProxyData {
+                ident,
+                endpoint
+            }),
+            None => Err(Box::new(ProtocolError::new(&format!("Adapter of {} has no endpoints", ident))))
         }
     }
 
     pub async fn find_object_by_id(&mut self, req: &str) -> Result<LocatorResult, Box<dyn std::error::Error + Sync + Send>> {
-        self.request_id = self.request_id + 1;
-        let mut bytes = req.to_bytes()?;
-        bytes.push(0);
-        let req_data = RequestData {
-            request_id: self.request_id,
-            id: Identity::new(&self.proxy.ident),
-            facet: vec![],
-            operation: String::from("findObjectById"),
-            mode: 1,
-            context: HashMap::new(),
-            params: Encapsulation::from(bytes)
-        };
-        let reply = self.proxy.make_request::<ProtocolError>(&req_data).await?;
-
-        let mut read = 0;
-        LocatorResult::from_bytes(&reply.body.data[read as usize..reply.body.data.len()], &mut read)
+        let id = Identity::new(req);
+        let result = self.locator.find_object_by_id(&ice::Identity { name: id.name, category: id.category }, None).await?;
+        if result.is_nil() {
+            return Err(Box::new(ice::ObjectNotFoundException {}));
+        }
+        Ok(result.data)
     }
 
     pub async fn find_adapter_by_id(&mut self, req: &str) -> Result<LocatorResult, Box<dyn std::error::Error + Sync + Send>> {
-        self.request_id = self.request_id + 1;
-        let bytes = req.to_bytes()?;
-        let req_data = RequestData {
-            request_id: self.request_id,
-            id: Identity::new(&self.proxy.ident),
-            facet: vec![],
-            operation: String::from("findAdapterById"),
-            mode: 1,
-            context: HashMap::new(),
-            params: Encapsulation::from(bytes)
-        };
-        let reply = self.proxy.make_request::<ProtocolError>(&req_data).await?;
-
-        let mut read = 0;
-        LocatorResult::from_bytes(&reply.body.data[read as usize..reply.body.data.len()], &mut read)
+        let result = self.locator.find_adapter_by_id(&String::from(req), None).await?;
+        if result.is_nil() {
+            return Err(Box::new(ice::AdapterNotFoundException {}));
+        }
+        Ok(result.data)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,129 @@
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+
+use crate::errors::*;
+use crate::transport::{ConnectionInfo, Transport};
+
+const MEM_BUFFER_SIZE: usize = 65536;
+
+lazy_static! {
+    static ref MEM_ADAPTERS: Mutex<HashMap<String, UnboundedSender<DuplexStream>>> = Mutex::new(HashMap::new());
+}
+
+/// The `MemTransport` connects proxies and adapters of the same process
+/// through an in-memory duplex stream. `mem -n <name>` endpoints do not
+/// bind any socket, but still run the full protocol.
+pub struct MemTransport {
+    stream: DuplexStream,
+    name: String,
+    incoming: bool
+}
+
+/// Accepts the connections to a `mem` endpoint. The name is released when
+/// the listener is dropped.
+pub struct MemListener {
+    name: String,
+    receiver: UnboundedReceiver<DuplexStream>
+}
+
+impl MemTransport {
+    /// Connects to the adapter listening on `name`. Fails with
+    /// `ConnectionRefused` if there is none.
+    pub async fn new(name: &str) -> Result<MemTransport, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let (client, server) = tokio::io::duplex(MEM_BUFFER_SIZE);
+        let mut adapters = MEM_ADAPTERS.lock().unwrap();
+        let sent = match adapters.get(name) {
+            Some(sender) => sender.send(server).is_ok(),
+            None => false
+        };
+        if !sent {
+            adapters.remove(name);
+            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::ConnectionRefused, format!("No adapter listening on mem endpoint {}", name))));
+        }
+        Ok(MemTransport {
+            stream: client,
+            name: String::from(name),
+            incoming: false
+        })
+    }
+
+    pub fn bind(name: &str) -> Result<MemListener, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let mut adapters = MEM_ADAPTERS.lock().unwrap();
+        if adapters.get(name).is_some_and(|sender| !sender.is_closed()) {
+            return Err(Box::new(ProtocolError::new(&format!("mem endpoint {} is already in use", name))));
+        }
+        let (sender, receiver) = unbounded_channel();
+        adapters.insert(String::from(name), sender);
+        Ok(MemListener {
+            name: String::from(name),
+            receiver
+        })
+    }
+}
+
+impl MemListener {
+    pub async fn accept(&mut self) -> Result<MemTransport, Box<dyn std::error::Error + Sync + Send>>
+    {
+        match self.receiver.recv().await {
+            Some(stream) => Ok(MemTransport {
+                stream,
+                name: self.name.clone(),
+                incoming: true
+            }),
+            None => Err(Box::new(ProtocolError::new(&format!("mem endpoint {} closed", self.name))))
+        }
+    }
+}
+
+impl Drop for MemListener {
+    fn drop(&mut self) {
+        if let Ok(mut adapters) = MEM_ADAPTERS.lock() {
+            adapters.remove(&self.name);
+        }
+    }
+}
+
+impl AsyncWrite for MemTransport {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<Result<usize, std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+impl AsyncRead for MemTransport {
+    fn poll_read(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>, buf: &mut tokio::io::ReadBuf<'_>) -> std::task::Poll<std::io::Result<()>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl Transport for MemTransport {
+    fn transport_type(&self) -> String {
+        String::from("mem")
+    }
+
+    fn connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            transport: String::from("mem"),
+            incoming: self.incoming,
+            local_address: self.name.clone(),
+            remote_address: self.name.clone(),
+            ..Default::default()
+        }
+    }
+}
@@ -29,6 +29,15 @@ impl Properties {
         return self.properties.contains_key(key)
     }
 
+    /// Returns the properties whose key starts with `prefix`.
+    pub fn get_for_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a String, &'a String)> + 'a {
+        self.properties.range(String::from(prefix)..).take_while(move |(key, _)| key.starts_with(prefix))
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.properties.insert(String::from(key), String::from(value));
+    }
+
     pub fn load(&mut self, config_file: &str) -> Result<(), Box<dyn std::error::Error>> {
         let mut content = String::new();
         let mut file = File::open(Path::new(&config_file))?;
@@ -22,7 +22,7 @@ pub struct Header {
     pub message_size: i32
 }
 
-#[derive(Debug, IceDerive)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, IceDerive)]
 pub struct Identity {
     pub name: String,
     pub category: String
@@ -46,6 +46,16 @@ impl Identity {
     }
 }
 
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.category.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}/{}", self.category, self.name)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Encapsulation {
     pub size: i32,
@@ -54,7 +64,7 @@ pub struct Encapsulation {
     pub data: Vec<u8>
 }
 
-#[derive(Debug, IceDerive)]
+#[derive(Debug, Clone, IceDerive)]
 pub struct RequestData {
     pub request_id: i32,
     pub id: Identity,
@@ -65,23 +75,25 @@ pub struct RequestData {
     pub params: Encapsulation
 }
 
-#[derive(Debug, IceEncode)]
+/// The body of a reply with status 0 or 1 is an encapsulation. Replies
+/// with any other status carry their data unencapsulated in `body.data`.
+#[derive(Debug, Clone)]
 pub struct ReplyData {
     pub request_id: i32,
     pub status: u8,
     pub body: Encapsulation
 }
 
-#[derive(Debug, IceDerive)]
+#[derive(Debug, Clone, PartialEq, Default, IceDerive)]
 pub struct Version
 {
     pub major: u8,
     pub minor: u8
 }
 
-#[derive(Debug, IceDerive)]
+#[derive(Debug, Clone, PartialEq, Default, IceDerive)]
 pub struct ProxyData {
-    pub id: String,
+    pub id: Identity,
     pub facet: Vec<String>,
     pub mode: u8,
     pub secure: bool,
@@ -89,21 +101,77 @@ pub struct ProxyData {
     pub encoding: Version
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum EndPointType {
     WellKnownObject(String),
     TCP(EndpointData),
     SSL(EndpointData),
+    WS(WsEndpointData),
+    WSS(WsEndpointData),
+    UDP(UdpEndpointData),
+    MEM(MemEndpointData),
 }
 
-#[derive(Debug)]
+impl EndPointType {
+    pub fn protocol(&self) -> &str {
+        match self {
+            EndPointType::WellKnownObject(_) => "",
+            EndPointType::TCP(_) => "tcp",
+            EndPointType::SSL(_) => "ssl",
+            EndPointType::WS(_) => "ws",
+            EndPointType::WSS(_) => "wss",
+            EndPointType::UDP(_) => "udp",
+            EndPointType::MEM(_) => "mem"
+        }
+    }
+
+    pub fn host(&self) -> &str {
+        match self {
+            EndPointType::WellKnownObject(_) | EndPointType::MEM(_) => "",
+            EndPointType::TCP(data) | EndPointType::SSL(data) => &data.host,
+            EndPointType::WS(data) | EndPointType::WSS(data) => &data.host,
+            EndPointType::UDP(data) => &data.host
+        }
+    }
+
+    pub fn port(&self) -> i32 {
+        match self {
+            EndPointType::WellKnownObject(_) | EndPointType::MEM(_) => 0,
+            EndPointType::TCP(data) | EndPointType::SSL(data) => data.port,
+            EndPointType::WS(data) | EndPointType::WSS(data) => data.port,
+            EndPointType::UDP(data) => data.port
+        }
+    }
+
+    /// Sets the port of endpoints that have one.
+    pub fn set_port(&mut self, port: i32) {
+        match self {
+            EndPointType::WellKnownObject(_) | EndPointType::MEM(_) => {},
+            EndPointType::TCP(data) | EndPointType::SSL(data) => data.port = port,
+            EndPointType::WS(data) | EndPointType::WSS(data) => data.port = port,
+            EndPointType::UDP(data) => data.port = port
+        }
+    }
+}
+
+/// A marshaled proxy as returned by a locator. `endpoints` holds all
+/// endpoints of a supported transport in their encoded order. A proxy
+/// without endpoints refers to the adapter `adapter_id`, or to a well-known
+/// object if that is empty. The default is the nil proxy.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct LocatorResult {
     pub proxy_data: ProxyData,
-    pub size: IceSize,
-    pub endpoint: EndPointType
+    pub endpoints: Vec<EndPointType>,
+    pub adapter_id: String
+}
+
+impl LocatorResult {
+    pub fn is_nil(&self) -> bool {
+        self.proxy_data.id.name.is_empty()
+    }
 }
 
-#[derive(Debug, IceDerive)]
+#[derive(Debug, Clone, PartialEq, IceDerive)]
 pub struct EndpointData
 {
     pub host: String,
@@ -112,6 +180,35 @@ pub struct EndpointData
     pub compress: bool
 }
 
+#[derive(Debug, Clone, PartialEq, IceDerive)]
+pub struct WsEndpointData
+{
+    pub host: String,
+    pub port: i32,
+    pub timeout: i32,
+    pub compress: bool,
+    pub resource: String
+}
+
+/// The multicast interface and TTL of a UDP endpoint are local
+/// options and not part of its encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UdpEndpointData
+{
+    pub host: String,
+    pub port: i32,
+    pub compress: bool,
+    pub interface: String,
+    pub ttl: i32
+}
+
+/// A `mem` endpoint only exists within the process, so it is never encoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemEndpointData
+{
+    pub name: String
+}
+
 impl Header {
     pub fn new(message_type: u8, message_size: i32) -> Header {
         Header {
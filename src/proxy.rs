@@ -1,37 +1,60 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, hash::Hash, marker::PhantomData, ops::Deref};
 use std::sync::Arc;
 
 use task::JoinHandle;
 use tokio::{io::{AsyncReadExt, AsyncWriteExt, ReadHalf, WriteHalf}, sync::Mutex, task};
 
-use crate::{errors::{ProtocolError, UserError}, protocol::{Header, MessageType}, proxy_factory::ProxyFactory, proxy_parser::{ProxyStringType, parse_proxy_string}, transport::Transport};
-use crate::protocol::{ReplyData, RequestData, Identity, Encapsulation};
+use crate::{adapter::Dispatcher, interceptor::ClientInterceptor, errors::{DeclaredExceptions, ProtocolError}, protocol::{EndPointType, Header, MessageType}, proxy_factory::ProxyFactory, proxy_parser::DirectProxyData, transport::{ConnectionInfo, Transport}};
+use crate::protocol::{ReplyData, RequestData, Identity, Encapsulation, LocatorResult};
+use crate::communicator::Communicator;
 use crate::encoding::{ToBytes, FromBytes};
 
 #[derive(Parser)]
 #[grammar = "proxystring.pest"]
 pub struct ProxyParser;
 
+/// Datagram invocations are sent without waiting for a reply. Batch
+/// datagram invocations are queued until `ice_flush_batch_requests`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InvocationMode {
+    Twoway,
+    Datagram,
+    BatchDatagram
+}
+
 pub struct Proxy {
-    pub write: WriteHalf<Box<dyn Transport + Send + Sync + Unpin>>,
+    pub write: Option<WriteHalf<Box<dyn Transport + Send + Sync + Unpin>>>,
     pub request_id: i32,
     pub ident: String,
     pub host: String,
     pub port: i32,
+    pub endpoint: EndPointType,
     pub context: Option<HashMap<String, String>>,
     pub handle: Option<JoinHandle<Result<(), Box<dyn std::error::Error + Sync + Send>>>>,
     pub message_queue: Arc<Mutex<Vec<MessageType>>>,
-    pub stream_type: String
+    pub stream_type: String,
+    pub invocation_mode: InvocationMode,
+    pub batch_requests: Vec<RequestData>,
+    /// Set if the target adapter lives in this process. Requests are then
+    /// dispatched directly instead of being sent over a transport.
+    pub collocated: Option<Dispatcher>,
+    pub collocation_optimized: bool,
+    /// The communicator the proxy belongs to, see `InitializationData::communicator_id`.
+    pub communicator_id: u64,
+    pub interceptors: Vec<Arc<dyn ClientInterceptor>>
 }
 
 
 impl Drop for Proxy {
     fn drop(&mut self) {
-        tokio::task::block_in_place(|| {
-            futures::executor::block_on(async {
-                self.close_connection().await
-            })
-        }).expect("Could not close connection");
+        // datagram endpoints and collocated proxies have no connection to close
+        if self.stream_type != "udp" && self.write.is_some() {
+            tokio::task::block_in_place(|| {
+                futures::executor::block_on(async {
+                    self.close_connection().await
+                })
+            }).expect("Could not close connection");
+        }
         match &self.handle {
             Some(handle) => handle.abort(),
             None => {}
@@ -63,23 +86,31 @@ impl Proxy {
                 lock.push(message);
             }
 
-            std::thread::sleep(std::time::Duration::from_millis(1));
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
         }
     }
 
-    pub fn new(stream: Box<dyn Transport + Send + Sync + Unpin>, ident: &str, host: &str, port: i32, context: Option<HashMap<String, String>>) -> Proxy {
+    pub fn new(stream: Box<dyn Transport + Send + Sync + Unpin>, ident: &str, endpoint: EndPointType, context: Option<HashMap<String, String>>) -> Proxy {
         let stream_type = stream.transport_type();
+        let invocation_mode = if stream_type == "udp" { InvocationMode::Datagram } else { InvocationMode::Twoway };
         let (rx, tx) = tokio::io::split(stream);
         let mut proxy = Proxy {
-            write: tx,
+            write: Some(tx),
             request_id: 0,
             ident: String::from(ident),
-            host: String::from(host),
-            port,
+            host: String::from(endpoint.host()),
+            port: endpoint.port(),
+            endpoint,
             context: context,
             handle: None,
             message_queue: Arc::new(Mutex::new(Vec::new())),
-            stream_type
+            stream_type,
+            invocation_mode,
+            batch_requests: Vec::new(),
+            collocated: None,
+            collocation_optimized: true,
+            communicator_id: 0,
+            interceptors: Vec::new()
         };
         let message_queue = proxy.message_queue.clone();
         proxy.handle = Some(task::spawn(async move {
@@ -89,11 +120,42 @@ impl Proxy {
         proxy
     }
 
+    /// Creates a proxy that dispatches its requests to a local adapter
+    /// without a transport.
+    pub fn collocated(dispatcher: Dispatcher, ident: &str, endpoint: EndPointType, context: Option<HashMap<String, String>>) -> Proxy {
+        Proxy {
+            write: None,
+            request_id: 0,
+            ident: String::from(ident),
+            host: String::from(endpoint.host()),
+            port: endpoint.port(),
+            stream_type: String::from(endpoint.protocol()),
+            invocation_mode: if endpoint.protocol() == "udp" { InvocationMode::Datagram } else { InvocationMode::Twoway },
+            endpoint,
+            context,
+            handle: None,
+            message_queue: Arc::new(Mutex::new(Vec::new())),
+            batch_requests: Vec::new(),
+            collocated: Some(dispatcher),
+            collocation_optimized: true,
+            communicator_id: 0,
+            interceptors: Vec::new()
+        }
+    }
+
+    async fn write_message(&mut self, bytes: &[u8]) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>
+    {
+        match self.write.as_mut() {
+            Some(write) => Ok(write.write(bytes).await?),
+            None => Err(Box::new(ProtocolError::new("Collocated proxy has no connection")))
+        }
+    }
+
     async fn close_connection(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
     {
         let header = Header::new(4, 14);
-        let mut bytes = header.to_bytes()?;
-        let written = self.write.write(&mut bytes).await?;
+        let bytes = header.to_bytes()?;
+        let written = self.write_message(&bytes).await?;
         if written != header.message_size as usize {
             return Err(Box::new(ProtocolError::new("TCP: Could not validate connection")))
         }
@@ -102,20 +164,94 @@ impl Proxy {
     }
 
     pub async fn ice_context(&mut self, context: HashMap<String, String>) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
-        let init_data = crate::communicator::INITDATA.lock().unwrap();
-        let proxy_string = format!("{}:{} -h {} -p {}", self.ident, self.stream_type, self.host, self.port);
-        match parse_proxy_string(&proxy_string)? {
-            ProxyStringType::DirectProxy(data) => {
-                ProxyFactory::create_proxy(data, init_data.properties(), Some(context)).await
-            }
-            _ => {
-                Err(Box::new(ProtocolError::new("ice_context() - could not create proxy")))
+        let mut proxy = self.clone_with_context(Some(context)).await?;
+        proxy.invocation_mode = self.invocation_mode;
+        proxy.interceptors = self.interceptors.clone();
+        Ok(proxy)
+    }
+
+    /// Returns a proxy that sends its requests as datagrams. Requires a UDP endpoint.
+    pub async fn ice_datagram(&mut self) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
+        self.with_invocation_mode(InvocationMode::Datagram).await
+    }
+
+    /// Returns a proxy that queues its requests until `ice_flush_batch_requests`
+    /// sends them as a single datagram. Requires a UDP endpoint.
+    pub async fn ice_batch_datagram(&mut self) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
+        self.with_invocation_mode(InvocationMode::BatchDatagram).await
+    }
+
+    /// Returns a proxy that dispatches to a local adapter directly if
+    /// `optimized` is true, or always goes through the transport otherwise.
+    pub async fn ice_collocation_optimized(&mut self, optimized: bool) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
+        let mut init_data = crate::communicator::INITDATA.lock().unwrap().clone();
+        init_data.set_communicator_id(self.communicator_id);
+        let data = DirectProxyData {
+            ident: self.ident.clone(),
+            endpoint: self.endpoint.clone()
+        };
+        let mut proxy = ProxyFactory::create_proxy_with_collocation(data, &init_data, self.context.clone(), optimized).await?;
+        proxy.invocation_mode = self.invocation_mode;
+        proxy.interceptors = self.interceptors.clone();
+        Ok(proxy)
+    }
+
+    /// Adds an interceptor that runs around the requests of this proxy,
+    /// after the ones installed on the communicator.
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn ClientInterceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Sends all queued batch requests as one batch request message.
+    pub async fn ice_flush_batch_requests(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.batch_requests.is_empty() {
+            return Ok(());
+        }
+        if self.collocated.is_some() {
+            let requests = std::mem::take(&mut self.batch_requests);
+            for request in &requests {
+                self.dispatch_collocated(request).await?;
             }
+            return Ok(());
+        }
+        let mut body = (self.batch_requests.len() as i32).to_bytes()?;
+        for request in &self.batch_requests {
+            body.extend(request.to_batch_bytes()?);
         }
+        let header = Header::new(1, 14 + body.len() as i32);
+        let mut bytes = header.to_bytes()?;
+        bytes.extend(body);
+
+        let written = self.write_message(&bytes).await?;
+        if written != header.message_size as usize {
+            return Err(Box::new(ProtocolError::new("UDP: Error writing batch request")))
+        }
+        self.batch_requests.clear();
+        Ok(())
+    }
+
+    async fn with_invocation_mode(&mut self, mode: InvocationMode) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
+        if self.stream_type != "udp" {
+            return Err(Box::new(ProtocolError::new("Datagram invocations require a udp endpoint")));
+        }
+        let mut proxy = self.clone_with_context(self.context.clone()).await?;
+        proxy.invocation_mode = mode;
+        proxy.interceptors = self.interceptors.clone();
+        Ok(proxy)
+    }
+
+    async fn clone_with_context(&mut self, context: Option<HashMap<String, String>>) -> Result<Proxy, Box<dyn std::error::Error + Send + Sync>> {
+        let mut init_data = crate::communicator::INITDATA.lock().unwrap().clone();
+        init_data.set_communicator_id(self.communicator_id);
+        let data = DirectProxyData {
+            ident: self.ident.clone(),
+            endpoint: self.endpoint.clone()
+        };
+        ProxyFactory::create_proxy_with_collocation(data, &init_data, context, self.collocation_optimized).await
     }
 
     pub async fn dispatch<
-        T: 'static + std::fmt::Debug + std::fmt::Display + FromBytes + Send + Sync,
+        T: DeclaredExceptions,
     >(
         &mut self,
         op: &str,
@@ -156,13 +292,27 @@ impl Proxy {
         let mut bytes = header.to_bytes()?;
         bytes.extend(req_bytes);
 
-        let written = self.write.write(&mut bytes).await?;
+        let written = self.write_message(&bytes).await?;
         if written != header.message_size as usize {
             return Err(Box::new(ProtocolError::new(&format!("TCP: Error writing request {}", request.request_id))))
         }
         Ok(())
     }
 
+    /// Dispatches a request to the collocated adapter. Request and reply are
+    /// still marshaled, so servants see the same data as over a transport.
+    async fn dispatch_collocated(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        let dispatcher = match self.collocated.as_ref() {
+            Some(dispatcher) => dispatcher.clone(),
+            None => return Err(Box::new(ProtocolError::new("Proxy is not collocated")))
+        };
+        let mut read = 0;
+        let request = RequestData::from_bytes(&request.to_bytes()?, &mut read)?;
+        let reply = dispatcher.dispatch(&request, &ConnectionInfo::default()).await?;
+        let mut read = 0;
+        ReplyData::from_bytes(&reply.to_bytes()?, &mut read)
+    }
+
     pub async fn await_validate_connection_message(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
         let timeout = std::time::Duration::from_secs(30); // TODO: read from ice config
         let now = std::time::Instant::now();
@@ -189,7 +339,7 @@ impl Proxy {
                 return Err(Box::new(ProtocolError::new("Timeout waiting for response")));
             }
 
-            std::thread::sleep(std::time::Duration::from_millis(1));
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
         }
         Ok(())
     }
@@ -226,31 +376,191 @@ impl Proxy {
                 return Err(Box::new(ProtocolError::new("Timeout waiting for response")));
             }
 
-            std::thread::sleep(std::time::Duration::from_millis(1));
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
         }
     }
 
-    async fn read_response<T: 'static + std::fmt::Debug + std::fmt::Display + FromBytes + Send + Sync>(&mut self, request_id: i32) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+    async fn read_response<T: DeclaredExceptions>(&mut self, request_id: i32) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
         let message = self.await_reply_message(request_id).await?;
         match message {
-            MessageType::Reply(_header, reply) => {
-                match reply.status {
-                    1 => {
-                        let mut read = 0;
-                        Err(Box::new(UserError {
-                            exception: T::from_bytes(&reply.body.data, &mut read)?
-                        }))
-                    }
-                    _ => Ok(reply)
-                }
-            },
+            MessageType::Reply(_header, reply) => Proxy::check_reply::<T>(reply),
             _ => Err(Box::new(ProtocolError::new(&format!("Unsupported message type: {:?}", message))))
         }
     }
 
-    pub async fn make_request<T: 'static + std::fmt::Debug + std::fmt::Display + FromBytes + Send + Sync>(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>
+    fn check_reply<T: DeclaredExceptions>(reply: ReplyData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+        if let Some(error) = reply.error()? {
+            return Err(error);
+        }
+        match reply.status {
+            1 => Err(T::decode(&reply.body.data)?),
+            _ => Ok(reply)
+        }
+    }
+
+    pub async fn make_request<T: DeclaredExceptions>(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>
     {
+        if self.interceptors.is_empty() {
+            return self.invoke::<T>(request).await;
+        }
+
+        let interceptors = self.interceptors.clone();
+        let mut request = request.clone();
+        let start = std::time::Instant::now();
+        let mut called = 0;
+        let mut result = None;
+        for interceptor in &interceptors {
+            called += 1;
+            match interceptor.before_request(&mut request).await {
+                Ok(None) => {}
+                Ok(Some(reply)) => {
+                    result = Some(Proxy::check_reply::<T>(reply));
+                    break;
+                }
+                Err(e) => {
+                    result = Some(Err(e));
+                    break;
+                }
+            }
+        }
+        let result = match result {
+            Some(result) => result,
+            None => self.invoke::<T>(&request).await
+        };
+
+        let elapsed = start.elapsed();
+        for interceptor in interceptors[0..called].iter().rev() {
+            interceptor.after_reply(&request, result.as_ref().map_err(|e| e.as_ref()), elapsed).await;
+        }
+        result
+    }
+
+    async fn invoke<T: DeclaredExceptions>(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>>
+    {
+        match self.invocation_mode {
+            InvocationMode::Twoway => {}
+            InvocationMode::Datagram => {
+                // datagrams are never answered, so they carry no request id
+                let request = RequestData {
+                    request_id: 0,
+                    ..request.clone()
+                };
+                if self.collocated.is_some() {
+                    self.dispatch_collocated(&request).await?;
+                } else {
+                    self.send_request(&request).await?;
+                }
+                return Ok(ReplyData {
+                    request_id: 0,
+                    status: 0,
+                    body: Encapsulation::empty()
+                });
+            }
+            InvocationMode::BatchDatagram => {
+                self.batch_requests.push(request.clone());
+                return Ok(ReplyData {
+                    request_id: 0,
+                    status: 0,
+                    body: Encapsulation::empty()
+                });
+            }
+        }
+        if self.collocated.is_some() {
+            let reply = self.dispatch_collocated(request).await?;
+            return Proxy::check_reply::<T>(reply);
+        }
         self.send_request(request).await?;
         self.read_response::<T>(request.request_id).await
     }
-}
\ No newline at end of file
+}
+/// A marshaled proxy to an object implementing the interface of `T`, the
+/// generated proxy type. Slice proxy types like `Hello*` are mapped to
+/// `TypedProxy<HelloPrx>` and `Object*` to `TypedProxy<Proxy>`.
+pub struct TypedProxy<T> {
+    pub data: LocatorResult,
+    proxy_type: PhantomData<fn() -> T>
+}
+
+impl<T> TypedProxy<T> {
+    pub fn new(data: LocatorResult) -> TypedProxy<T> {
+        TypedProxy {
+            data,
+            proxy_type: PhantomData
+        }
+    }
+
+    /// Returns the marshaled proxy as a proxy to another interface.
+    pub fn cast<U>(self) -> TypedProxy<U> {
+        TypedProxy::new(self.data)
+    }
+}
+
+impl<T: From<Proxy>> TypedProxy<T> {
+    /// Connects to the object. The endpoints are tried in order, a proxy
+    /// without endpoints is resolved by the locator of `communicator`.
+    pub async fn connect(&self, communicator: &mut Communicator) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+        if self.data.is_nil() {
+            return Err(Box::new(ProtocolError::new("Cannot connect to a nil proxy")));
+        }
+        let ident = self.data.proxy_data.id.to_string();
+        if self.data.endpoints.is_empty() {
+            let proxy_string = if self.data.adapter_id.is_empty() {
+                ident
+            } else {
+                format!("{} @ {}", ident, self.data.adapter_id)
+            };
+            return Ok(T::from(communicator.string_to_proxy(&proxy_string).await?));
+        }
+
+        let mut error = None;
+        for endpoint in &self.data.endpoints {
+            let proxy_data = DirectProxyData {
+                ident: ident.clone(),
+                endpoint: endpoint.clone()
+            };
+            match communicator.create_proxy(proxy_data).await {
+                Ok(proxy) => return Ok(T::from(proxy)),
+                Err(e) => error = Some(e)
+            }
+        }
+        Err(error.unwrap())
+    }
+}
+
+impl<T> From<LocatorResult> for TypedProxy<T> {
+    fn from(data: LocatorResult) -> Self {
+        TypedProxy::new(data)
+    }
+}
+
+impl<T> Deref for TypedProxy<T> {
+    type Target = LocatorResult;
+
+    fn deref(&self) -> &LocatorResult {
+        &self.data
+    }
+}
+
+impl<T> Default for TypedProxy<T> {
+    fn default() -> Self {
+        TypedProxy::new(LocatorResult::default())
+    }
+}
+
+impl<T> Clone for TypedProxy<T> {
+    fn clone(&self) -> Self {
+        TypedProxy::new(self.data.clone())
+    }
+}
+
+impl<T> PartialEq for TypedProxy<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T> std::fmt::Debug for TypedProxy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedProxy").field(&self.data).finish()
+    }
+}
@@ -1,49 +1,74 @@
 use std::collections::HashMap;
 
-use crate::{errors::ProtocolError, locator::Locator, properties::Properties, protocol::EndPointType, proxy::Proxy, proxy_parser::{DirectProxyData, ProxyStringType, parse_proxy_string}, ssl::SslTransport, tcp::TcpTransport};
+use crate::{collocation, errors::ProtocolError, initdata::InitializationData, locator::Locator, mem::MemTransport, protocol::EndPointType, proxy::Proxy, proxy_parser::{DirectProxyData, ProxyStringType, parse_proxy_string}, ssl::SslTransport, tcp::TcpTransport, transport::Transport, udp::UdpTransport, ws::WsTransport};
 
 pub struct ProxyFactory {
     locator: Option<Locator>
 }
 
 impl ProxyFactory {
-    pub async fn create_proxy(proxy_data: DirectProxyData, properties: &Properties, context: Option<HashMap<String, String>>) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
-        let mut proxy = match proxy_data.endpoint {            
+    pub async fn create_proxy(proxy_data: DirectProxyData, init_data: &InitializationData, context: Option<HashMap<String, String>>) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        let optimized = init_data.properties().get("Ice.Default.CollocationOptimized").unwrap_or(&String::from("1")).parse::<u8>()? > 0;
+        ProxyFactory::create_proxy_with_collocation(proxy_data, init_data, context, optimized).await
+    }
+
+    /// Creates a proxy for `proxy_data`. If `optimized` is set and the endpoint
+    /// belongs to an adapter of this process, the proxy dispatches directly.
+    pub async fn create_proxy_with_collocation(proxy_data: DirectProxyData, init_data: &InitializationData, context: Option<HashMap<String, String>>, optimized: bool) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+        if optimized {
+            if let Some(dispatcher) = collocation::find_by_endpoint(init_data.communicator_id(), &proxy_data.endpoint) {
+                let mut proxy = Proxy::collocated(dispatcher, &proxy_data.ident, proxy_data.endpoint, context);
+                proxy.communicator_id = init_data.communicator_id();
+                proxy.interceptors = init_data.client_interceptors().to_vec();
+                return Ok(proxy);
+            }
+        }
+        let endpoint = proxy_data.endpoint.clone();
+        let stream: Box<dyn Transport + Send + Sync + Unpin> = match proxy_data.endpoint {
             EndPointType::TCP(endpoint) => {
-                Proxy::new(
-                    Box::new(TcpTransport::new(&format!("{}:{}", endpoint.host, endpoint.port)).await?),
-                    &proxy_data.ident,
-                    &endpoint.host,
-                    endpoint.port,
-                    context
-                )
+                Box::new(TcpTransport::new(&format!("{}:{}", endpoint.host, endpoint.port)).await?)
             }
             EndPointType::SSL(endpoint) => {
-                Proxy::new(
-                    Box::new(SslTransport::new(&format!("{}:{}", endpoint.host, endpoint.port), properties).await?),
-                    &proxy_data.ident,
-                    &endpoint.host,
-                    endpoint.port,
-                    context
-                )
+                Box::new(SslTransport::new(&endpoint.host, endpoint.port, init_data).await?)
+            }
+            EndPointType::WS(endpoint) => {
+                let transport = TcpTransport::new(&format!("{}:{}", endpoint.host, endpoint.port)).await?;
+                Box::new(WsTransport::connect(transport, &endpoint.host, endpoint.port, &endpoint.resource, init_data).await?)
+            }
+            EndPointType::WSS(endpoint) => {
+                let transport = SslTransport::new(&endpoint.host, endpoint.port, init_data).await?;
+                Box::new(WsTransport::connect(transport, &endpoint.host, endpoint.port, &endpoint.resource, init_data).await?)
+            }
+            EndPointType::MEM(endpoint) => {
+                Box::new(MemTransport::new(&endpoint.name).await?)
+            }
+            EndPointType::UDP(endpoint) => {
+                Box::new(UdpTransport::new(&endpoint).await?)
             }
             _ => return Err(Box::new(ProtocolError::new(&format!("Error creating proxy"))))
         };
 
-        proxy.await_validate_connection_message().await?;
+        let mut proxy = Proxy::new(stream, &proxy_data.ident, endpoint, context);
+        proxy.collocation_optimized = optimized;
+        proxy.communicator_id = init_data.communicator_id();
+        proxy.interceptors = init_data.client_interceptors().to_vec();
+        // datagram proxies have no connection to validate
+        if proxy.stream_type != "udp" {
+            proxy.await_validate_connection_message().await?;
+        }
 
         Ok(proxy)
     }
 
-    pub async fn new(properties: &Properties) -> Result<ProxyFactory, Box<dyn std::error::Error + Sync + Send>> {
+    pub async fn new(init_data: &InitializationData) -> Result<ProxyFactory, Box<dyn std::error::Error + Sync + Send>> {
         Ok(ProxyFactory {
-            locator: match properties.get("Ice.Default.Locator") {
+            locator: match init_data.properties().get("Ice.Default.Locator") {
                 Some(locator_proxy) => {
                     match parse_proxy_string(locator_proxy) {
                         Ok(proxy_type) => {
                             match proxy_type {
                                 ProxyStringType::DirectProxy(data) => {
-                                    Some(Locator::from(ProxyFactory::create_proxy(data, properties, None).await?))
+                                    Some(Locator::from(ProxyFactory::create_proxy(data, init_data, None).await?))
                                 }
                                 _ => None
                             }
@@ -56,16 +81,25 @@ impl ProxyFactory {
         })
     }
 
-    pub async fn create(&mut self, proxy_string: &str, properties: &Properties) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
+    pub async fn create(&mut self, proxy_string: &str, init_data: &InitializationData) -> Result<Proxy, Box<dyn std::error::Error + Sync + Send>> {
         match parse_proxy_string(proxy_string)? {
             ProxyStringType::DirectProxy(data) => {
-                ProxyFactory::create_proxy(data, properties, None).await
+                ProxyFactory::create_proxy(data, init_data, None).await
             }
             ProxyStringType::IndirectProxy(data) => {
+                let optimized = init_data.properties().get("Ice.Default.CollocationOptimized").unwrap_or(&String::from("1")).parse::<u8>()? > 0;
+                if optimized {
+                    if let Some((dispatcher, endpoint)) = data.adapter.as_ref().and_then(|adapter| collocation::find_by_adapter_id(init_data.communicator_id(), adapter)) {
+                        let mut proxy = Proxy::collocated(dispatcher, &data.ident, endpoint, None);
+                        proxy.communicator_id = init_data.communicator_id();
+                        proxy.interceptors = init_data.client_interceptors().to_vec();
+                        return Ok(proxy);
+                    }
+                }
                 match self.locator.as_mut() {
                     Some(locator) => {
                         let data = locator.locate(data).await?;
-                        ProxyFactory::create_proxy(data, properties, None).await
+                        ProxyFactory::create_proxy(data, init_data, None).await
                     }
                     _ => Err(Box::new(ProtocolError::new(&format!("No locator set up to resolve indirect proxy"))))
                 }
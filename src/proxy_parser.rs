@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use crate::{errors::*, protocol::{EndPointType, EndpointData}};
+use crate::{errors::*, protocol::{EndPointType, EndpointData, MemEndpointData, UdpEndpointData, WsEndpointData}};
 use pest::{Parser, iterators::Pairs};
 
 
@@ -97,13 +97,17 @@ pub fn parse_endpoint(rules: Pairs<Rule>) -> Result<EndPointType, Box<dyn std::e
     let mut protocol = "";
     let mut host = "";
     let mut port = 0i32;
+    let mut resource = "/";
+    let mut interface = "";
+    let mut ttl = -1;
+    let mut name = "";
 
     for child in rules {
         match child.as_rule() {
             Rule::endpoint_protocol => {
                 protocol = child.as_str();
             }
-            Rule::endpoint_host | Rule::endpoint_port => {
+            Rule::endpoint_host | Rule::endpoint_port | Rule::endpoint_resource | Rule::endpoint_interface | Rule::endpoint_ttl | Rule::endpoint_name => {
                 for item in child.into_inner() {
                     match item.as_rule() {
                         Rule::hostname | Rule::ip => {
@@ -112,6 +116,18 @@ pub fn parse_endpoint(rules: Pairs<Rule>) -> Result<EndPointType, Box<dyn std::e
                         Rule::port => {
                             port = item.as_str().parse()?;
                         }
+                        Rule::resource => {
+                            resource = item.as_str();
+                        }
+                        Rule::interface => {
+                            interface = item.as_str();
+                        }
+                        Rule::ttl => {
+                            ttl = item.as_str().parse()?;
+                        }
+                        Rule::name => {
+                            name = item.as_str();
+                        }
                         _ => return Err(Box::new(ParsingError::new(&format!("Unexpected proxy string rule: {:?}", item.as_rule()))))
                     };
                 }
@@ -120,6 +136,39 @@ pub fn parse_endpoint(rules: Pairs<Rule>) -> Result<EndPointType, Box<dyn std::e
         }
     }
 
+    if protocol == "mem" {
+        if name.is_empty() {
+            return Err(Box::new(ParsingError::new("mem endpoint requires a name (-n)")));
+        }
+        return Ok(EndPointType::MEM(MemEndpointData {
+            name: String::from(name)
+        }))
+    }
+
+    if protocol == "udp" {
+        return Ok(EndPointType::UDP(UdpEndpointData {
+            host: String::from(host),
+            port,
+            compress: false,
+            interface: String::from(interface),
+            ttl
+        }))
+    }
+
+    if protocol == "ws" || protocol == "wss" {
+        let endpoint_data = WsEndpointData {
+            host: String::from(host),
+            port,
+            timeout: 60000,
+            compress: false,
+            resource: String::from(resource)
+        };
+        return match protocol {
+            "ws" => Ok(EndPointType::WS(endpoint_data)),
+            _ => Ok(EndPointType::WSS(endpoint_data))
+        }
+    }
+
     let endpoint_data = EndpointData {
         host: String::from(host),
         port,
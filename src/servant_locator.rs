@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::adapter::Servant;
+use crate::current::Current;
+
+/// Any value a `ServantLocator` wants to pass from `locate` to `finished`.
+pub type Cookie = Box<dyn std::any::Any + Send + Sync>;
+
+/// A `ServantLocator` provides servants on demand for all identities of a
+/// category, e.g. to serve objects stored in a database without keeping a
+/// servant for each of them.
+#[async_trait]
+pub trait ServantLocator: Send + Sync {
+    /// Returns the servant for `current.id`, or `None` if the object does not exist.
+    async fn locate(&self, current: &Current) -> Result<Option<(Servant, Cookie)>, Box<dyn std::error::Error + Sync + Send>>;
+
+    /// Called once the located servant has dispatched the request.
+    async fn finished(&self, _current: &Current, _servant: &Servant, _cookie: Cookie) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::errors::ParsingError;
+use crate::slice::parser;
+use std::path::Path;
+
+
+/// The name of the file written by `compile` to `OUT_DIR`.
+pub const OUT_FILE: &str = "slice.rs";
+
+/// Compiles Slice files in a build script. The generated code of all
+/// modules is written to the single file `slice.rs` in `OUT_DIR`, which
+/// is included with `ice_rs::include_slice!()`:
+///
+/// ```Rust
+/// // build.rs
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     ice_rs::slice::build::compile(&["Hello.ice"], &["."])
+/// }
+///
+/// // main.rs
+/// mod gen {
+///     ice_rs::include_slice!();
+/// }
+/// ```
+///
+/// Includes are searched in `include_dirs`. Cargo reruns the build
+/// script whenever one of the Slice files, including the transitively
+/// included ones, changes.
+pub fn compile<F: AsRef<Path>, I: AsRef<Path>>(ice_files: &[F], include_dirs: &[I]) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::env::var("OUT_DIR").map_err(|_| {
+        ParsingError::new("OUT_DIR is not set, compile must be called from a build script")
+    })?;
+    compile_to(ice_files, include_dirs, &Path::new(&out_dir).join(OUT_FILE))
+}
+
+/// Compiles Slice files like `compile`, but writes the generated code
+/// to `dest`.
+pub fn compile_to<F: AsRef<Path>, I: AsRef<Path>>(ice_files: &[F], include_dirs: &[I], dest: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let ice_files = to_strings(ice_files);
+    let include_dirs = to_strings(include_dirs);
+    let (root, dependencies) = parser::parse_ice_files_with_dependencies(&ice_files, &include_dirs)?;
+    for dependency in dependencies {
+        println!("cargo:rerun-if-changed={}", dependency.display());
+    }
+    for warning in &root.warnings {
+        println!("cargo:warning={}", warning);
+    }
+    root.generate_file(dest)
+}
+
+fn to_strings<P: AsRef<Path>>(paths: &[P]) -> Vec<String> {
+    paths.iter().map(|path| path.as_ref().to_string_lossy().into_owned()).collect()
+}
+
+/// Includes the code generated by `ice_rs::slice::build::compile`.
+#[macro_export]
+macro_rules! include_slice {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/slice.rs"));
+    };
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_compile_to() {
+        let dir = std::env::temp_dir().join(format!("ice-rs-build-{}", std::process::id()));
+        fs::create_dir_all(dir.join("include")).unwrap();
+        fs::write(dir.join("include/Types.ice"), "#pragma once\nmodule Demo { module Sub { struct Item { int id; } } }\n").unwrap();
+        fs::write(dir.join("Hello.ice"), "#include <Types.ice>\nmodule Demo { interface Hello { Sub::Item get(); } }\n").unwrap();
+
+        let dest = dir.join("out").join(OUT_FILE);
+        compile_to(&[dir.join("Hello.ice")], &[dir.join("include")], &dest).unwrap();
+        let code = fs::read_to_string(&dest).unwrap();
+        assert!(code.contains("pub mod demo {"));
+        assert!(code.contains("pub mod sub {"));
+        assert!(code.contains("pub trait Hello"));
+
+        assert!(compile_to(&[dir.join("Hello.ice")], &[dir.to_str().unwrap()], &dest).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
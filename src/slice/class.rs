@@ -10,6 +10,7 @@ pub struct Class {
     pub ice_id: String,
     pub members: Vec<StructMember>,
     pub extends: Option<IceType>,
+    pub derives: Vec<TokenStream>
 }
 
 impl Class {
@@ -18,7 +19,8 @@ impl Class {
             id: TokenStream::new(),
             ice_id: String::new(),
             members: Vec::new(),
-            extends: None
+            extends: None,
+            derives: Vec::new()
         }
     }
 
@@ -43,7 +45,7 @@ impl Class {
                 }
                 _ => {
                     quote! {
-                        let #id_token = #var_token::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
+                        let #id_token = <#var_token>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
                     }
                 }
             }
@@ -78,7 +80,7 @@ impl Class {
                 extends: #token
             });
             member_to_struct.push(quote!{
-                extends: #token::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
+                extends: <#token>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
             });
         }
 
@@ -97,7 +99,7 @@ impl Class {
                     let var_token = option_type.token();
                     Some(quote! {
                         #tag => {
-                            #id_token = Some(#var_token::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?);
+                            #id_token = Some(<#var_token>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?);
                         }
                     })
                 },
@@ -130,12 +132,21 @@ impl Class {
             None
         };
 
+        // derived classes are rejected by the parser if their members have default values
+        let default_impl = match self.extends {
+            Some(_) => None,
+            None => StructMember::default_impl(id_token, &self.members, &self.derives)?
+        };
+
+        let derives = &self.derives;
         Ok(quote! {
-            #[derive(Debug, Clone, PartialEq)]
+            #[derive(Debug, Clone, PartialEq #(, #derives)*)]
             pub struct #id_token {
                 #(#member_tokens),*
             }
 
+            #default_impl
+
             impl ToBytes for #id_token {
                 fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
                     let mut bytes = Vec::new();
@@ -0,0 +1,188 @@
+use crate::errors::ParsingError;
+use crate::slice::types::IceType;
+use std::convert::TryFrom;
+use quote::{__private::TokenStream, format_ident, quote};
+
+
+/// The value of a constant or of a default value of a member.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Str(String),
+    Identifier(String)
+}
+
+impl ConstValue {
+    /// Parses an integer literal. Literals starting with `0x` are
+    /// hexadecimal, literals with a leading `0` are octal.
+    pub fn parse_integer(text: &str) -> Result<ConstValue, Box<dyn std::error::Error>> {
+        let (negative, digits) = match text.chars().next() {
+            Some('-') => (true, &text[1..]),
+            Some('+') => (false, &text[1..]),
+            _ => (false, text)
+        };
+        let value = if digits.starts_with("0x") || digits.starts_with("0X") {
+            i64::from_str_radix(&digits[2..], 16)?
+        } else if digits.len() > 1 && digits.starts_with('0') {
+            i64::from_str_radix(&digits[1..], 8)?
+        } else {
+            digits.parse()?
+        };
+        Ok(ConstValue::Integer(if negative { -value } else { value }))
+    }
+
+    pub fn parse_float(text: &str) -> Result<ConstValue, Box<dyn std::error::Error>> {
+        Ok(ConstValue::Float(text.trim_end_matches(['f', 'F']).parse()?))
+    }
+
+    /// Parses the content of a string literal and resolves its escape
+    /// sequences.
+    pub fn parse_string(text: &str) -> Result<ConstValue, Box<dyn std::error::Error>> {
+        let mut result = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+            let escaped = chars.next().ok_or_else(|| Box::new(ParsingError::new("Unterminated escape sequence")))?;
+            match escaped {
+                'n' => result.push('\n'),
+                't' => result.push('\t'),
+                'r' => result.push('\r'),
+                'a' => result.push('\x07'),
+                'b' => result.push('\x08'),
+                'f' => result.push('\x0c'),
+                'v' => result.push('\x0b'),
+                '\\' | '"' | '\'' | '?' => result.push(escaped),
+                'x' | 'u' | 'U' => {
+                    let max_digits = match escaped {
+                        'x' => 2,
+                        'u' => 4,
+                        _ => 8
+                    };
+                    let mut digits = String::new();
+                    while digits.len() < max_digits && chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                        digits.push(chars.next().unwrap_or_default());
+                    }
+                    let code = u32::from_str_radix(&digits, 16)?;
+                    result.push(std::char::from_u32(code).ok_or_else(|| Box::new(ParsingError::new(
+                        &format!("Invalid escape sequence \\{}{}", escaped, digits)
+                    )))?);
+                }
+                '0'..='7' => {
+                    let mut digits = escaped.to_string();
+                    while digits.len() < 3 && chars.peek().is_some_and(|c| ('0'..='7').contains(c)) {
+                        digits.push(chars.next().unwrap_or_default());
+                    }
+                    let code = u32::from_str_radix(&digits, 8)?;
+                    result.push(std::char::from_u32(code).unwrap_or_default());
+                }
+                _ => return Err(Box::new(ParsingError::new(
+                    &format!("Unknown escape sequence \\{}", escaped)
+                )))
+            }
+        }
+        Ok(ConstValue::Str(result))
+    }
+
+    /// Returns the value as a literal of the given type. Identifiers
+    /// name a variant of the enum type.
+    pub fn token(&self, r#type: &IceType) -> Result<TokenStream, Box<dyn std::error::Error>> {
+        match (self, r#type) {
+            (ConstValue::Bool(value), IceType::BoolType) => Ok(quote! { #value }),
+            (ConstValue::Integer(value), IceType::ByteType) => {
+                let value = u8::try_from(*value)?;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Integer(value), IceType::ShortType) => {
+                let value = i16::try_from(*value)?;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Integer(value), IceType::IntType) => {
+                let value = i32::try_from(*value)?;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Integer(value), IceType::LongType) => Ok(quote! { #value }),
+            (ConstValue::Integer(value), IceType::FloatType) => {
+                let value = *value as f32;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Integer(value), IceType::DoubleType) => {
+                let value = *value as f64;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Float(value), IceType::FloatType) => {
+                let value = *value as f32;
+                Ok(quote! { #value })
+            },
+            (ConstValue::Float(value), IceType::DoubleType) => Ok(quote! { #value }),
+            (ConstValue::Str(value), IceType::StringType) => Ok(quote! { #value }),
+            (ConstValue::Identifier(value), IceType::CustomType(_)) => {
+                let type_token = r#type.token();
+                let variant = format_ident!("{}", value.rsplit("::").next().unwrap_or(value));
+                Ok(quote! { #type_token::#variant })
+            },
+            _ => Err(Box::new(ParsingError::new(
+                &format!("Value {:?} does not match type {}", self, r#type.rust_type())
+            )))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub id: TokenStream,
+    pub ice_id: String,
+    pub r#type: IceType,
+    pub value: ConstValue
+}
+
+impl Constant {
+    pub fn empty() -> Constant {
+        Constant {
+            id: TokenStream::new(),
+            ice_id: String::new(),
+            r#type: IceType::VoidType,
+            value: ConstValue::Bool(false)
+        }
+    }
+
+    pub fn generate(&self) -> Result<TokenStream, Box<dyn std::error::Error>> {
+        let id_token = &self.id;
+        let type_token = match self.r#type {
+            IceType::StringType => quote! { &str },
+            _ => self.r#type.token()
+        };
+        let value_token = self.value.token(&self.r#type)?;
+        Ok(quote! {
+            pub const #id_token: #type_token = #value_token;
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(ConstValue::Integer(100), ConstValue::parse_integer("100").unwrap());
+        assert_eq!(ConstValue::Integer(-31), ConstValue::parse_integer("-0x1F").unwrap());
+        assert_eq!(ConstValue::Integer(15), ConstValue::parse_integer("017").unwrap());
+        assert_eq!(ConstValue::Integer(0), ConstValue::parse_integer("0").unwrap());
+        assert!(ConstValue::parse_integer("09").is_err());
+
+        assert_eq!(ConstValue::Float(1.5), ConstValue::parse_float("1.5f").unwrap());
+        assert_eq!(ConstValue::Float(1000.0), ConstValue::parse_float("1e3").unwrap());
+
+        assert_eq!(ConstValue::Str(String::from("a\"b\n\tc\\")), ConstValue::parse_string("a\\\"b\\n\\tc\\\\").unwrap());
+        assert_eq!(ConstValue::Str(String::from("AB\u{e9}")), ConstValue::parse_string("\\x41\\102\\u00e9").unwrap());
+        assert!(ConstValue::parse_string("\\q").is_err());
+
+        assert!(ConstValue::Integer(300).token(&IceType::ByteType).is_err());
+        assert!(ConstValue::Str(String::from("x")).token(&IceType::IntType).is_err());
+    }
+}
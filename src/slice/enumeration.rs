@@ -5,7 +5,9 @@ pub struct Enum {
     pub id: TokenStream,
     pub ice_id: String,
     variants: Vec<TokenStream>,
-    next_value: i32
+    first: Option<TokenStream>,
+    next_value: i32,
+    pub derives: Vec<TokenStream>
 }
 
 impl Enum {
@@ -14,7 +16,9 @@ impl Enum {
             id: TokenStream::new(),
             ice_id: String::new(),
             variants: vec![],
-            next_value: 0
+            first: None,
+            next_value: 0,
+            derives: Vec::new()
         }
     }
 
@@ -31,6 +35,9 @@ impl Enum {
             }
         };
         let id = format_ident!("{}", name);
+        if self.first.is_none() {
+            self.first = Some(quote! { #id });
+        }
         self.variants.push(quote! {
             #id = #value
         });
@@ -44,13 +51,29 @@ impl Enum {
             }
         }).collect::<Vec<_>>();
 
+        // like in Slice, the first enumerator is the default value
+        let derives_default = self.derives.iter().any(|derive| derive.to_string() == "Default");
+        let default_impl = match &self.first {
+            Some(first) if !derives_default => Some(quote! {
+                impl Default for #id_token {
+                    fn default() -> Self {
+                        #id_token::#first
+                    }
+                }
+            }),
+            _ => None
+        };
+
+        let derives = &self.derives;
         Ok(quote! {
-            #[derive(Debug, Copy, Clone, TryFromPrimitive, PartialEq)]
+            #[derive(Debug, Copy, Clone, TryFromPrimitive, PartialEq #(, #derives)*)]
             #[repr(i32)]
             pub enum #id_token {
                 #(#variant_tokens),*
             }
 
+            #default_impl
+
             impl OptionalType for #id_token {
                 fn optional_type() -> u8 {
                     4
@@ -8,8 +8,10 @@ use super::struct_member::StructMember;
 pub struct Exception {
     pub id: TokenStream,
     pub ice_id: String,
+    pub type_id: String,
     pub members: Vec<StructMember>,
     pub extends: Option<IceType>,
+    pub derives: Vec<TokenStream>
 }
 
 impl Exception {
@@ -17,8 +19,10 @@ impl Exception {
         Exception {
             id: TokenStream::new(),
             ice_id: String::new(),
+            type_id: String::new(),
             members: Vec::new(),
-            extends: None
+            extends: None,
+            derives: Vec::new()
         }
     }
 
@@ -33,6 +37,7 @@ impl Exception {
     pub fn generate(&self) -> Result<TokenStream, Box<dyn std::error::Error>> {
         let id_token = &self.id;
         let ice_id = &self.ice_id;
+        let type_id = &self.type_id;
         let mut member_tokens = self.members.iter().map(|member| {
             member.declare()
         }).collect::<Vec<_>>();
@@ -49,16 +54,25 @@ impl Exception {
                 extends: #token
             });
             member_from_bytes_tokens.push(quote!{
-                extends: #token::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
+                extends: <#token>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
             });
         }
 
+        // derived exceptions are rejected by the parser if their members have default values
+        let default_impl = match self.extends {
+            Some(_) => None,
+            None => StructMember::default_impl(id_token, &self.members, &self.derives)?
+        };
+
+        let derives = &self.derives;
         Ok(quote! {
-            #[derive(Debug)]
+            #[derive(Debug #(, #derives)*)]
             pub struct #id_token {
                 #(#member_tokens),*
             }
 
+            #default_impl
+
             impl std::fmt::Display for #id_token {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     write!(f, #ice_id)
@@ -67,6 +81,10 @@ impl Exception {
 
             impl std::error::Error for #id_token {}
 
+            impl UserException for #id_token {
+                const TYPE_ID: &'static str = #type_id;
+            }
+
             impl ToBytes for #id_token {
                 fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
                     let mut bytes = Vec::new();
@@ -82,7 +100,7 @@ impl Exception {
                     let _flag = SliceFlags::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
                     let _slice_name = String::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?;
                     let obj = Self {
-                        #(#member_from_bytes_tokens);*
+                        #(#member_from_bytes_tokens),*
                     };
                     *read_bytes = *read_bytes + read;
                     Ok(obj)
@@ -11,7 +11,8 @@ pub struct Function {
     pub return_type: FunctionReturn,
     pub arguments: Vec<FunctionArgument>,
     pub throws: FunctionThrows,
-    idempotent: bool
+    idempotent: bool,
+    amd: bool
 }
 
 impl Function {
@@ -23,6 +24,7 @@ impl Function {
             arguments: Vec::new(),
             throws: FunctionThrows::empty(),
             idempotent: false,
+            amd: false
         }
     }
 
@@ -30,6 +32,18 @@ impl Function {
         self.idempotent = true;
     }
 
+    /// Dispatches the operation asynchronously. The servant returns an
+    /// `AmdFuture` and is released while the future runs. The future
+    /// resolves to the return value followed by the out parameters, as a
+    /// tuple if there is more than one of them.
+    pub fn set_amd(&mut self) {
+        self.amd = true;
+    }
+
+    pub fn is_amd(&self) -> bool {
+        self.amd
+    }
+
     pub fn add_argument(&mut self, arg: FunctionArgument) {
         self.arguments.push(arg);
     }
@@ -50,15 +64,43 @@ impl Function {
         })
     }
 
+    /// The values an `["amd"]` operation resolves to: the return value,
+    /// unless it is void, followed by the out parameters.
+    fn amd_results(&self) -> Vec<(TokenStream, TokenStream)> {
+        let mut results = vec![];
+        if !matches!(self.return_type.r#type, IceType::VoidType) {
+            results.push((quote! { result }, self.return_type.token()));
+        }
+        results.extend(self.arguments.iter().filter(|arg| arg.out).map(|arg| (arg.id.clone(), arg.r#type.token())));
+        results
+    }
+
     pub fn generate_server_decl(&self) -> Result<TokenStream, Box<dyn std::error::Error>> {       
         let id_token = &self.id;
         let return_token = self.return_type.token();
+        if self.amd {
+            let mut arg_tokens = vec![
+                quote! { &mut self }
+            ];
+            arg_tokens.extend(self.arguments.iter().filter(|arg| !arg.out).map(|arg| arg.value_token()).collect::<Vec<_>>());
+            arg_tokens.push(quote! {
+                current: &Current
+            });
+            let result_tokens = self.amd_results().into_iter().map(|(_, type_token)| type_token).collect::<Vec<_>>();
+            let result_token = match result_tokens.len() {
+                1 => quote! { #(#result_tokens)* },
+                _ => quote! { (#(#result_tokens),*) }
+            };
+            return Ok(quote! {
+                fn #id_token (#(#arg_tokens),*) -> AmdFuture<#result_token>;
+            });
+        }
         let mut arg_tokens = vec![
             quote! { &mut self }
         ];
         arg_tokens.extend(self.arguments.iter().map(|arg| arg.token()).collect::<Vec<_>>());
         arg_tokens.push(quote! {
-            context: Option<HashMap<String, String>>
+            current: &Current
         });
 
         Ok(quote! {
@@ -130,33 +172,51 @@ impl Function {
         let ice_id_token = self.ice_id.clone();
         let id_token = &self.id;
         let wrapped_result = self.wrap_result();
+        if self.amd {
+            // `["amd"]` operations are dispatched by `handle_request_deferred`
+            return Ok(quote! {
+                #ice_id_token => {
+                    match self.handle_request_deferred(request, current).await? {
+                        Some(deferred) => deferred.await,
+                        None => ReplyData::request_failed(request, 4)
+                    }
+                },
+            });
+        }
         
         let func_call = if self.arguments.len() > 0 {
-            let mut arg_tokens = vec![];
-            arg_tokens.extend(self.arguments.iter().map(|arg| arg.pass_argument()).collect::<Vec<_>>());
+            let arg_tokens = self.arguments.iter().map(|arg| arg.pass_argument()).collect::<Vec<_>>();
+            let in_arguments = self.arguments.iter().filter(|arg| !arg.out).collect::<Vec<_>>();
             // TODO: split non optionals and options as non optionals come first and optionals need special handling
-            let decoded_tokens = self.arguments.iter().map(|arg| arg.decode_request()).collect::<Vec<_>>();
-            let decoded_opt_tokens = self.arguments.iter().map(|arg| arg.decode_optional_request()).collect::<Vec<_>>();
+            let decoded_tokens = in_arguments.iter().map(|arg| arg.decode_request()).collect::<Vec<_>>();
+            let decoded_opt_tokens = in_arguments.iter().map(|arg| arg.decode_optional_request()).collect::<Vec<_>>();
+            // out parameters are not sent by the client, the servant sets them
+            let out_tokens = self.arguments.iter().filter(|arg| arg.out).map(|arg| {
+                let id = &arg.id;
+                quote! { let mut #id = Default::default(); }
+            }).collect::<Vec<_>>();
             let encoded_outputs = self.arguments.iter().map(|arg| arg.encode_output()).collect::<Vec<_>>();
-            let has_outputs = self.arguments.iter().any(|arg| arg.out);
-            let mut_token = if has_outputs {
-                quote!{ mut }
+            let read_token = if !in_arguments.is_empty() {
+                Some(quote! { let mut read_bytes = 0; })
             } else {
-                quote!{ }
-            }; 
+                None
+            };
 
             quote!{
-                let mut read_bytes = 0;
+                #read_token
                 #(#decoded_tokens)*
                 #(#decoded_opt_tokens)*
-                let result = self.server_impl.#id_token (#(#arg_tokens),*, None).await;
+                #(#out_tokens)*
+                let result = self.server_impl.#id_token (#(#arg_tokens),*, current).await;
                 #wrapped_result
-                let #mut_token result = wrapped_result.to_bytes()?;
-                #(#encoded_outputs)*                
+                // out parameters are marshaled before the return value
+                let mut result = Vec::new();
+                #(#encoded_outputs)*
+                result.extend(wrapped_result.to_bytes()?);
             }
         } else {
             quote!{
-                let result = self.server_impl.#id_token (None).await;
+                let result = self.server_impl.#id_token (current).await;
                 #wrapped_result
                 let result = wrapped_result.to_bytes()?;
             }
@@ -173,4 +233,57 @@ impl Function {
             },
         })
     }
-}
\ No newline at end of file
+
+    /// Generates the handler of an `["amd"]` operation, which returns the
+    /// reply as a future that runs after the servant has been released.
+    pub fn generate_deferred_handler(&self) -> Result<TokenStream, Box<dyn std::error::Error>> {
+        let ice_id_token = self.ice_id.clone();
+        let id_token = &self.id;
+        let wrapped_result = self.wrap_result();
+        let in_arguments = self.arguments.iter().filter(|arg| !arg.out).collect::<Vec<_>>();
+        let arg_tokens = in_arguments.iter().map(|arg| arg.id.clone()).collect::<Vec<_>>();
+        let decoded_tokens = in_arguments.iter().map(|arg| arg.decode_request()).collect::<Vec<_>>();
+        let decoded_opt_tokens = in_arguments.iter().map(|arg| arg.decode_optional_request()).collect::<Vec<_>>();
+        let encoded_outputs = self.arguments.iter().map(|arg| arg.encode_output()).collect::<Vec<_>>();
+        let read_token = if !in_arguments.is_empty() {
+            Some(quote! { let mut read_bytes = 0; })
+        } else {
+            None
+        };
+        let result_ids = self.amd_results().into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let await_token = match result_ids.len() {
+            0 => quote! { future.await?; let result = (); },
+            1 => quote! { let #(#result_ids)* = future.await?; },
+            _ => quote! { let (#(#result_ids),*) = future.await?; }
+        };
+        let void_token = if matches!(self.return_type.r#type, IceType::VoidType) && !result_ids.is_empty() {
+            Some(quote! { let result = (); })
+        } else {
+            None
+        };
+
+        Ok(quote! {
+            #ice_id_token => {
+                #read_token
+                #(#decoded_tokens)*
+                #(#decoded_opt_tokens)*
+                let future = self.server_impl.#id_token (#(#arg_tokens,)* current);
+                let request_id = request.request_id;
+                Ok(Some(Box::pin(async move {
+                    #await_token
+                    #void_token
+                    #wrapped_result
+                    // out parameters are marshaled before the return value
+                    let mut result = Vec::new();
+                    #(#encoded_outputs)*
+                    result.extend(wrapped_result.to_bytes()?);
+                    Ok(ReplyData {
+                        request_id,
+                        status: 0,
+                        body: Encapsulation::from(result)
+                    })
+                })))
+            },
+        })
+    }
+}
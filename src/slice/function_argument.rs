@@ -32,6 +32,14 @@ impl FunctionArgument {
         }
     }
 
+    /// Declares the argument of an `["amd"]` operation, which takes
+    /// ownership of all its arguments.
+    pub fn value_token(&self) -> TokenStream {
+        let id = &self.id;
+        let typename = self.r#type.token();
+        quote! { #id: #typename }
+    }
+
     pub fn token(&self) -> TokenStream {
         let id = &self.id;
         let out = if self.out { 
@@ -62,7 +70,7 @@ impl FunctionArgument {
                     quote! { }
                 };
                 Some(quote! {
-                    let #mut_token #id_token = #type_token::from_bytes(&request.params.data[read_bytes as usize..request.params.data.len()], &mut read_bytes)?;
+                    let #mut_token #id_token = <#type_token>::from_bytes(&request.params.data[read_bytes as usize..request.params.data.len()], &mut read_bytes)?;
                 })        
             }
         }
@@ -78,7 +86,7 @@ impl FunctionArgument {
                     match OptionalFlag::from_bytes(&request.params.data[read_bytes as usize..request.params.data.len()], &mut flag_bytes) {
                         Ok(flag) => {
                             if flag.tag == #tag {
-                                #id_token = #type_token::from_bytes(&request.params.data[read_bytes as usize..request.params.data.len()], &mut read_bytes)?;
+                                #id_token = <#type_token>::from_bytes(&request.params.data[read_bytes as usize..request.params.data.len()], &mut read_bytes)?;
                             }
                         }
                         _ => {}
@@ -115,7 +123,7 @@ impl FunctionArgument {
             let id_token = &self.id;
             let type_token = &self.r#type.token_from();
             Some(quote! {
-                *#id_token = #type_token::from_bytes(&reply.body.data[read_bytes as usize..reply.body.data.len()], &mut read_bytes)?;
+                *#id_token = <#type_token>::from_bytes(&reply.body.data[read_bytes as usize..reply.body.data.len()], &mut read_bytes)?;
             })
         } else {
             None
@@ -4,30 +4,30 @@ use super::types::IceType;
 
 #[derive(Clone, Debug)]
 pub struct FunctionThrows {
-    pub r#type: Option<IceType>,
+    pub types: Vec<IceType>,
 }
 
 impl FunctionThrows {
-    pub fn new(r#type: IceType) -> FunctionThrows {
+    pub fn new(types: Vec<IceType>) -> FunctionThrows {
         FunctionThrows {
-            r#type: Some(r#type)
+            types
         }
     }
 
     pub fn empty() -> FunctionThrows {
         FunctionThrows {
-            r#type: None
+            types: Vec::new()
         }
     }
 
+    /// The `DeclaredExceptions` type passed to `Proxy::dispatch`, a tuple
+    /// if the operation throws more than one exception.
     pub fn token(&self) -> TokenStream {
-        match &self.r#type {
-            Some(throw) => {
-                throw.token()                
-            },
-            _ => quote! {
-                ProtocolError
-            }
-        }   
+        let tokens = self.types.iter().map(|throw| throw.token()).collect::<Vec<_>>();
+        match tokens.len() {
+            0 => quote! { ProtocolError },
+            1 => quote! { #(#tokens)* },
+            _ => quote! { (#(#tokens),*) }
+        }
     }
 }
@@ -6,7 +6,11 @@ use quote::{__private::TokenStream, format_ident, quote};
 pub struct Interface {
     pub id: TokenStream,
     pub ice_id: String,
-    pub functions: Vec<Function>
+    pub type_id: String,
+    pub path: String,
+    pub functions: Vec<Function>,
+    pub extends: Vec<String>,
+    pub bases: Vec<Interface>
 }
 
 impl Interface {
@@ -14,7 +18,11 @@ impl Interface {
         Interface {
             id: TokenStream::new(),
             ice_id: String::from(""),
-            functions: Vec::new()
+            type_id: String::new(),
+            path: String::new(),
+            functions: Vec::new(),
+            extends: Vec::new(),
+            bases: Vec::new()
         }
     }
 
@@ -22,15 +30,51 @@ impl Interface {
         self.functions.push(function);
     }
 
-    pub fn generate(&self, mod_path: &str) -> Result<TokenStream, Box<dyn std::error::Error>> {
-        let mut decl_tokens = TokenStream::new();
-        for function in &self.functions {
-            let token = function.generate_decl()?;
-            decl_tokens = quote! {
-                #decl_tokens
-                #token
-            };
+    /// Adds a direct base interface. The bases of `base` are added as
+    /// well, so `bases` holds every inherited interface exactly once.
+    pub fn add_base(&mut self, base: Interface) {
+        for inherited in &base.bases {
+            if !self.bases.iter().any(|known| known.type_id == inherited.type_id) {
+                self.bases.push(inherited.clone());
+            }
         }
+        if !self.bases.iter().any(|known| known.type_id == base.type_id) {
+            self.bases.push(base);
+        }
+    }
+
+    /// Returns the functions of the interface followed by all inherited
+    /// functions.
+    pub fn all_functions(&self) -> Vec<&Function> {
+        self.functions.iter()
+            .chain(self.bases.iter().flat_map(|base| base.functions.iter()))
+            .collect()
+    }
+
+    /// Returns the sorted type ids of the interface, as reported by
+    /// `ice_ids`. Every interface implicitly derives from `::Ice::Object`.
+    pub fn type_ids(&self, mod_path: &str) -> Vec<String> {
+        let mut type_ids = vec![
+            String::from("::Ice::Object"),
+            format!("{}::{}", mod_path, self.ice_id)
+        ];
+        type_ids.extend(self.bases.iter().map(|base| base.type_id.clone()));
+        type_ids.sort();
+        type_ids.dedup();
+        type_ids
+    }
+
+    /// Returns the path of the generated item named after the interface
+    /// with the given suffix, like `slice_root::demo::HelloPrx`.
+    fn path_token(&self, suffix: &str) -> TokenStream {
+        let segments = self.path.split("::")
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| format_ident!("{}", segment));
+        let id = format_ident!("{}{}", self.id.to_string(), suffix);
+        quote! { #(#segments::)* #id }
+    }
+
+    fn generate_impl(&self) -> Result<TokenStream, Box<dyn std::error::Error>> {
         let mut impl_tokens = TokenStream::new();
         for function in &self.functions {
             let token = function.generate_impl()?;
@@ -39,6 +83,19 @@ impl Interface {
                 #token
             };
         }
+        Ok(impl_tokens)
+    }
+
+    pub fn generate(&self, mod_path: &str) -> Result<TokenStream, Box<dyn std::error::Error>> {
+        let mut decl_tokens = TokenStream::new();
+        for function in &self.functions {
+            let token = function.generate_decl()?;
+            decl_tokens = quote! {
+                #decl_tokens
+                #token
+            };
+        }
+        let impl_tokens = self.generate_impl()?;
         let mut server_decl_tokens = TokenStream::new();
         for function in &self.functions {
             let token = function.generate_server_decl()?;
@@ -48,27 +105,83 @@ impl Interface {
             };
         }
         let mut server_handler_tokens = TokenStream::new();
-        for function in &self.functions {
+        for function in self.all_functions() {
             let token = function.generate_server_handler()?;
             server_handler_tokens = quote! {
                 #server_handler_tokens
                 #token
             };
         }
+        let all_functions = self.all_functions();
+        let deferred_handler = if all_functions.iter().any(|function| function.is_amd()) {
+            let mut deferred_handler_tokens = TokenStream::new();
+            for function in all_functions.iter().filter(|function| function.is_amd()) {
+                let token = function.generate_deferred_handler()?;
+                deferred_handler_tokens = quote! {
+                    #deferred_handler_tokens
+                    #token
+                };
+            }
+            Some(quote! {
+                async fn handle_request_deferred(&mut self, request: &RequestData, current: &Current) -> Result<Option<DeferredReply>, Box<dyn std::error::Error + Sync + Send>> {
+                    match request.operation.as_ref() {
+                        #deferred_handler_tokens
+                        _ => Ok(None)
+                    }
+                }
+            })
+        } else {
+            None
+        };
 
         let id_token = &self.id;
         let id_proxy_token = format_ident!("{}Prx", self.id.to_string());
         let id_server_trait_token = format_ident!("{}I", self.id.to_string());
         let id_server_token = format_ident!("{}Server", self.id.to_string());
         let type_id_token = format!("{}::{}", mod_path, self.ice_id);
+        let type_ids_token = self.type_ids(mod_path);
+        let direct_bases = self.bases.iter()
+            .filter(|base| self.extends.contains(&base.type_id))
+            .collect::<Vec<_>>();
+        let base_tokens = direct_bases.iter().map(|base| base.path_token("")).collect::<Vec<_>>();
+        let base_server_trait_tokens = direct_bases.iter()
+            .map(|base| base.path_token("I"))
+            .collect::<Vec<_>>();
+        let server_trait_bounds = if base_server_trait_tokens.is_empty() {
+            None
+        } else {
+            Some(quote! { : #(#base_server_trait_tokens)+* })
+        };
+        let mut base_impl_tokens = TokenStream::new();
+        for base in &self.bases {
+            let base_id_token = base.path_token("");
+            let base_proxy_token = base.path_token("Prx");
+            let impl_tokens = base.generate_impl()?;
+            base_impl_tokens = quote! {
+                #base_impl_tokens
+
+                #[async_trait]
+                impl #base_id_token for #id_proxy_token {
+                    #impl_tokens
+                }
+
+                impl From<#id_proxy_token> for #base_proxy_token {
+                    fn from(proxy: #id_proxy_token) -> #base_proxy_token {
+                        #base_proxy_token {
+                            proxy: proxy.proxy
+                        }
+                    }
+                }
+            };
+        }
         Ok(quote! {
             #[async_trait]
-            pub trait #id_token : IceObject {
+            pub trait #id_token : IceObject #(+ #base_tokens)* {
                 #decl_tokens
             }
 
             #[async_trait]
-            pub trait #id_server_trait_token {
+            pub trait #id_server_trait_token #server_trait_bounds {
                 #server_decl_tokens
             }
 
@@ -84,29 +197,51 @@ impl Interface {
                     }
                 }
 
-                async fn ice_is_a(&self, param: &str) -> bool {
-                    param == #type_id_token
-                }
-                // TODO: ice_ids etc...
+                const TYPE_ID: &'static str = #type_id_token;
+                const TYPE_IDS: &'static [&'static str] = &[#(#type_ids_token),*];
             }
 
             #[async_trait]
             impl IceObjectServer for #id_server_token {
-                async fn handle_request(&mut self, request: &RequestData) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
+                async fn handle_request(&mut self, request: &RequestData, current: &Current) -> Result<ReplyData, Box<dyn std::error::Error + Sync + Send>> {
                     match request.operation.as_ref() {
+                        "ice_ping" => {
+                            Ok(ReplyData {
+                                request_id: request.request_id,
+                                status: 0,
+                                body: Encapsulation::empty()
+                            })
+                        },
                         "ice_isA" => {
                             let mut read = 0;
                             let param = String::from_bytes(&request.params.data, &mut read)?;
                             Ok(ReplyData {
                                 request_id: request.request_id,
                                 status: 0,
-                                body: Encapsulation::from(self.ice_is_a(&param).await.to_bytes()?)
+                                body: Encapsulation::from(Self::TYPE_IDS.contains(&param.as_str()).to_bytes()?)
+                            })
+                        },
+                        "ice_id" => {
+                            Ok(ReplyData {
+                                request_id: request.request_id,
+                                status: 0,
+                                body: Encapsulation::from(String::from(Self::TYPE_ID).to_bytes()?)
+                            })
+                        },
+                        "ice_ids" => {
+                            let type_ids = Self::TYPE_IDS.iter().map(|id| String::from(*id)).collect::<Vec<String>>();
+                            Ok(ReplyData {
+                                request_id: request.request_id,
+                                status: 0,
+                                body: Encapsulation::from(type_ids.to_bytes()?)
                             })
                         },
                         #server_handler_tokens
-                        _ => Err(Box::new(ProtocolError::new("Operation not found")))
+                        _ => ReplyData::request_failed(request, 4)
                     }
                 }
+
+                #deferred_handler
             }
 
             pub struct #id_proxy_token {
@@ -147,6 +282,16 @@ impl Interface {
                 #impl_tokens
             }
 
+            #base_impl_tokens
+
+            impl From<Proxy> for #id_proxy_token {
+                fn from(proxy: Proxy) -> Self {
+                    Self {
+                        proxy: proxy,
+                    }
+                }
+            }
+
             impl #id_proxy_token {
                 #[allow(dead_code)]
                 pub async fn unchecked_cast(proxy: Proxy) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
@@ -168,3 +313,89 @@ impl Interface {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+    use crate::adapter::Adapter;
+    use crate::bindings::glacier_2::{PermissionsVerifier, PermissionsVerifierI, PermissionsVerifierPrx, PermissionsVerifierServer};
+    use crate::bindings::ice::{ProcessI, ProcessPrx, ProcessServer};
+    use crate::current::Current;
+    use crate::encoding::{FromBytes, ToBytes};
+    use crate::errors::{OperationNotExistException, ProtocolError};
+    use crate::iceobject::IceObject;
+    use crate::initdata::InitializationData;
+    use crate::protocol::Encapsulation;
+
+    struct ProcessImpl {}
+
+    #[async_trait]
+    impl ProcessI for ProcessImpl {
+        async fn shutdown(&mut self, _current: &Current) -> () {}
+
+        async fn write_message(&mut self, _message: &String, _fd: i32, _current: &Current) -> () {}
+    }
+
+    struct VerifierImpl {}
+
+    #[async_trait]
+    impl PermissionsVerifierI for VerifierImpl {
+        async fn check_permissions(&mut self, user_id: &String, password: &String, reason: &mut String, _current: &Current) -> bool {
+            assert!(reason.is_empty());
+            if user_id == password {
+                return true;
+            }
+            *reason = format!("wrong password for {}", user_id);
+            false
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_out_parameters() {
+        let adapter = Adapter::with_endpoint("verifier", "mem -n verifiertest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("verifier", Box::new(PermissionsVerifierServer::new(Box::new(VerifierImpl {}))));
+        adapter.activate().await.expect("Cannot activate adapter");
+        let proxy = adapter.create_proxy("verifier").await.expect("Cannot create proxy");
+        let mut verifier = PermissionsVerifierPrx::unchecked_cast(proxy).await.expect("Cannot cast proxy");
+
+        let mut reason = String::new();
+        assert!(verifier.check_permissions(&String::from("admin"), &String::from("admin"), &mut reason, None).await.expect("Request failed"));
+        assert!(reason.is_empty());
+        assert!(!verifier.check_permissions(&String::from("admin"), &String::from("secret"), &mut reason, None).await.expect("Request failed"));
+        assert_eq!("wrong password for admin", reason);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_handle_request() {
+        let adapter = Adapter::with_endpoint("generated", "mem -n generatedtest", &InitializationData::new()).expect("Cannot create adapter");
+        adapter.add("process", Box::new(ProcessServer::new(Box::new(ProcessImpl {}))));
+        adapter.activate().await.expect("Cannot activate adapter");
+        let mut proxy = adapter.create_proxy("process").await.expect("Cannot create proxy");
+
+        let reply = proxy.dispatch::<ProtocolError>("ice_ping", 1, &Encapsulation::empty(), None).await.expect("ice_ping failed");
+        assert_eq!(0, reply.status);
+        assert!(reply.body.data.is_empty());
+
+        let reply = proxy.dispatch::<ProtocolError>("ice_id", 1, &Encapsulation::empty(), None).await.expect("ice_id failed");
+        assert_eq!("::Ice::Process", String::from_bytes(&reply.body.data, &mut 0).expect("Cannot decode type id"));
+
+        let reply = proxy.dispatch::<ProtocolError>("ice_ids", 1, &Encapsulation::empty(), None).await.expect("ice_ids failed");
+        assert_eq!(vec!["::Ice::Object", "::Ice::Process"], Vec::<String>::from_bytes(&reply.body.data, &mut 0).expect("Cannot decode type ids"));
+
+        for (type_id, expected) in vec![("::Ice::Process", true), ("::Ice::Object", true), ("::Ice::Locator", false)] {
+            let params = Encapsulation::from(String::from(type_id).to_bytes().expect("Cannot encode type id"));
+            let reply = proxy.dispatch::<ProtocolError>("ice_isA", 1, &params, None).await.expect("ice_isA failed");
+            assert_eq!(expected, bool::from_bytes(&reply.body.data, &mut 0).expect("Cannot decode result"));
+        }
+
+        let error = proxy.dispatch::<ProtocolError>("destroy", 0, &Encapsulation::empty(), None).await.expect_err("Unknown operation dispatched");
+        let error = error.downcast_ref::<OperationNotExistException>().expect("Expected an OperationNotExistException");
+        assert_eq!("process", error.id.name);
+        assert_eq!("destroy", error.operation);
+
+        let mut process = ProcessPrx::checked_cast(proxy).await.expect("Cannot cast proxy");
+        process.ice_ping().await.expect("ice_ping failed");
+        assert_eq!("::Ice::Process", process.ice_id().await.expect("ice_id failed"));
+        assert!(process.ice_is_a().await.expect("ice_isA failed"));
+    }
+}
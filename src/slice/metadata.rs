@@ -0,0 +1,128 @@
+use crate::errors::ParsingError;
+use std::cell::RefCell;
+use std::str::FromStr;
+use quote::{__private::TokenStream, format_ident, quote};
+
+
+/// Metadata directives of other language mappings and of the Ice
+/// run time. They are ignored without a warning.
+const IGNORED_PREFIXES: &[&str] = &[
+    "cpp:", "cpp98:", "cpp11:", "cs:", "java:", "js:", "matlab:", "objc:",
+    "php:", "python:", "ruby:", "swift:", "ice-prefix", "suppress-warning",
+    "deprecate", "deprecated", "format:", "protected", "preserve-slice",
+    "delegate", "underscore"
+];
+
+thread_local! {
+    /// The warnings of `Metadata::check` not yet collected by the parser.
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+}
+
+/// Returns and clears the warnings reported by `Metadata::check` on
+/// this thread.
+pub(crate) fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// The metadata directives of a definition, like `["amd"]` or
+/// `["rust:derive:Hash"]`.
+#[derive(Clone, Debug, Default)]
+pub struct Metadata {
+    pub directives: Vec<String>
+}
+
+impl Metadata {
+    pub fn new(directives: Vec<String>) -> Metadata {
+        Metadata {
+            directives
+        }
+    }
+
+    pub fn has(&self, directive: &str) -> bool {
+        self.directives.iter().any(|item| item == directive)
+    }
+
+    /// Returns the values of all directives starting with `prefix`.
+    pub fn values(&self, prefix: &str) -> Vec<&str> {
+        self.directives.iter()
+            .filter(|item| item.starts_with(prefix))
+            .map(|item| &item[prefix.len()..])
+            .collect()
+    }
+
+    /// Returns the value of the last directive starting with `prefix`.
+    pub fn value(&self, prefix: &str) -> Option<&str> {
+        self.values(prefix).last().copied()
+    }
+
+    /// Reports a warning for every directive that is neither supported by
+    /// `context` nor meant for another language mapping. Entries of
+    /// `supported` ending with `:` match any value.
+    pub fn check(&self, context: &str, supported: &[&str]) {
+        for directive in &self.directives {
+            let known = supported.iter().any(|item| {
+                if item.ends_with(':') {
+                    directive.starts_with(item)
+                } else {
+                    directive == item
+                }
+            });
+            let ignored = IGNORED_PREFIXES.iter().any(|prefix| directive.starts_with(prefix));
+            if !known && !ignored {
+                let warning = format!("ignoring unknown metadata \"{}\" on {}", directive, context);
+                WARNINGS.with(|warnings| warnings.borrow_mut().push(warning));
+            }
+        }
+    }
+
+    /// Returns the identifier given by `["rust:identifier:..."]`.
+    pub fn identifier(&self) -> Result<Option<TokenStream>, Box<dyn std::error::Error>> {
+        match self.value("rust:identifier:") {
+            Some(name) => {
+                let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') &&
+                    name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid {
+                    return Err(Box::new(ParsingError::new(
+                        &format!("Invalid rust:identifier \"{}\"", name)
+                    )));
+                }
+                let id = format_ident!("{}", name);
+                Ok(Some(quote! { #id }))
+            }
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the additional derives given by `["rust:derive:..."]`.
+    pub fn derives(&self) -> Result<Vec<TokenStream>, Box<dyn std::error::Error>> {
+        self.values("rust:derive:").iter().map(|path| {
+            TokenStream::from_str(path).map_err(|_| -> Box<dyn std::error::Error> {
+                Box::new(ParsingError::new(&format!("Invalid rust:derive \"{}\"", path)))
+            })
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metadata() {
+        let metadata = Metadata::new(vec![
+            String::from("amd"),
+            String::from("rust:derive:Hash"),
+            String::from("rust:derive:serde::Serialize"),
+            String::from("rust:identifier:renamed")
+        ]);
+        assert!(metadata.has("amd"));
+        assert!(!metadata.has("rust:derive"));
+        assert_eq!(vec!["Hash", "serde::Serialize"], metadata.values("rust:derive:"));
+        assert_eq!(2, metadata.derives().unwrap().len());
+        assert_eq!("renamed", metadata.identifier().unwrap().unwrap().to_string());
+        assert_eq!(None, metadata.value("rust:type:"));
+
+        let metadata = Metadata::new(vec![String::from("rust:identifier:1abc")]);
+        assert!(metadata.identifier().is_err());
+    }
+}
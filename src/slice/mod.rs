@@ -12,4 +12,8 @@ pub mod function_throws;
 pub mod parser;
 pub mod exception;
 pub mod class;
-pub mod escape;
\ No newline at end of file
+pub mod constant;
+pub mod metadata;
+pub mod escape;
+pub mod preprocessor;
+pub mod build;
@@ -3,16 +3,18 @@ use crate::slice::structure::Struct;
 use crate::slice::interface::Interface;
 use crate::slice::exception::Exception;
 use crate::slice::class::Class;
-use std::{path::Path, process::Stdio};
-use std::fs::File;
+use crate::slice::constant::{Constant, ConstValue};
+use std::path::Path;
+#[cfg(feature = "rustfmt")]
+use std::process::{Command, Stdio};
 use std::collections::BTreeMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 use inflector::cases::{pascalcase, snakecase};
 use quote::{__private::TokenStream, format_ident, quote};
+#[cfg(feature = "rustfmt")]
 use std::io::Write;
 use super::types::IceType;
-use std::process::Command;
 
 
 struct UseStatements {
@@ -42,6 +44,25 @@ impl UseStatements {
     }
 }
 
+/// Looks up a Slice name the way the Slice compiler does. Names starting
+/// with `::` are absolute, other names are searched in `scope` first and
+/// then in each enclosing module.
+pub fn lookup<T: Clone>(map: &BTreeMap<String, T>, scope: &str, name: &str) -> Option<T> {
+    if name.starts_with("::") {
+        return map.get(name).cloned();
+    }
+    let mut scope = scope;
+    loop {
+        if let Some(value) = map.get(&format!("{}::{}", scope, name)) {
+            return Some(value.clone());
+        }
+        match scope.rfind("::") {
+            Some(index) => scope = &scope[..index],
+            None => return None
+        }
+    }
+}
+
 pub struct Module {
     pub name: String,
     pub full_name: String,
@@ -52,7 +73,12 @@ pub struct Module {
     interfaces: Vec<Interface>,
     typedefs: Vec<(String, IceType)>,
     classes: Vec<Class>,
-    pub type_map: Rc<RefCell<BTreeMap<String, String>>>
+    constants: Vec<Constant>,
+    pub type_map: Rc<RefCell<BTreeMap<String, String>>>,
+    pub interface_map: Rc<RefCell<BTreeMap<String, Interface>>>,
+    pub constant_map: Rc<RefCell<BTreeMap<String, ConstValue>>>,
+    /// The warnings reported while parsing, like unknown metadata.
+    pub warnings: Vec<String>
 }
 
 impl Module {
@@ -67,7 +93,11 @@ impl Module {
             exceptions: vec![],
             typedefs: vec![],
             classes: vec![],
-            type_map: type_map
+            constants: vec![],
+            type_map,
+            interface_map: Rc::new(RefCell::new(BTreeMap::new())),
+            constant_map: Rc::new(RefCell::new(BTreeMap::new())),
+            warnings: vec![]
         }
     }
 
@@ -85,6 +115,37 @@ impl Module {
         snakecase::to_snake_case(&self.name)
     }
 
+    /// Returns the path of the module in the generated code, relative to
+    /// the `slice_root` alias of the generated root module.
+    pub fn rust_path(&self) -> String {
+        std::iter::once(String::from("slice_root"))
+            .chain(self.full_name.split("::").filter(|name| !name.is_empty()).map(snakecase::to_snake_case))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Registers the type `ice_id` of this module, which is generated as
+    /// the Rust item `id`.
+    pub fn register_type(&self, ice_id: &str, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let scoped_name = format!("{}::{}", self.full_name, ice_id);
+        if self.type_map.borrow().contains_key(&scoped_name) {
+            return Err(Box::new(ParsingError::about(&format!("Duplicate definition of {}", scoped_name), ice_id)));
+        }
+        self.type_map.borrow_mut().insert(scoped_name, format!("{}::{}", self.rust_path(), id));
+        Ok(())
+    }
+
+    /// Replaces the Slice names in `r#type` by the Rust paths of the
+    /// types they refer to, as seen from this module.
+    pub fn resolve_type(&self, r#type: &mut IceType) -> Result<(), Box<dyn std::error::Error>> {
+        let type_map = self.type_map.borrow();
+        r#type.resolve(&|name| {
+            lookup(&type_map, &self.full_name, name).ok_or_else(|| -> Box<dyn std::error::Error> {
+                Box::new(ParsingError::about(&format!("Unknown type {} in module {}", name, self.full_name), name))
+            })
+        })
+    }
+
     pub fn add_enum(&mut self, enumeration: Enum) {
         self.enumerations.push(enumeration);
     }
@@ -109,7 +170,11 @@ impl Module {
         self.classes.push(class);
     }
 
-    fn uses(&self, super_mod: &str) -> UseStatements {
+    pub fn add_constant(&mut self, constant: Constant) {
+        self.constants.push(constant);
+    }
+
+    fn uses(&self) -> UseStatements {
         let mut use_statements = UseStatements::new();
 
         use_statements.use_crate(quote! { use async_trait::async_trait });
@@ -127,94 +192,25 @@ impl Module {
             use_statements.use_crate(quote! { use ice_rs::encoding::* });
         }
 
-        if self.structs.len() > 0 {
+        if !self.structs.is_empty() || !self.classes.is_empty() {
             use_statements.use_crate(quote! { use ice_rs::encoding::* });
-
-            for item in &self.structs {
-                for member in &item.members {
-                    match &member.r#type {
-                        IceType::CustomType(name) => {
-                            let use_statement = self.type_map.as_ref().borrow().get(name).unwrap().clone();
-                            if !use_statement.eq(&self.snake_name()) {
-                                let super_token = format_ident!("{}", super_mod);
-                                let use_token = format_ident!("{}", use_statement);
-                                use_statements.use_crate(quote! { use crate::#super_token::#use_token::* });
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
-
-        if self.classes.len() > 0 {
-            use_statements.use_crate(quote! { use ice_rs::encoding::* });
-
-            for item in &self.classes {
-                for member in &item.members {
-                    match &member.r#type {
-                        IceType::CustomType(name) => {
-                            let use_statement = self.type_map.as_ref().borrow().get(name).unwrap().clone();
-                            if !use_statement.eq(&self.snake_name()) {
-                                let super_token = format_ident!("{}", super_mod);
-                                let use_token = format_ident!("{}", use_statement);
-                                use_statements.use_crate(quote! { use crate::#super_token::#use_token::* });
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
         }
 
         if self.interfaces.len() > 0 {
             use_statements.use_crate(quote! { use ice_rs::encoding::* });
             use_statements.use_crate(quote! { use ice_rs::proxy::Proxy });
             use_statements.use_crate(quote! { use ice_rs::iceobject::* });
-            use_statements.use_crate(quote! { use ice_rs::protocol::* });            
-
-            for item in &self.interfaces {
-                for func in &item.functions {
-                    use_statements.use_crate(quote! { use std::collections::HashMap });
-
-                    for arg in &func.arguments {
-                        match &arg.r#type {
-                            IceType::CustomType(name) => {
-                                let use_statement = self.type_map.as_ref().borrow().get(name).unwrap().clone();
-                                if !use_statement.eq(&self.snake_name()) {
-                                    let super_token = format_ident!("{}", super_mod);
-                                    let use_token = format_ident!("{}", use_statement);
-                                    use_statements.use_crate(quote! { use crate::#super_token::#use_token::* });
-                                }
-                            }
-                            _ => {}
-                        };
-                    }
-
-                    match &func.throws.r#type {
-                        Some(throws) => {
-                            match throws {
-                                IceType::CustomType(name) => {
-                                    let use_statement = self.type_map.as_ref().borrow().get(name).unwrap().clone();
-                                    if !use_statement.eq(&self.snake_name()) {
-                                        let super_token = format_ident!("{}", super_mod);
-                                        let use_token = format_ident!("{}", use_statement);
-                                        use_statements.use_crate(quote! { use crate::#super_token::#use_token::* });
-                                    }
-                                }
-                                _ => {}
-                            };
-                        }
-                        _ => {}
-                    };
-                }
-            }
+            use_statements.use_crate(quote! { use ice_rs::protocol::* });
+            use_statements.use_crate(quote! { use ice_rs::current::Current });
+            use_statements.use_crate(quote! { use std::collections::HashMap });
         }
 
         use_statements
     }
 
-    pub fn generate(&self, dest: &Path, mod_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    /// Returns the code of this module. Sub modules are declared as
+    /// `pub mod name;` or, if `inline` is set, generated in place.
+    fn tokens(&self, inline: bool) -> Result<TokenStream, Box<dyn std::error::Error>> {
         let mut tokens = vec![];
         tokens.push(quote! {
             // This file has been generated.
@@ -222,27 +218,40 @@ impl Module {
             #[allow(unused_imports)]
         });
 
-        // build up use statements
-        let mut use_path = mod_path;
-        if use_path.len() == 0 {
-            use_path = dest.iter().last().unwrap().to_str().unwrap();
+        tokens.push(self.uses().generate()?);
+
+        // every module refers to types through the alias of the root module
+        let depth = self.full_name.split("::").filter(|name| !name.is_empty()).count();
+        if depth > 0 {
+            let super_tokens = vec![quote! { super }; depth];
+            tokens.push(quote! {
+                #[allow(unused_imports)]
+                use #(#super_tokens)::*::{self as slice_root};
+            });
         }
-        tokens.push(self.uses(&use_path).generate()?);
 
         for sub_module in &self.sub_modules {
             let mod_name = sub_module.snake_name();
             let ident = format_ident!("{}", mod_name);
-            tokens.push(quote! {
-                pub mod #ident;
-            });
-            sub_module.generate(&dest.join(Path::new(&mod_name)), &use_path)?;
+            if inline {
+                let sub_tokens = sub_module.tokens(true)?;
+                tokens.push(quote! {
+                    pub mod #ident {
+                        #sub_tokens
+                    }
+                });
+            } else {
+                tokens.push(quote! {
+                    pub mod #ident;
+                });
+            }
         }
 
         for (id, vartype) in &self.typedefs {
             let id_str = format_ident!("{}", pascalcase::to_pascal_case(&id));
             let var_token = vartype.token();
             tokens.push(quote! {
-                type #id_str = #var_token;
+                pub type #id_str = #var_token;
             });
         }
 
@@ -250,6 +259,10 @@ impl Module {
             tokens.push(enumeration.generate()?);
         }
 
+        for constant in &self.constants {
+            tokens.push(constant.generate()?);
+        }
+
         for structure in &self.structs {
             tokens.push(structure.generate()?);
         }
@@ -266,25 +279,88 @@ impl Module {
             tokens.push(interface.generate(&self.full_name)?);
         }
 
-        let mod_token = quote! { #(#tokens)* };
+        Ok(quote! { #(#tokens)* })
+    }
+
+    /// Generates this module as `mod.rs` in `dest` and every sub module
+    /// in a sub directory of the same name.
+    pub fn generate(&self, dest: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        for sub_module in &self.sub_modules {
+            sub_module.generate(&dest.join(Path::new(&sub_module.snake_name())))?;
+        }
 
         std::fs::create_dir_all(dest)?;
-        let mod_file = &dest.join(Path::new("mod.rs")); 
-        let mut child = Command::new("rustfmt")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .arg("--edition")
-            .arg("2018")
-            .spawn()?;
-        {
-            let stdin = child.stdin.as_mut().ok_or(ParsingError::new("Could not get stdin of rustfmt process"))?;
-            stdin.write_all(mod_token.to_string().as_bytes())?;
-        }    
-        let output = child.wait_with_output()?;
-        let mut file = File::create(mod_file)?;
-        match file.write_all(&output.stdout) {
-            Ok(_) => Ok(()),
-            Err(_) =>  Err(Box::new(ParsingError::new("Could not write file")))
+        write_formatted(&dest.join(Path::new("mod.rs")), &self.tokens(false)?)
+    }
+
+    /// Generates this module and all sub modules into the single file
+    /// `dest`, which can be included with `include!`.
+    pub fn generate_file(&self, dest: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
         }
+        write_formatted(dest, &self.tokens(true)?)
+    }
+}
+
+fn write_formatted(mod_file: &Path, mod_token: &TokenStream) -> Result<(), Box<dyn std::error::Error>> {
+    let code = format_code(mod_token)?;
+    std::fs::write(mod_file, code).map_err(|error| -> Box<dyn std::error::Error> {
+        Box::new(ParsingError::new(&format!("Could not write {}: {}", mod_file.display(), error)))
+    })
+}
+
+/// Formats the generated code with prettyplease.
+#[cfg(not(feature = "rustfmt"))]
+fn format_code(mod_token: &TokenStream) -> Result<String, Box<dyn std::error::Error>> {
+    let file = syn::parse2::<syn::File>(mod_token.clone()).map_err(|error| {
+        ParsingError::new(&format!("Generated code is invalid: {}", error))
+    })?;
+    Ok(prettyplease::unparse(&file))
+}
+
+/// Formats the generated code with rustfmt, which must be installed.
+#[cfg(feature = "rustfmt")]
+fn format_code(mod_token: &TokenStream) -> Result<String, Box<dyn std::error::Error>> {
+    let mut child = Command::new("rustfmt")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .arg("--edition")
+        .arg("2018")
+        .spawn()
+        .map_err(|error| ParsingError::new(&format!("Could not run rustfmt: {}", error)))?;
+    {
+        let stdin = child.stdin.as_mut().ok_or(ParsingError::new("Could not get stdin of rustfmt process"))?;
+        stdin.write_all(mod_token.to_string().as_bytes())?;
     }
-}
\ No newline at end of file
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(Box::new(ParsingError::new(
+            &format!("rustfmt failed: {}", String::from_utf8_lossy(&output.stderr).trim())
+        )));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write_formatted() {
+        let dir = std::env::temp_dir().join(format!("ice-rs-module-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mod_file = dir.join("mod.rs");
+
+        write_formatted(&mod_file, &quote! { pub struct Item { pub id: i32 } }).unwrap();
+        assert_eq!("pub struct Item {\n    pub id: i32,\n}\n", std::fs::read_to_string(&mod_file).unwrap());
+
+        // invalid code is reported instead of writing an empty file
+        std::fs::remove_file(&mod_file).unwrap();
+        assert!(write_formatted(&mod_file, &quote! { pub struct { } }).is_err());
+        assert!(!mod_file.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
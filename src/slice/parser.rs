@@ -1,23 +1,26 @@
 use std::collections::{BTreeSet, BTreeMap};
-use std::path::Path;
-use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::cell::RefCell;
-use std::io::prelude::*;
 use escape::escape;
-use inflector::cases::{classcase, pascalcase, snakecase};
+use inflector::cases::{classcase, pascalcase, screamingsnakecase, snakecase};
 use pest::Parser;
-use pest::iterators::Pairs;
+use pest::Span;
+use pest::error::{ErrorVariant, InputLocation};
+use pest::iterators::{Pair, Pairs};
 use quote::__private::TokenStream;
 use quote::*;
 use crate::errors::*;
-use crate::slice::module::Module;
+use crate::slice::module::{lookup, Module};
 use crate::slice::enumeration::Enum;
 use crate::slice::structure::Struct;
 use crate::slice::interface::Interface;
 use crate::slice::function::Function;
 use crate::slice::exception::Exception;
 use crate::slice::class::Class;
+use crate::slice::constant::{Constant, ConstValue};
+use crate::slice::metadata::{self, Metadata};
+use crate::slice::preprocessor::{Preprocessor, SourceFile};
 use crate::slice::types::IceType;
 
 use super::{escape, function_argument::FunctionArgument, function_return::FunctionReturn, function_throws::FunctionThrows, struct_member::StructMember};
@@ -38,9 +41,16 @@ pub trait ParsedModule {
 
 impl ParsedModule for Module {
     fn parse(&mut self, rule: &mut Pairs<Rule>) -> Result<(), Box<dyn std::error::Error>> where Self: Sized {
-        let it = rule.next().ok_or(
+        let mut it = rule.next().ok_or(
             Box::new(ParsingError::new("No more items"))
         )?;
+        let mut metadata = Metadata::default();
+        if it.as_rule() == Rule::metadata {
+            metadata = Metadata::parse(it.into_inner())?;
+            it = rule.next().ok_or(
+                Box::new(ParsingError::new("No more items"))
+            )?;
+        }
         if it.as_rule() != Rule::keyword_module {
             return Err(Box::new(ParsingError::new(
                 &format!("Expected keyword module but found {:?}", it.as_rule())
@@ -55,12 +65,15 @@ impl ParsedModule for Module {
             )));
         }
         let name = it.as_str();
+        metadata.check(&format!("module {}", name), &[]);
         let module = match self.sub_modules.iter_mut().find(|f| f.name == name) {
             Some(module) => {
                 module
             }
             None => {
                 let mut new_module = Module::new(Rc::clone(&self.type_map));
+                new_module.interface_map = Rc::clone(&self.interface_map);
+                new_module.constant_map = Rc::clone(&self.constant_map);
                 new_module.name = String::from(name);
                 new_module.full_name = format!("{}::{}", self.full_name, new_module.name);
                 self.sub_modules.push(new_module);
@@ -75,57 +88,14 @@ impl ParsedModule for Module {
                 Rule::block_open => {},
                 Rule::any_block => {
                     for block in child.into_inner() {
-                        match block.as_rule() {
-                            Rule::module_block => {
-                                module.parse(&mut block.into_inner())?;
-                            },
-                            Rule::enum_block => {
-                                let enumeration = Enum::parse(block.into_inner())?;
-                                self.type_map.borrow_mut().insert(enumeration.id.to_string(), module.snake_name());
-                                module.add_enum(enumeration);
-                            },
-                            Rule::struct_block => {
-                                let structure = Struct::parse(block.into_inner())?;
-                                self.type_map.borrow_mut().insert(structure.id.to_string(), module.snake_name());
-                                module.add_struct(structure);
-                            },
-                            Rule::class_block => {
-                                let class = Class::parse(block.into_inner())?;
-                                self.type_map.borrow_mut().insert(class.id.to_string(), module.snake_name());
-                                module.add_class(class);
-                            },
-                            Rule::interface_block => {
-                                let interface = Interface::parse(block.into_inner())?;
-                                module.add_interface(interface);
-                            },
-                            Rule::exception_block => {
-                                let exception = Exception::parse(block.into_inner())?;
-                                self.type_map.borrow_mut().insert(exception.id.to_string(), module.snake_name());
-                                module.add_exception(exception);
-                            }
-                            _ => return Err(Box::new(ParsingError::new(
-                                &format!("Unexpected rule {:?}", block.as_rule())
-                            )))
-                        }
+                        let span = block.as_span();
+                        module.parse_definition(block).map_err(|error| locate(error, &span))?;
                     }
                 },
-                Rule::typedef => {
-                    let mut vartype = IceType::VoidType;
-                    let mut id = "";
-                    for item in child.into_inner() {
-                        match item.as_rule() {
-                            Rule::typename => { vartype = IceType::from(&escape::escape(item.as_str()))? },
-                            Rule::identifier => { id = item.as_str(); },
-                            Rule::typedef_end => {
-                                self.type_map.borrow_mut().insert(String::from(id), module.snake_name());
-                                module.add_typedef(id, vartype.clone());
-                            },
-                            _ => return Err(Box::new(ParsingError::new(
-                                &format!("Unexpected rule {:?}", item.as_rule())
-                            )))
-                        }
-                    }
-                }
+                Rule::const_block | Rule::typedef => {
+                    let span = child.as_span();
+                    module.parse_definition(child).map_err(|error| locate(error, &span))?;
+                },
                 Rule::block_close => {},
                 _ => return Err(Box::new(ParsingError::new(
                     &format!("Unexpected rule {:?}", child.as_rule())
@@ -136,11 +106,311 @@ impl ParsedModule for Module {
     }
 }
 
+impl Module {
+    /// Parses a definition of the module and registers its types.
+    fn parse_definition(&mut self, pair: Pair<Rule>) -> Result<(), Box<dyn std::error::Error>> {
+        match pair.as_rule() {
+            Rule::module_block => {
+                self.parse(&mut pair.into_inner())?;
+            },
+            Rule::enum_block => {
+                let enumeration = Enum::parse(pair.into_inner())?;
+                self.register_type(&enumeration.ice_id, &enumeration.id.to_string())?;
+                self.add_enum(enumeration);
+            },
+            Rule::struct_block => {
+                let mut structure = Struct::parse(pair.into_inner())?;
+                self.register_type(&structure.ice_id, &structure.id.to_string())?;
+                self.resolve_members(&mut structure.members, false, "struct", &structure.ice_id)?;
+                self.add_struct(structure);
+            },
+            Rule::class_block => {
+                let mut class = Class::parse(pair.into_inner())?;
+                self.register_type(&class.ice_id, &class.id.to_string())?;
+                if let Some(extends) = class.extends.as_mut() {
+                    self.resolve_type(extends)?;
+                }
+                self.resolve_members(&mut class.members, class.extends.is_some(), "class", &class.ice_id)?;
+                check_tags(class.members.iter().map(|member| &member.r#type), "class", &class.ice_id)?;
+                self.add_class(class);
+            },
+            Rule::interface_block => {
+                let mut interface = Interface::parse(pair.into_inner())?;
+                interface.type_id = format!("{}::{}", self.full_name, interface.ice_id);
+                interface.path = self.rust_path();
+                self.register_type(&interface.ice_id, &interface.id.to_string())?;
+                let mut extends = vec![];
+                for base in interface.extends.clone() {
+                    let base = lookup(&self.interface_map.borrow(), &self.full_name, &base).ok_or_else(|| Box::new(ParsingError::about(
+                        &format!("Interface {} extends unknown interface {}", interface.ice_id, base), &base
+                    )))?;
+                    extends.push(base.type_id.clone());
+                    interface.add_base(base);
+                }
+                interface.extends = extends;
+                for function in interface.functions.iter_mut() {
+                    self.resolve_type(&mut function.return_type.r#type)?;
+                    for throws in function.throws.types.iter_mut() {
+                        self.resolve_type(throws)?;
+                    }
+                    for argument in function.arguments.iter_mut() {
+                        self.resolve_type(&mut argument.r#type)?;
+                    }
+                    check_tags(
+                        function.arguments.iter().map(|argument| &argument.r#type).chain(std::iter::once(&function.return_type.r#type)),
+                        "operation",
+                        &function.ice_id
+                    )?;
+                }
+                self.interface_map.borrow_mut().insert(interface.type_id.clone(), interface.clone());
+                self.add_interface(interface);
+            },
+            Rule::exception_block => {
+                let mut exception = Exception::parse(pair.into_inner())?;
+                exception.type_id = format!("{}::{}", self.full_name, exception.ice_id);
+                self.register_type(&exception.ice_id, &exception.id.to_string())?;
+                if let Some(extends) = exception.extends.as_mut() {
+                    self.resolve_type(extends)?;
+                }
+                self.resolve_members(&mut exception.members, exception.extends.is_some(), "exception", &exception.ice_id)?;
+                self.add_exception(exception);
+            }
+            Rule::const_block => {
+                let mut constant = Constant::parse(pair.into_inner())?;
+                self.resolve_type(&mut constant.r#type)?;
+                constant.value = self.resolve_constant(constant.value);
+                let scoped_name = format!("{}::{}", self.full_name, constant.ice_id);
+                if self.constant_map.borrow().contains_key(&scoped_name) {
+                    return Err(Box::new(ParsingError::about(&format!("Duplicate definition of {}", scoped_name), &constant.ice_id)));
+                }
+                self.constant_map.borrow_mut().insert(scoped_name, constant.value.clone());
+                self.add_constant(constant);
+            },
+            Rule::typedef => {
+                let mut vartype = IceType::VoidType;
+                let mut id = "";
+                let mut metadata = Metadata::default();
+                for item in pair.into_inner() {
+                    match item.as_rule() {
+                        Rule::metadata => { metadata = Metadata::parse(item.into_inner())? },
+                        Rule::collection_type => { vartype = IceType::parse(item.into_inner())? },
+                        Rule::identifier => { id = item.as_str(); },
+                        Rule::typedef_end => {
+                            metadata.check(&format!("type {}", id), &["rust:type:"]);
+                            if let Some(collection) = metadata.value("rust:type:") {
+                                vartype = vartype.with_collection(collection)?;
+                            }
+                            self.resolve_type(&mut vartype)?;
+                            self.register_type(id, &pascalcase::to_pascal_case(id))?;
+                            self.add_typedef(id, vartype.clone());
+                        },
+                        _ => return Err(Box::new(ParsingError::new(
+                            &format!("Unexpected rule {:?}", item.as_rule())
+                        )))
+                    }
+                }
+            }
+            _ => return Err(Box::new(ParsingError::new(
+                &format!("Unexpected rule {:?}", pair.as_rule())
+            )))
+        }
+        Ok(())
+    }
+
+    /// Resolves the types and default values of the members of a struct,
+    /// class or exception. Default values are not supported in derived
+    /// types, as the `Default` of their base is not known.
+    fn resolve_members(&self, members: &mut [StructMember], derived: bool, kind: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        for member in members.iter_mut() {
+            self.resolve_type(&mut member.r#type)?;
+            if derived && member.default.is_some() {
+                return Err(Box::new(ParsingError::about(
+                    &format!("Default value of {} in derived {} {} is not supported", member.ice_id, kind, name), &member.ice_id
+                )));
+            }
+            member.default = member.default.take().map(|value| self.resolve_constant(value));
+        }
+        Ok(())
+    }
+
+    /// Replaces a reference to a constant by the value of the constant.
+    /// Other identifiers are kept as they name enum variants.
+    fn resolve_constant(&self, value: ConstValue) -> ConstValue {
+        match &value {
+            ConstValue::Identifier(name) => {
+                lookup(&self.constant_map.borrow(), &self.full_name, name).unwrap_or(value)
+            }
+            _ => value
+        }
+    }
+}
+
+/// Parses the tag of an optional member or parameter.
+fn parse_tag(tag: &Pair<Rule>) -> Result<u8, Box<dyn std::error::Error>> {
+    tag.as_str().parse().map_err(|_| -> Box<dyn std::error::Error> {
+        Box::new(ParsingError::at(
+            &format!("Invalid optional tag {}, tags must be between 0 and 255", tag.as_str()),
+            tag.as_span().start()
+        ))
+    })
+}
+
+/// Fails if two optional members or parameters of the definition `name`
+/// share a tag.
+fn check_tags<'a>(types: impl Iterator<Item = &'a IceType>, kind: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut tags = BTreeSet::new();
+    for r#type in types {
+        if let IceType::Optional(_, tag) = r#type {
+            if !tags.insert(*tag) {
+                return Err(Box::new(ParsingError::about(&format!("Duplicate optional tag {} in {} {}", tag, kind, name), name)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the byte offset of the first occurrence of the Slice name
+/// `name` in `text`, ignoring occurrences inside longer names.
+fn find_name(text: &str, name: &str) -> Option<usize> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == ':';
+    text.match_indices(name).map(|(index, _)| index).find(|index| {
+        !text[..*index].ends_with(is_name_char) && !text[index + name.len()..].starts_with(is_name_char)
+    })
+}
+
+/// Attaches the position of the definition `span` to an error that is not
+/// located yet. Errors about a subject point to its first occurrence in
+/// the definition.
+fn locate(error: Box<dyn std::error::Error>, span: &Span) -> Box<dyn std::error::Error> {
+    let (detail, position) = match error.downcast_ref::<ParsingError>() {
+        Some(parsing_error) if parsing_error.position().is_some() => return error,
+        Some(parsing_error) => {
+            let position = parsing_error.subject()
+                .and_then(|subject| find_name(span.as_str(), subject))
+                .map(|index| span.start() + index)
+                .unwrap_or_else(|| span.start());
+            (String::from(parsing_error.detail()), position)
+        }
+        None => (error.to_string(), span.start())
+    };
+    Box::new(ParsingError::at(&detail, position))
+}
+
+impl ParsedObject for Metadata {
+    fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
+        let mut directives = vec![];
+        for child in rule {
+            match child.as_rule() {
+                Rule::metadata_string => {
+                    let directive = child.into_inner().next().map(|directive| directive.as_str()).unwrap_or("");
+                    directives.push(String::from(directive));
+                }
+                _ => return Err(Box::new(ParsingError::new(
+                    &format!("Unexpected rule {:?}", child.as_rule())
+                )))
+            }
+        }
+        Ok(Metadata::new(directives))
+    }
+}
+
+impl ParsedObject for IceType {
+    fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
+        match rule.into_iter().next() {
+            Some(child) => match child.as_rule() {
+                Rule::collection_type => IceType::parse(child.into_inner()),
+                Rule::sequence_type => {
+                    let mut types = child.into_inner();
+                    let element_type = types.next().ok_or(Box::new(ParsingError::new("Did not find sequence element type")))?;
+                    Ok(IceType::SequenceType(Box::new(IceType::parse(element_type.into_inner())?)))
+                },
+                Rule::dictionary_type => {
+                    let mut types = child.into_inner();
+                    let key_type = types.next().ok_or(Box::new(ParsingError::new("Did not find dictionary key type")))?;
+                    let value_type = types.next().ok_or(Box::new(ParsingError::new("Did not find dictionary value type")))?;
+                    Ok(IceType::DictType(
+                        Box::new(IceType::parse(key_type.into_inner())?),
+                        Box::new(IceType::parse(value_type.into_inner())?)
+                    ))
+                },
+                Rule::proxy_type => {
+                    let name = child.into_inner().next().ok_or(Box::new(ParsingError::new("Did not find proxy type")))?;
+                    Ok(IceType::ProxyType(String::from(name.as_str())))
+                },
+                Rule::scoped_identifier => IceType::from(child.as_str()),
+                _ => Err(Box::new(ParsingError::new(
+                    &format!("Unexpected rule {:?}", child.as_rule())
+                )))
+            },
+            None => Err(Box::new(ParsingError::new("Did not find type")))
+        }
+    }
+}
+
+impl ParsedObject for ConstValue {
+    fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
+        match rule.into_iter().next() {
+            Some(child) => match child.as_rule() {
+                Rule::string_literal => {
+                    let content = child.into_inner().next().map(|content| content.as_str()).unwrap_or("");
+                    ConstValue::parse_string(content)
+                },
+                Rule::float_literal => ConstValue::parse_float(child.as_str()),
+                Rule::integer_literal => ConstValue::parse_integer(child.as_str()),
+                Rule::bool_literal => Ok(ConstValue::Bool(child.as_str() == "true")),
+                Rule::scoped_identifier => Ok(ConstValue::Identifier(String::from(child.as_str()))),
+                _ => Err(Box::new(ParsingError::new(
+                    &format!("Unexpected rule {:?}", child.as_rule())
+                )))
+            },
+            None => Err(Box::new(ParsingError::new("Did not find constant value")))
+        }
+    }
+}
+
+impl ParsedObject for Constant {
+    fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
+        let mut constant = Constant::empty();
+        let mut metadata = Metadata::default();
+        for child in rule {
+            match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
+                Rule::keyword_const => {},
+                Rule::typename => {
+                    constant.r#type = IceType::parse(child.into_inner())?;
+                },
+                Rule::identifier => {
+                    constant.ice_id = String::from(child.as_str());
+                    let id_str = format_ident!("{}", screamingsnakecase::to_screaming_snake_case(&constant.ice_id));
+                    constant.id = quote! { #id_str };
+                },
+                Rule::const_value => {
+                    constant.value = ConstValue::parse(child.into_inner())?;
+                },
+                _ => return Err(Box::new(ParsingError::new(
+                    &format!("Unexpected rule {:?}", child.as_rule())
+                )))
+            }
+        }
+        metadata.check(&format!("constant {}", constant.ice_id), &["rust:identifier:"]);
+        if let Some(id) = metadata.identifier()? {
+            constant.id = id;
+        }
+        Ok(constant)
+    }
+}
+
 impl ParsedObject for Enum {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut enumeration = Enum::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_enum => {},
                 Rule::identifier => { 
                     enumeration.ice_id = String::from(child.as_str());
@@ -183,6 +453,8 @@ impl ParsedObject for Enum {
             }
         }
 
+        metadata.check(&format!("enum {}", enumeration.ice_id), &["rust:derive:"]);
+        enumeration.derives = metadata.derives()?;
         Ok(enumeration)
     }
 }
@@ -192,14 +464,18 @@ impl ParsedObject for StructMember {
         let mut member = StructMember::empty();
         let mut optional = false;
         let mut optional_tag = 0;
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_optional => {
                     optional = true;
                     for line in child.into_inner() {
                         match line.as_rule() {
                             Rule::optional_tag => {
-                                optional_tag = line.as_str().parse()?;
+                                optional_tag = parse_tag(&line)?;
                             }
                             _ => return Err(Box::new(ParsingError::new(
                                 &format!("Unexpected rule {:?}", line.as_rule())
@@ -209,9 +485,9 @@ impl ParsedObject for StructMember {
                 },
                 Rule::typename => {
                     if optional {
-                        member.r#type = IceType::Optional(Box::new(IceType::from(child.as_str())?), optional_tag);
+                        member.r#type = IceType::Optional(Box::new(IceType::parse(child.into_inner())?), optional_tag);
                     } else {
-                        member.r#type = IceType::from(child.as_str())?;
+                        member.r#type = IceType::parse(child.into_inner())?;
                     }
                 },
                 Rule::identifier => {
@@ -220,7 +496,16 @@ impl ParsedObject for StructMember {
                     member.id = quote! { #id_str };
                 },
                 Rule::struct_line_default | Rule::class_line_default => {
-                    // TODO
+                    for line in child.into_inner() {
+                        match line.as_rule() {
+                            Rule::const_value => {
+                                member.default = Some(ConstValue::parse(line.into_inner())?);
+                            }
+                            _ => return Err(Box::new(ParsingError::new(
+                                &format!("Unexpected rule {:?}", line.as_rule())
+                            )))
+                        }
+                    }
                 }
                 Rule::struct_line_end => {
                 },
@@ -231,6 +516,13 @@ impl ParsedObject for StructMember {
                 )))
             }
         }
+        metadata.check(&format!("member {}", member.ice_id), &["rust:type:", "rust:identifier:"]);
+        if let Some(collection) = metadata.value("rust:type:") {
+            member.r#type = member.r#type.with_collection(collection)?;
+        }
+        if let Some(id) = metadata.identifier()? {
+            member.id = id;
+        }
         Ok(member)
     }
 }
@@ -238,8 +530,12 @@ impl ParsedObject for StructMember {
 impl ParsedObject for Struct {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut structure = Struct::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_struct => {},
                 Rule::identifier => { 
                     structure.ice_id = String::from(child.as_str());
@@ -259,6 +555,8 @@ impl ParsedObject for Struct {
             }
         }
 
+        metadata.check(&format!("struct {}", structure.ice_id), &["rust:derive:"]);
+        structure.derives = metadata.derives()?;
         Ok(structure)
     }
 }
@@ -266,8 +564,12 @@ impl ParsedObject for Struct {
 impl ParsedObject for Class {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut class = Class::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_class => {},
                 Rule::identifier => { 
                     class.ice_id = String::from(child.as_str());
@@ -278,7 +580,7 @@ impl ParsedObject for Class {
                     for line in child.into_inner() {
                         match line.as_rule() {
                             Rule::keyword_extends => { },
-                            Rule::identifier => {
+                            Rule::scoped_identifier => {
                                 class.extends = Some(IceType::from(line.as_str())?);
                             },
                             _ => return Err(Box::new(ParsingError::new(
@@ -299,6 +601,8 @@ impl ParsedObject for Class {
             }
         }
 
+        metadata.check(&format!("class {}", class.ice_id), &["rust:derive:"]);
+        class.derives = metadata.derives()?;
         Ok(class)
     }
 }
@@ -306,14 +610,31 @@ impl ParsedObject for Class {
 impl ParsedObject for Interface {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut interface = Interface::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_interface => {},
                 Rule::identifier => { 
                     interface.ice_id = String::from(child.as_str());
                     let id_str = format_ident!("{}", classcase::to_class_case(&interface.ice_id));
                     interface.id = quote! { #id_str };
                 },
+                Rule::interface_extends => {
+                    for line in child.into_inner() {
+                        match line.as_rule() {
+                            Rule::keyword_extends => { },
+                            Rule::scoped_identifier => {
+                                interface.extends.push(String::from(line.as_str()));
+                            },
+                            _ => return Err(Box::new(ParsingError::new(
+                                &format!("Unexpected rule {:?}", line.as_rule())
+                            )))
+                        }
+                    }
+                },
                 Rule::block_open => {},
                 Rule::function => {
                     interface.add_function(Function::parse(child.into_inner())?);
@@ -324,6 +645,13 @@ impl ParsedObject for Interface {
                 )))
             }
         }
+        metadata.check(&format!("interface {}", interface.ice_id), &["amd", "marshaled-result"]);
+        if metadata.has("amd") {
+            // `["amd"]` on an interface applies to all of its operations
+            for function in &mut interface.functions {
+                function.set_amd();
+            }
+        }
         Ok(interface)
     }
 }
@@ -331,14 +659,15 @@ impl ParsedObject for Interface {
 impl ParsedObject for Function {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut function = Function::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                }
                 Rule::keyword_idempotent => {
                     function.set_idempotent();
                 }
-                Rule::fn_return_proxy => {
-                    function.return_type.set_proxy();
-                }
                 Rule::fn_return => {
                     function.return_type = FunctionReturn::parse(child.into_inner())?;
                 },
@@ -371,24 +700,33 @@ impl ParsedObject for Function {
                 )))
             }
         }
+        metadata.check(&format!("operation {}", function.ice_id), &["amd", "marshaled-result", "rust:identifier:"]);
+        if metadata.has("amd") {
+            function.set_amd();
+        }
+        if let Some(id) = metadata.identifier()? {
+            function.id = id;
+        }
         Ok(function)
     }
 }
 
 impl ParsedObject for FunctionThrows {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
+        let mut types = vec![];
         for child in rule {
             match child.as_rule() {
                 Rule::keyword_throws => {}
-                Rule::identifier => {
-                    return Ok(FunctionThrows::new(IceType::from(child.as_str())?));
+                Rule::scoped_identifier => {
+                    types.push(IceType::from(child.as_str())?);
                 }
                 _ => { }
             }
         }
-        return Err(Box::new(ParsingError::new(
-            &format!("Did not find throw identifier")
-        )))
+        if types.is_empty() {
+            return Err(Box::new(ParsingError::new("Did not find throw identifier")));
+        }
+        Ok(FunctionThrows::new(types))
     }
 }
 
@@ -408,13 +746,13 @@ impl ParsedObject for FunctionReturn {
                             &format!("Expected keyword optional_tag but found {:?}", tag.as_rule())
                         )));
                     }
-                    optional_tag = tag.as_str().parse()?;
+                    optional_tag = parse_tag(&tag)?;
                 }
-                Rule::identifier => {
+                Rule::typename => {
                     if optional {
-                        return_type = IceType::Optional(Box::new(IceType::from(child.as_str())?), optional_tag);
+                        return_type = IceType::Optional(Box::new(IceType::parse(child.into_inner())?), optional_tag);
                     } else {
-                        return_type = IceType::from(child.as_str())?;
+                        return_type = IceType::parse(child.into_inner())?;
                     }
                 }
                 _ => return Err(Box::new(ParsingError::new(
@@ -438,9 +776,9 @@ impl ParsedObject for FunctionArgument {
             match child.as_rule() {
                 Rule::typename => {
                     if optional {
-                        typename = IceType::Optional(Box::new(IceType::from(child.as_str())?), optional_tag);
+                        typename = IceType::Optional(Box::new(IceType::parse(child.into_inner())?), optional_tag);
                     } else {
-                        typename = IceType::from(child.as_str())?;
+                        typename = IceType::parse(child.into_inner())?;
                     }
                 },
                 Rule::identifier => {
@@ -457,7 +795,7 @@ impl ParsedObject for FunctionArgument {
                             &format!("Expected keyword optional_tag but found {:?}", tag.as_rule())
                         )));
                     }
-                    optional_tag = tag.as_str().parse()?;
+                    optional_tag = parse_tag(&tag)?;
                 }
                 _ => return Err(Box::new(ParsingError::new(
                     &format!("Unexpected rule {:?}", child.as_rule())
@@ -471,8 +809,12 @@ impl ParsedObject for FunctionArgument {
 impl ParsedObject for Exception {
     fn parse(rule: Pairs<Rule>) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized {
         let mut exception = Exception::empty();
+        let mut metadata = Metadata::default();
         for child in rule {
             match child.as_rule() {
+                Rule::metadata => {
+                    metadata = Metadata::parse(child.into_inner())?;
+                },
                 Rule::keyword_exception => {},
                 Rule::identifier => { 
                     exception.ice_id = String::from(child.as_str());
@@ -484,7 +826,7 @@ impl ParsedObject for Exception {
                     for line in child.into_inner() {
                         match line.as_rule() {
                             Rule::keyword_extends => { },
-                            Rule::identifier => {
+                            Rule::scoped_identifier => {
                                 exception.extends = Some(IceType::from(line.as_str())?);
                             },
                             _ => return Err(Box::new(ParsingError::new(
@@ -504,43 +846,33 @@ impl ParsedObject for Exception {
             }
         }
 
+        metadata.check(&format!("exception {}", exception.ice_id), &["rust:derive:"]);
+        exception.derives = metadata.derives()?;
         Ok(exception)
     }
 }
 
 impl Module {
-    fn parse_file(&mut self, file: &mut File, include_dir: &Path, parsed_files: &mut BTreeSet<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let mut content = String::new();
-        file.read_to_string(&mut content)?;
+    /// Parses a preprocessed Slice file. Errors are returned as a
+    /// `SliceError` that points into the file.
+    fn parse_file(&mut self, file: &SourceFile) -> Result<(), Box<dyn std::error::Error>> {
+        let result = self.parse_content(&file.content)
+            .map_err(|error| SliceError::from_error(&file.path.to_string_lossy(), &file.content, error));
+        for warning in metadata::take_warnings() {
+            self.warnings.push(format!("{}: {}", file.path.display(), warning));
+        }
+        result
+    }
 
-        let pairs = IceParser::parse(Rule::ice, &content).unwrap();
+    fn parse_content(&mut self, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let pairs = IceParser::parse(Rule::ice, content).map_err(syntax_error)?;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::ice => {
                     for child in pair.into_inner() {
                         match child.as_rule() {
-                            Rule::file_include => {
-                                for item in child.into_inner() {
-                                    match item.as_rule() {
-                                        Rule::keyword_include => {},
-                                        Rule::identifier => {
-                                            let include = include_dir.join(format!("{}.ice", item.as_str()));
-                                            let include_str = String::from(include_dir.join(format!("{}.ice", item.as_str())).to_str().unwrap());
-                                            println!("  parsing include {} ... ", include_str);
-                                            if parsed_files.contains(&include_str) {
-                                                println!("  skip file!");
-                                            } else {
-                                                parsed_files.insert(include_str);
-                                                let mut include_file = File::open(include)?;
-                                                self.parse_file(&mut include_file, include_dir, parsed_files)?;
-                                                println!("  finished include");
-                                            }
-                                        }
-                                        _ => return Err(Box::new(ParsingError::new(
-                                            &format!("Unexpected rule {:?}", item.as_rule())
-                                        )))
-                                    }
-                                }
+                            Rule::global_metadata => {
+                                Metadata::parse(child.into_inner())?.check("file", &[]);
                             }
                             Rule::module_block => {
                                 self.parse(&mut child.into_inner())?;
@@ -564,28 +896,67 @@ impl Module {
     }
 }
 
+/// Converts a pest error into a located `ParsingError`.
+fn syntax_error(error: pest::error::Error<Rule>) -> Box<dyn std::error::Error> {
+    let position = match error.location {
+        InputLocation::Pos(position) => position,
+        InputLocation::Span((start, _)) => start
+    };
+    let rule_names = |rules: &[Rule]| rules.iter()
+        .map(|rule| format!("{:?}", rule).replace('_', " "))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let detail = match &error.variant {
+        ErrorVariant::ParsingError { positives, .. } if !positives.is_empty() => {
+            format!("Syntax error, expected {}", rule_names(positives))
+        },
+        ErrorVariant::ParsingError { negatives, .. } if !negatives.is_empty() => {
+            format!("Syntax error, unexpected {}", rule_names(negatives))
+        },
+        ErrorVariant::ParsingError { .. } => String::from("Syntax error"),
+        ErrorVariant::CustomError { message } => format!("Syntax error, {}", message)
+    };
+    Box::new(ParsingError::at(&detail, position))
+}
+
+/// Parses the given Slice files and their includes into a root module.
+/// Syntax and semantic errors are returned as `SliceError`.
 pub fn parse_ice_files(ice_files: &Vec<String>, include_dir: &str) -> Result<Module, Box<dyn std::error::Error>> {
-    let mut parsed_files = BTreeSet::new();
-    let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+    parse_ice_files_with_include_dirs(ice_files, &[String::from(include_dir)])
+}
+
+/// Parses the given Slice files like `parse_ice_files`. Includes are
+/// searched in all `include_dirs`, in the given order.
+pub fn parse_ice_files_with_include_dirs(ice_files: &[String], include_dirs: &[String]) -> Result<Module, Box<dyn std::error::Error>> {
+    let (root, _) = parse_ice_files_with_dependencies(ice_files, include_dirs)?;
+    Ok(root)
+}
+
+/// Parses the given Slice files like `parse_ice_files_with_include_dirs`
+/// and also returns the paths of all parsed files, including the
+/// transitively included ones.
+pub fn parse_ice_files_with_dependencies(ice_files: &[String], include_dirs: &[String]) -> Result<(Module, Vec<PathBuf>), Box<dyn std::error::Error>> {
+    let mut preprocessor = Preprocessor::new(include_dirs.iter().map(PathBuf::from).collect());
     for item in ice_files {
-        println!("parsing {} ... ", item);
-        if parsed_files.contains(item) {
-            println!("skip file!");
-        } else {
-            parsed_files.insert(item.clone());
-            let mut file = File::open(Path::new(&item))?;
-            root.parse_file(&mut file, Path::new(include_dir), &mut parsed_files)?;
-            println!("finished parsing!");
-        }
+        preprocessor.process(Path::new(item))?;
     }
 
-    Ok(root)
+    let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+    for file in preprocessor.files() {
+        println!("parsing {} ... ", file.path.display());
+        root.parse_file(file)?;
+    }
+    println!("finished parsing!");
+
+    let dependencies = preprocessor.files().iter().map(|file| file.path.clone()).collect();
+    Ok((root, dependencies))
 }
 
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::slice::preprocessor::SLICE_DIR;
 
     #[test]
     fn test_function() {
@@ -596,7 +967,14 @@ mod test {
         assert!(IceParser::parse(Rule::function, "long test(long radius, out long area);").is_ok());
         assert!(IceParser::parse(Rule::function, "long test(out long area);").is_ok());
         assert!(IceParser::parse(Rule::function, "long test(out long area) throws exception;").is_ok());
+        assert!(IceParser::parse(Rule::function, "void test() throws First, ::Demo::Second, Third;").is_ok());
 
+        let function = Function::parse(IceParser::parse(Rule::function, "void test() throws First, Second;").unwrap().next().unwrap().into_inner()).unwrap();
+        assert_eq!("(First , Second)", function.throws.token().to_string());
+        let function = Function::parse(IceParser::parse(Rule::function, "void test() throws First;").unwrap().next().unwrap().into_inner()).unwrap();
+        assert_eq!("First", function.throws.token().to_string());
+
+        assert!(IceParser::parse(Rule::function, "void test() throws First,;").is_err());
         assert!(IceParser::parse(Rule::function, "123abc test();").is_err());
         assert!(IceParser::parse(Rule::function, "void 123abc();").is_err());
         assert!(IceParser::parse(Rule::function, "void test(123abc width);").is_err());
@@ -607,9 +985,60 @@ mod test {
         assert!(IceParser::parse(Rule::function, "void test(out long result, long input);").is_err());
     }
 
+    #[test]
+    fn test_function_metadata() {
+        assert!(IceParser::parse(Rule::function, "[\"amd\"] void test();").is_ok());
+        assert!(IceParser::parse(Rule::function, "[\"amd\"] idempotent long test(long width);").is_ok());
+        assert!(IceParser::parse(Rule::function, "[\"amd\", \"cpp:const\"] void test();").is_ok());
+
+        let function = Function::parse(IceParser::parse(Rule::function, "[\"amd\"] long test(long width);").unwrap().next().unwrap().into_inner()).unwrap();
+        assert!(function.is_amd());
+        let function = Function::parse(IceParser::parse(Rule::function, "long test(long width);").unwrap().next().unwrap().into_inner()).unwrap();
+        assert!(!function.is_amd());
+        let function = Function::parse(IceParser::parse(Rule::function, "[\"amd\"] long test(long width, out long area, out string unit);").unwrap().next().unwrap().into_inner()).unwrap();
+        assert!(function.is_amd());
+        let decl = function.generate_server_decl().unwrap().to_string();
+        assert!(decl.contains("width : i64 , current : & Current"));
+        assert!(decl.contains("AmdFuture < (i64 , i64 , String) >"));
+        let handler = function.generate_deferred_handler().unwrap().to_string();
+        assert!(handler.contains("let (result , area , unit) = future . await ?"));
+
+        let interface = Interface::parse(IceParser::parse(Rule::interface_block, "[\"amd\"] interface Test { void a(); long b(long width); }").unwrap().next().unwrap().into_inner()).unwrap();
+        assert!(interface.functions.iter().all(|function| function.is_amd()));
+
+        // synchronous operations decode only in parameters and marshal out parameters first
+        let function = Function::parse(IceParser::parse(Rule::function, "long op(long a, out long b);").unwrap().next().unwrap().into_inner()).unwrap();
+        let handler = function.generate_server_handler().unwrap().to_string();
+        assert!(handler.contains("let a = < i64 > :: from_bytes"));
+        assert!(!handler.contains("let mut b = < i64 > :: from_bytes"));
+        assert!(handler.contains("let mut b = Default :: default ()"));
+        let output = handler.find("result . extend (b . to_bytes () ?)").expect("Out parameter is not encoded");
+        assert!(output < handler.find("result . extend (wrapped_result . to_bytes () ?)").expect("Result is not encoded"));
+
+        assert!(IceParser::parse(Rule::function, "[amd] void test();").is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        assert!(IceParser::parse(Rule::ice, "\n[[\"cpp:header-ext:h\"]]\nmodule Test { }").is_ok());
+        assert!(IceParser::parse(Rule::module_block, "[\"java:package:demo\"] module Test { }").is_ok());
+        assert!(IceParser::parse(Rule::module_block, "module Test { [\"rust:derive:Hash\"] enum Test2 { First } }").is_ok());
+        assert!(IceParser::parse(Rule::module_block, "module Test { [\"rust:type:VecDeque\"] sequence<long> Numbers; }").is_ok());
+        assert!(IceParser::parse(Rule::struct_block, "struct Test { [\"rust:identifier:kind\"] long type; }").is_ok());
+        assert!(IceParser::parse(Rule::class_block, "[\"rust:derive:Hash\"] class Test { [\"cpp:type:wstring\"] optional(1) string name; }").is_ok());
+
+        assert!(IceParser::parse(Rule::struct_block, "struct Test { [rust:identifier:kind] long type; }").is_err());
+        assert!(IceParser::parse(Rule::struct_block, "struct Test { [\"amd\" long width; }").is_err());
+
+        let member = StructMember::parse(IceParser::parse(Rule::struct_line, "[\"rust:identifier:kind\", \"rust:type:BTreeMap\"] dictionary<int, string> type;").unwrap().next().unwrap().into_inner()).unwrap();
+        assert_eq!("kind", member.id.to_string());
+        assert!(matches!(member.r#type, IceType::Collection(_, _)));
+        assert!(StructMember::parse(IceParser::parse(Rule::struct_line, "[\"rust:type:VecDeque\"] long width;").unwrap().next().unwrap().into_inner()).is_err());
+    }
+
     #[test]
     fn test_module_block() {
-        assert!(IceParser::parse(Rule::ice, "#pragma once\nmodule Test { }").is_ok());
+        assert!(IceParser::parse(Rule::ice, "\nmodule Test { }").is_ok());
 
         assert!(IceParser::parse(Rule::module_block, "module Test { }").is_ok());
         assert!(IceParser::parse(Rule::module_block, "module Test { module Test2 {} }").is_ok());
@@ -661,11 +1090,45 @@ mod test {
         assert!(IceParser::parse(Rule::struct_block, "struct Test { struct Test2 { } }").is_err());
     }
 
+    #[test]
+    fn test_const_block() {
+        assert!(IceParser::parse(Rule::const_block, "const int MaxItems = 100;").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const long Mask = 0xFF;").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const double Pi = 3.14;").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const float Half = .5f;").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const string Name = \"x\\\"y\";").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const bool Flag = false;").is_ok());
+        assert!(IceParser::parse(Rule::const_block, "const Color Favourite = ::Demo::Red;").is_ok());
+        assert!(IceParser::parse(Rule::module_block, "module Test { const int MaxItems = 100; struct Item { int count = MaxItems; } }").is_ok());
+
+        assert!(IceParser::parse(Rule::const_block, "const int MaxItems;").is_err());
+        assert!(IceParser::parse(Rule::const_block, "const int MaxItems = 100").is_err());
+        assert!(IceParser::parse(Rule::const_block, "const int 12Max = 100;").is_err());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Test { const int MaxItems = 0x10; const int Alias = MaxItems; }").unwrap();
+        root.parse(&mut pairs.next().unwrap().into_inner()).unwrap();
+        assert_eq!(Some(&ConstValue::Integer(16)), root.constant_map.borrow().get("::Test::Alias"));
+    }
+
+    #[test]
+    fn test_metadata_warnings() {
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let file = SourceFile {
+            path: PathBuf::from("Test.ice"),
+            content: String::from("module Test { interface Calc { [\"marshaled-result\", \"cpp:const\"] long add(long a); [\"unknown\"] void reset(); } }")
+        };
+        root.parse_file(&file).unwrap();
+        assert_eq!(vec![String::from("Test.ice: ignoring unknown metadata \"unknown\" on operation reset")], root.warnings);
+    }
+
     #[test]
     fn test_interface_block() {
         assert!(IceParser::parse(Rule::interface_block, "interface Test { void test(); }").is_ok());
         assert!(IceParser::parse(Rule::interface_block, "interface Test { void test(long width); }").is_ok());
         assert!(IceParser::parse(Rule::interface_block, "interface Test { void test(long width, long height); }").is_ok());
+        assert!(IceParser::parse(Rule::interface_block, "interface Test extends Base { void test(); }").is_ok());
+        assert!(IceParser::parse(Rule::interface_block, "interface Test extends Left, Right { }").is_ok());
 
         assert!(IceParser::parse(Rule::interface_block, "interface Test { void 12test(); }").is_err());
         assert!(IceParser::parse(Rule::interface_block, "interface Test { test(); }").is_err());
@@ -673,5 +1136,179 @@ mod test {
         assert!(IceParser::parse(Rule::interface_block, "enum Test {}").is_err());
         assert!(IceParser::parse(Rule::interface_block, "struct Test {}").is_err());
         assert!(IceParser::parse(Rule::interface_block, "module Test {}").is_err());
+        assert!(IceParser::parse(Rule::interface_block, "interface Test extends { }").is_err());
+        assert!(IceParser::parse(Rule::interface_block, "interface Test extends Left, { }").is_err());
+    }
+
+    #[test]
+    fn test_interface_inheritance() {
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Test { interface Root { void a(); } interface Left extends Root { void b(); } interface Right extends Root { } interface Test extends Left, Right { void c(); } }").unwrap();
+        root.parse(&mut pairs.next().unwrap().into_inner()).unwrap();
+
+        let interface = root.interface_map.borrow().get("::Test::Test").cloned().unwrap();
+        let bases = interface.bases.iter().map(|base| base.type_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(vec!["::Test::Root", "::Test::Left", "::Test::Right"], bases);
+        assert_eq!(3, interface.all_functions().len());
+        assert_eq!(5, interface.type_ids("::Test").len());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Test { interface Test extends Unknown { } }").unwrap();
+        assert!(root.parse(&mut pairs.next().unwrap().into_inner()).is_err());
+    }
+
+    #[test]
+    fn test_scoped_names() {
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { struct Item { int id; } module Sub { struct Item { string name; } struct Pair { Item inner; ::Demo::Item outer; Demo::Sub::Item same; } } interface Store { Sub::Item get(Item item); } }").unwrap();
+        root.parse(&mut pairs.next().unwrap().into_inner()).unwrap();
+
+        let type_map = root.type_map.borrow();
+        assert_eq!(Some(&String::from("slice_root::demo::Item")), type_map.get("::Demo::Item"));
+        assert_eq!(Some(&String::from("slice_root::demo::sub::Item")), type_map.get("::Demo::Sub::Item"));
+        assert_eq!(Some(String::from("slice_root::demo::sub::Item")), lookup(&type_map, "::Demo::Sub", "Item"));
+        assert_eq!(Some(String::from("slice_root::demo::Item")), lookup(&type_map, "::Demo::Sub", "::Demo::Item"));
+        assert_eq!(Some(String::from("slice_root::demo::Item")), lookup(&type_map, "::Demo", "Item"));
+        assert_eq!(None, lookup(&type_map, "::Other", "Item"));
+
+        let sub = &root.sub_modules[0].sub_modules[0];
+        assert_eq!("slice_root::demo::sub", sub.rust_path());
+        let interface = root.interface_map.borrow().get("::Demo::Store").cloned().unwrap();
+        let function = &interface.functions[0];
+        assert_eq!("slice_root :: demo :: sub :: Item", function.return_type.token().to_string());
+        assert_eq!("slice_root :: demo :: Item", function.arguments[0].r#type.token().to_string());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { struct Pair { Unknown first; } }").unwrap();
+        assert!(root.parse(&mut pairs.next().unwrap().into_inner()).is_err());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { module Sub { struct Item { int id; } } struct Pair { Item first; } }").unwrap();
+        assert!(root.parse(&mut pairs.next().unwrap().into_inner()).is_err());
+    }
+
+    #[test]
+    fn test_nested_types() {
+        assert!(IceParser::parse(Rule::typename, "sequence<dictionary<string,int>>").is_ok());
+        assert!(IceParser::parse(Rule::typename, "dictionary<string, sequence<dictionary<int,string>>>").is_ok());
+        assert!(IceParser::parse(Rule::typedef, "sequence<::Demo::Item> Items;").is_ok());
+        assert!(IceParser::parse(Rule::typedef, "dictionary<string, sequence<int>> Groups;").is_ok());
+        assert!(IceParser::parse(Rule::typedef, "int Number;").is_err());
+        assert!(IceParser::parse(Rule::typedef, "dictionary<string> Names;").is_err());
+
+        let mut pairs = IceParser::parse(Rule::typename, "dictionary<string, sequence<dictionary<int,string>>>").unwrap();
+        let vartype = IceType::parse(pairs.next().unwrap().into_inner()).unwrap();
+        assert_eq!("HashMap < String , Vec < HashMap < i32 , String > > >", vartype.token().to_string());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { struct Item { int id; } sequence<Item> Items; dictionary<string, sequence<Items>> Groups; struct Store { Groups groups; sequence<dictionary<string,int>> counts; } }").unwrap();
+        root.parse(&mut pairs.next().unwrap().into_inner()).unwrap();
+        assert_eq!(Some(&String::from("slice_root::demo::Groups")), root.type_map.borrow().get("::Demo::Groups"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_proxy_types() {
+        assert!(IceParser::parse(Rule::function, "Object* find(Hello* hello);").is_ok());
+        assert!(IceParser::parse(Rule::typedef, "sequence<Object*> Proxies;").is_ok());
+
+        let mut pairs = IceParser::parse(Rule::typename, "sequence<Hello*>").unwrap();
+        let vartype = IceType::parse(pairs.next().unwrap().into_inner()).unwrap();
+        assert_eq!("Vec < ice_rs :: proxy :: TypedProxy < HelloPrx > >", vartype.token().to_string());
+        let mut pairs = IceParser::parse(Rule::typename, "::Ice::Object*").unwrap();
+        let vartype = IceType::parse(pairs.next().unwrap().into_inner()).unwrap();
+        assert_eq!("ice_rs :: proxy :: TypedProxy < ice_rs :: proxy :: Proxy >", vartype.token().to_string());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { interface Hello { void sayHello(); } sequence<::Ice::Object*> Proxies; struct Entry { Hello* hello; } }").unwrap();
+        assert!(root.parse(&mut pairs.next().unwrap().into_inner()).is_ok());
+
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { struct Entry { Hello* hello; } }").unwrap();
+        assert!(root.parse(&mut pairs.next().unwrap().into_inner()).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "rustfmt"))]
+    fn test_bundled_slice() {
+        let slice_dir = Path::new(SLICE_DIR);
+        let mut ice_files = vec![];
+        for module in ["Ice", "IceGrid", "Glacier2"].iter() {
+            for entry in std::fs::read_dir(slice_dir.join(module)).unwrap() {
+                ice_files.push(String::from(entry.unwrap().path().to_str().unwrap()));
+            }
+        }
+        ice_files.sort();
+        let root = parse_ice_files_with_include_dirs(&ice_files, &[]).unwrap();
+
+        // the bindings in src/bindings must be regenerated when the
+        // bundled Slice files or the generator change
+        let dir = std::env::temp_dir().join(format!("ice-rs-bindings-{}", std::process::id()));
+        root.generate(&dir).unwrap();
+        let bindings = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("bindings");
+        for module in ["mod.rs", "ice/mod.rs", "ice_grid/mod.rs", "glacier_2/mod.rs"].iter() {
+            let generated = std::fs::read_to_string(dir.join(module)).unwrap();
+            let bundled = std::fs::read_to_string(bindings.join(module)).unwrap();
+            assert!(generated == bundled, "src/bindings/{} is out of date", module);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_member_defaults() {
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let mut pairs = IceParser::parse(Rule::module_block, "module Demo { const int Max = 10; class Item { int count = Max; string name = \"item\"; optional(1) bool flag = true; } exception Failed { int code = Max; string reason; } }").unwrap();
+        root.parse(&mut pairs.next().unwrap().into_inner()).unwrap();
+        let dest = std::env::temp_dir().join(format!("ice-rs-defaults-{}.rs", std::process::id()));
+        root.generate_file(&dest).unwrap();
+        let code = std::fs::read_to_string(&dest).unwrap();
+        std::fs::remove_file(&dest).unwrap();
+        assert!(code.contains("impl Default for Item"));
+        assert!(code.contains("count: 10"));
+        assert!(code.contains("name: String::from(\"item\")"));
+        assert!(code.contains("flag: Some(true)"));
+        assert!(code.contains("impl Default for Failed"));
+        assert!(code.contains("code: 10"));
+        assert!(code.contains("reason: Default::default()"));
+
+        let error = parse_error("module Demo\n{\n    class Item { int count; }\n    class Special extends Item\n    {\n        int extra = 1;\n    }\n}\n");
+        assert_eq!((6, 13), (error.line, error.column));
+        assert_eq!("Default value of extra in derived class Special is not supported", error.message);
+    }
+
+    fn parse_error(content: &str) -> SliceError {
+        let mut root = Module::new(Rc::new(RefCell::new(BTreeMap::new())));
+        let file = SourceFile {
+            path: PathBuf::from("test.ice"),
+            content: String::from(content)
+        };
+        let error = root.parse_file(&file).err().unwrap();
+        *error.downcast::<SliceError>().unwrap()
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let error = parse_error("module Demo\n{\n    struct Item\n    {\n        int id\n    }\n}\n");
+        assert_eq!((5, 15), (error.line, error.column));
+        assert!(error.message.starts_with("Syntax error"));
+
+        let error = parse_error("module Demo\n{\n    struct Pair\n    {\n        Items first;\n        Item second;\n    }\n}\n");
+        assert_eq!((5, 9), (error.line, error.column));
+        assert_eq!("        Items first;", error.snippet);
+        assert_eq!("Unknown type Items in module ::Demo", error.message);
+
+        let error = parse_error("module Demo\n{\n    struct Item { int id; }\n    module Sub { }\n    enum Item { A }\n}\n");
+        assert_eq!((5, 10), (error.line, error.column));
+        assert_eq!("Duplicate definition of ::Demo::Item", error.message);
+
+        let error = parse_error("module Demo\n{\n    class Item\n    {\n        optional(256) int id;\n    }\n}\n");
+        assert_eq!((5, 18), (error.line, error.column));
+
+        let error = parse_error("module Demo\n{\n    interface Api\n    {\n        void op(optional(1) int a, optional(1) int b);\n    }\n}\n");
+        assert_eq!((5, 14), (error.line, error.column));
+        assert_eq!("Duplicate optional tag 1 in operation op", error.message);
+        assert_eq!(
+            "error: Duplicate optional tag 1 in operation op\n --> test.ice:5:14\n  |\n5 |         void op(optional(1) int a, optional(1) int b);\n  |              ^",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::errors::{ParsingError, SliceError};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+
+/// The directory of the Slice definitions bundled with ice-rs, like
+/// `Ice/Locator.ice`. It is searched for includes after all other
+/// include directories.
+pub const SLICE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/slice");
+
+/// A preprocessed Slice file. Directives and lines of inactive
+/// conditional blocks are blanked, so lines and columns still match the
+/// original file.
+#[derive(Clone, Debug)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: String
+}
+
+/// The state of an `#if` block.
+struct Condition {
+    /// Whether the lines of the current branch are used.
+    active: bool,
+    /// Whether any branch of the block has been used so far.
+    taken: bool,
+    /// Whether the enclosing block is active.
+    parent_active: bool,
+    position: usize
+}
+
+/// Preprocesses Slice files like the preprocessor of the ZeroC Slice
+/// compilers. It resolves `#include "..."` and `#include <...>`,
+/// evaluates `#ifdef`, `#ifndef`, `#if`, `#elif`, `#else` and `#endif`
+/// and keeps track of `#define` and `#undef`.
+///
+/// Every file is used at most once, as if it started with
+/// `#pragma once`. Macros defined by a file are visible in the files it
+/// includes and in the files included after it, but not in the files
+/// processed by later calls to `process`.
+pub struct Preprocessor {
+    include_dirs: Vec<PathBuf>,
+    predefined: BTreeSet<String>,
+    defines: BTreeSet<String>,
+    visited: BTreeSet<PathBuf>,
+    files: Vec<SourceFile>
+}
+
+impl Preprocessor {
+    pub fn new(mut include_dirs: Vec<PathBuf>) -> Preprocessor {
+        include_dirs.push(PathBuf::from(SLICE_DIR));
+        let mut predefined = BTreeSet::new();
+        predefined.insert(String::from("__SLICE2RS__"));
+        Preprocessor {
+            include_dirs,
+            defines: predefined.clone(),
+            predefined,
+            visited: BTreeSet::new(),
+            files: Vec::new()
+        }
+    }
+
+    /// Defines `name` for all files, like `-D` of the Slice compilers.
+    pub fn define(&mut self, name: &str) {
+        self.predefined.insert(String::from(name));
+        self.defines.insert(String::from(name));
+    }
+
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.defines.contains(name)
+    }
+
+    /// Returns the preprocessed files. Included files come before the
+    /// files including them.
+    pub fn files(&self) -> &Vec<SourceFile> {
+        &self.files
+    }
+
+    /// Preprocesses the file at `path` and all files it includes. Errors
+    /// are returned as `SliceError`.
+    pub fn process(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.defines = self.predefined.clone();
+        self.process_file(path)
+    }
+
+    fn process_file(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !self.visited.insert(key) {
+            return Ok(());
+        }
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+        let processed = self.process_content(path, &content)
+            .map_err(|error| SliceError::from_error(&path.to_string_lossy(), &content, error))?;
+        self.files.push(SourceFile {
+            path: path.to_path_buf(),
+            content: processed
+        });
+        Ok(())
+    }
+
+    fn process_content(&mut self, path: &Path, content: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let mut result = String::with_capacity(content.len());
+        let mut conditions: Vec<Condition> = vec![];
+        let mut position = 0;
+        for line in content.split_inclusive('\n') {
+            let line_position = position;
+            position += line.len();
+            let active = conditions.last().map(|condition| condition.active).unwrap_or(true);
+            let trimmed = line.trim();
+            if !trimmed.starts_with('#') {
+                if active {
+                    result.push_str(line);
+                } else if line.ends_with('\n') {
+                    result.push('\n');
+                }
+                continue;
+            }
+            if line.ends_with('\n') {
+                result.push('\n');
+            }
+
+            let directive_position = line_position + line.find('#').unwrap_or(0);
+            let directive = trimmed[1..].trim_start();
+            let (name, argument) = match directive.find(|c: char| c.is_whitespace() || c == '(' || c == '"' || c == '<') {
+                Some(index) => (&directive[..index], directive[index..].trim()),
+                None => (directive, "")
+            };
+            let argument = strip_comment(argument);
+            match name {
+                "if" | "ifdef" | "ifndef" => {
+                    let value = active && match name {
+                        "ifdef" => self.is_defined(&macro_name(argument, directive_position)?),
+                        "ifndef" => !self.is_defined(&macro_name(argument, directive_position)?),
+                        _ => self.evaluate(argument, directive_position)?
+                    };
+                    conditions.push(Condition {
+                        active: value,
+                        taken: value,
+                        parent_active: active,
+                        position: directive_position
+                    });
+                },
+                "elif" => {
+                    let condition = conditions.last().ok_or_else(|| unmatched(name, directive_position))?;
+                    let value = condition.parent_active && !condition.taken && self.evaluate(argument, directive_position)?;
+                    if let Some(condition) = conditions.last_mut() {
+                        condition.active = value;
+                        condition.taken |= value;
+                    }
+                },
+                "else" => {
+                    let condition = conditions.last_mut().ok_or_else(|| unmatched(name, directive_position))?;
+                    condition.active = condition.parent_active && !condition.taken;
+                    condition.taken = true;
+                },
+                "endif" => {
+                    conditions.pop().ok_or_else(|| unmatched(name, directive_position))?;
+                },
+                _ if !active => {},
+                "define" => {
+                    let name = macro_name(argument.split_whitespace().next().unwrap_or(""), directive_position)?;
+                    self.defines.insert(name);
+                },
+                "undef" => {
+                    let name = macro_name(argument, directive_position)?;
+                    self.defines.remove(&name);
+                },
+                "include" => {
+                    let include = self.resolve_include(path, argument, directive_position)?;
+                    self.process_file(&include)?;
+                },
+                "pragma" | "" => {},
+                "error" => {
+                    return Err(Box::new(ParsingError::at(&format!("#error {}", argument), directive_position)));
+                },
+                _ => {
+                    return Err(Box::new(ParsingError::at(
+                        &format!("Unknown preprocessor directive #{}", name),
+                        directive_position
+                    )));
+                }
+            }
+        }
+        match conditions.last() {
+            Some(condition) => Err(Box::new(ParsingError::at("Unterminated conditional directive", condition.position))),
+            None => Ok(result)
+        }
+    }
+
+    /// Resolves the argument of an `#include`. Quoted names are searched
+    /// next to the including file first, then in the include directories.
+    /// Names in angle brackets are only searched in the include
+    /// directories.
+    fn resolve_include(&self, path: &Path, argument: &str, position: usize) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let (name, local) = if argument.len() > 1 && argument.starts_with('"') && argument.ends_with('"') {
+            (&argument[1..argument.len() - 1], true)
+        } else if argument.len() > 1 && argument.starts_with('<') && argument.ends_with('>') {
+            (&argument[1..argument.len() - 1], false)
+        } else {
+            return Err(Box::new(ParsingError::at(
+                &format!("Expected \"file\" or <file> after #include but found {}", argument),
+                position
+            )));
+        };
+        let local_dir = if local { path.parent() } else { None };
+        local_dir.into_iter()
+            .chain(self.include_dirs.iter().map(|dir| dir.as_path()))
+            .map(|dir| dir.join(name))
+            .find(|candidate| candidate.is_file())
+            .ok_or_else(|| -> Box<dyn std::error::Error> {
+                Box::new(ParsingError::at(&format!("Could not find include file {}", name), position))
+            })
+    }
+
+    /// Evaluates the expression of an `#if` or `#elif`. Supported are
+    /// `defined(NAME)`, `defined NAME`, integers, `!`, `&&`, `||` and
+    /// parentheses. Other names are true if they are defined.
+    fn evaluate(&self, expression: &str, position: usize) -> Result<bool, Box<dyn std::error::Error>> {
+        let tokens = tokenize(expression);
+        let mut index = 0;
+        let value = self.evaluate_or(&tokens, &mut index, position)?;
+        if index != tokens.len() {
+            return Err(Box::new(ParsingError::at(&format!("Invalid expression {}", expression), position)));
+        }
+        Ok(value)
+    }
+
+    fn evaluate_or(&self, tokens: &[String], index: &mut usize, position: usize) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut value = self.evaluate_and(tokens, index, position)?;
+        while tokens.get(*index).map(|token| token.as_str()) == Some("||") {
+            *index += 1;
+            value |= self.evaluate_and(tokens, index, position)?;
+        }
+        Ok(value)
+    }
+
+    fn evaluate_and(&self, tokens: &[String], index: &mut usize, position: usize) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut value = self.evaluate_unary(tokens, index, position)?;
+        while tokens.get(*index).map(|token| token.as_str()) == Some("&&") {
+            *index += 1;
+            value &= self.evaluate_unary(tokens, index, position)?;
+        }
+        Ok(value)
+    }
+
+    fn evaluate_unary(&self, tokens: &[String], index: &mut usize, position: usize) -> Result<bool, Box<dyn std::error::Error>> {
+        let token = tokens.get(*index).ok_or_else(|| -> Box<dyn std::error::Error> {
+            Box::new(ParsingError::at("Unexpected end of expression", position))
+        })?;
+        *index += 1;
+        match token.as_str() {
+            "!" => Ok(!self.evaluate_unary(tokens, index, position)?),
+            "(" => {
+                let value = self.evaluate_or(tokens, index, position)?;
+                expect(tokens, index, ")", position)?;
+                Ok(value)
+            },
+            "defined" => {
+                let parenthesized = tokens.get(*index).map(|token| token.as_str()) == Some("(");
+                if parenthesized {
+                    *index += 1;
+                }
+                let name = tokens.get(*index).cloned().unwrap_or_default();
+                *index += 1;
+                let name = macro_name(&name, position)?;
+                if parenthesized {
+                    expect(tokens, index, ")", position)?;
+                }
+                Ok(self.is_defined(&name))
+            },
+            token if token.chars().all(|c| c.is_ascii_digit()) => Ok(token.chars().any(|c| c != '0')),
+            token => Ok(self.is_defined(&macro_name(token, position)?))
+        }
+    }
+}
+
+/// Splits an `#if` expression into names, integers and operators.
+fn tokenize(expression: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {},
+            '&' | '|' if chars.peek() == Some(&c) => {
+                chars.next();
+                tokens.push(format!("{}{}", c, c));
+            },
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut token = c.to_string();
+                while let Some(next) = chars.peek().copied().filter(|next| next.is_ascii_alphanumeric() || *next == '_') {
+                    token.push(next);
+                    chars.next();
+                }
+                tokens.push(token);
+            },
+            c => tokens.push(c.to_string())
+        }
+    }
+    tokens
+}
+
+fn expect(tokens: &[String], index: &mut usize, expected: &str, position: usize) -> Result<(), Box<dyn std::error::Error>> {
+    if tokens.get(*index).map(|token| token.as_str()) != Some(expected) {
+        return Err(Box::new(ParsingError::at(&format!("Expected {} in expression", expected), position)));
+    }
+    *index += 1;
+    Ok(())
+}
+
+/// Checks that `name` is a valid macro name.
+fn macro_name(name: &str, position: usize) -> Result<String, Box<dyn std::error::Error>> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') &&
+        name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(Box::new(ParsingError::at(&format!("Invalid macro name \"{}\"", name), position)));
+    }
+    Ok(String::from(name))
+}
+
+/// Removes a trailing `//` or `/* */` comment from a directive.
+fn strip_comment(argument: &str) -> &str {
+    let end = [argument.find("//"), argument.find("/*")].iter().flatten().min().copied();
+    match end {
+        Some(end) => argument[..end].trim_end(),
+        None => argument
+    }
+}
+
+fn unmatched(directive: &str, position: usize) -> Box<dyn std::error::Error> {
+    Box::new(ParsingError::at(&format!("#{} without #if", directive), position))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_evaluate() {
+        let mut preprocessor = Preprocessor::new(vec![]);
+        preprocessor.define("A");
+        assert!(preprocessor.evaluate("defined(A)", 0).unwrap());
+        assert!(preprocessor.evaluate("defined A && !defined(B)", 0).unwrap());
+        assert!(preprocessor.evaluate("defined(B) || (1 && A)", 0).unwrap());
+        assert!(!preprocessor.evaluate("!(defined(A) || B)", 0).unwrap());
+        assert!(!preprocessor.evaluate("0", 0).unwrap());
+        assert!(preprocessor.evaluate("defined(A", 0).is_err());
+        assert!(preprocessor.evaluate("A B", 0).is_err());
+    }
+
+    #[test]
+    fn test_process() {
+        let dir = std::env::temp_dir().join(format!("ice-rs-preprocessor-{}", std::process::id()));
+        fs::create_dir_all(dir.join("include/Ice")).unwrap();
+        fs::create_dir_all(dir.join("src/sub")).unwrap();
+        fs::write(dir.join("include/Ice/Identity.ice"), "#pragma once\n#ifndef IDENTITY\n#define IDENTITY\nmodule Ice {}\n#endif\n").unwrap();
+        fs::write(dir.join("src/sub/Types.ice"), "#include <Ice/Identity.ice>\n#define TYPES\nmodule Types {}\n").unwrap();
+        fs::write(
+            dir.join("src/Main.ice"),
+            "#pragma once\n#include \"sub/Types.ice\"\n#include <Ice/Identity.ice>\n#if defined(TYPES) && !defined(OTHER)\nmodule Main {}\n#else\nmodule Other {}\n#endif\n"
+        ).unwrap();
+        fs::write(dir.join("src/Broken.ice"), "module Broken {}\n#if defined(TYPES\n#endif\n").unwrap();
+
+        let mut preprocessor = Preprocessor::new(vec![dir.join("include")]);
+        preprocessor.process(&dir.join("src/Main.ice")).unwrap();
+        let files = preprocessor.files();
+        assert_eq!(3, files.len());
+        assert!(files[0].path.ends_with("Ice/Identity.ice"));
+        assert_eq!("\n\n\nmodule Ice {}\n\n", files[0].content);
+        assert!(files[1].path.ends_with("sub/Types.ice"));
+        assert_eq!("\n\n\n\nmodule Main {}\n\n\n\n", files[2].content);
+        assert!(preprocessor.is_defined("IDENTITY"));
+
+        // macros of a file do not leak into the next top-level file
+        fs::write(dir.join("src/Second.ice"), "#ifdef TYPES\nmodule Leaked {}\n#endif\n#ifdef __SLICE2RS__\nmodule Second {}\n#endif\n").unwrap();
+        preprocessor.process(&dir.join("src/Second.ice")).unwrap();
+        assert_eq!("\n\n\n\nmodule Second {}\n\n", preprocessor.files()[3].content);
+        assert!(!preprocessor.is_defined("TYPES"));
+
+        let error = preprocessor.process(&dir.join("src/Broken.ice")).err().unwrap();
+        let error = error.downcast::<SliceError>().unwrap();
+        assert_eq!((2, 1), (error.line, error.column));
+
+        // without include directories the bundled definitions are used
+        let mut preprocessor = Preprocessor::new(vec![]);
+        preprocessor.process(&dir.join("src/Main.ice")).unwrap();
+        assert!(preprocessor.files()[0].path.starts_with(SLICE_DIR));
+        fs::write(dir.join("src/Missing.ice"), "#include <Ice/Missing.ice>\n").unwrap();
+        assert!(preprocessor.process(&dir.join("src/Missing.ice")).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
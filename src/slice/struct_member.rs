@@ -1,4 +1,5 @@
 use crate::slice::types::IceType;
+use crate::slice::constant::ConstValue;
 use quote::{__private::TokenStream, quote};
 
 
@@ -6,7 +7,8 @@ use quote::{__private::TokenStream, quote};
 pub struct StructMember {
     pub id: TokenStream,
     pub ice_id: String,
-    pub r#type: IceType
+    pub r#type: IceType,
+    pub default: Option<ConstValue>
 }
 
 impl StructMember {
@@ -14,10 +16,61 @@ impl StructMember {
         StructMember {
             id: TokenStream::new(),
             ice_id: String::new(),
-            r#type: IceType::VoidType
+            r#type: IceType::VoidType,
+            default: None
         }
     }
 
+    /// Returns the initial value of the member, which is either its
+    /// default value or the `Default` of its type. Returns `None` if the
+    /// type has no `Default`.
+    pub fn default_value(&self) -> Result<Option<TokenStream>, Box<dyn std::error::Error>> {
+        match (&self.default, &self.r#type) {
+            (Some(value), IceType::Optional(type_name, _)) => {
+                let value = ConstValue::token(value, type_name)?;
+                match type_name.as_ref() {
+                    IceType::StringType => Ok(Some(quote! { Some(String::from(#value)) })),
+                    _ => Ok(Some(quote! { Some(#value) }))
+                }
+            },
+            (Some(value), IceType::StringType) => {
+                let value = value.token(&self.r#type)?;
+                Ok(Some(quote! { String::from(#value) }))
+            },
+            (Some(value), _) => Ok(Some(value.token(&self.r#type)?)),
+            (None, IceType::CustomType(_)) => Ok(None),
+            (None, _) => Ok(Some(quote! { Default::default() }))
+        }
+    }
+
+    /// Generates a `Default` implementation for the type `id_token` that
+    /// initializes every member with its default value. Returns `None` if
+    /// `Default` is derived or a member has no default.
+    pub fn default_impl(id_token: &TokenStream, members: &[StructMember], derives: &[TokenStream]) -> Result<Option<TokenStream>, Box<dyn std::error::Error>> {
+        if derives.iter().any(|derive| derive.to_string() == "Default") {
+            return Ok(None);
+        }
+        let mut default_tokens = vec![];
+        for member in members {
+            match member.default_value()? {
+                Some(value) => {
+                    let member_id = &member.id;
+                    default_tokens.push(quote! { #member_id: #value });
+                },
+                None => return Ok(None)
+            }
+        }
+        Ok(Some(quote! {
+            impl Default for #id_token {
+                fn default() -> Self {
+                    #id_token {
+                        #(#default_tokens),*
+                    }
+                }
+            }
+        }))
+    }
+
     pub fn declare(&self) -> TokenStream {
         let id_token = &self.id;
         let var_token = self.r#type.token();
@@ -37,7 +90,7 @@ impl StructMember {
         let id_token = &self.id;
         let var_token = self.r#type.token();
         quote! {
-            #id_token:  #var_token::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
+            #id_token:  <#var_token>::from_bytes(&bytes[read as usize..bytes.len()], &mut read)?
         }
     }
 }
\ No newline at end of file
@@ -6,7 +6,8 @@ use super::struct_member::StructMember;
 pub struct Struct {
     pub id: TokenStream,
     pub ice_id: String,
-    pub members: Vec<StructMember>
+    pub members: Vec<StructMember>,
+    pub derives: Vec<TokenStream>
 }
 
 impl Struct {
@@ -14,7 +15,8 @@ impl Struct {
         Struct {
             id: TokenStream::new(),
             ice_id: String::new(),
-            members: Vec::new()
+            members: Vec::new(),
+            derives: Vec::new()
         }
     }
 
@@ -28,11 +30,16 @@ impl Struct {
             member.declare()
         }).collect::<Vec<_>>();
 
+        let default_impl = StructMember::default_impl(id_token, &self.members, &self.derives)?;
+
+        let derives = &self.derives;
         Ok(quote! {
-            #[derive(Debug, Clone, PartialEq, IceDerive)]
+            #[derive(Debug, Clone, PartialEq, IceDerive #(, #derives)*)]
             pub struct #id_token {
                 #(#member_tokens),*
             }
+
+            #default_impl
         })
     }
 }
\ No newline at end of file
@@ -1,6 +1,5 @@
 use quote::__private::TokenStream;
 use quote::*;
-use regex::Regex;
 
 #[derive(Clone, Debug)]
 pub enum IceType {
@@ -16,56 +15,83 @@ pub enum IceType {
     SequenceType(Box<IceType>),
     DictType(Box<IceType>, Box<IceType>),
     Optional(Box<IceType>, u8),
-    CustomType(String)
+    CustomType(String),
+    /// A proxy to an object implementing the named interface. Proxies are
+    /// mapped to their marshaled form, a `TypedProxy` of the generated
+    /// proxy type.
+    ProxyType(String),
+    /// A sequence or dictionary mapped to another Rust collection by
+    /// `["rust:type:..."]` metadata.
+    Collection(String, Box<IceType>)
 }
 
 impl IceType {
+    /// Returns the builtin type named `text`. Other names refer to
+    /// user defined types. Sequences and dictionaries are parsed by the
+    /// grammar.
     pub fn from(text: &str) -> Result<IceType, Box<dyn std::error::Error>> {
-        let type_re = Regex::new(
-            r#"(?x)
-            (void) |
-            (bool) |
-            (byte) |
-            (short) |
-            (int) |
-            (long) |
-            (float) |
-            (double) |
-            (string) |
-            (sequence)<(.+)> |
-            (dictionary)<(.+),\s*(.+)> |
-            "#
-        )?; 
+        match text.trim() {
+            "void" => Ok(IceType::VoidType),
+            "bool" => Ok(IceType::BoolType),
+            "byte" => Ok(IceType::ByteType),
+            "short" => Ok(IceType::ShortType),
+            "int" => Ok(IceType::IntType),
+            "long" => Ok(IceType::LongType),
+            "float" => Ok(IceType::FloatType),
+            "double" => Ok(IceType::DoubleType),
+            "string" => Ok(IceType::StringType),
+            name => Ok(IceType::CustomType(name.to_string()))
+        }
+    }
 
-        let captures = type_re.captures(text.trim()).map(|captures| {
-            captures
-                .iter() // All the captured groups
-                .skip(1) // Skipping the complete match
-                .flat_map(|c| c) // Ignoring all empty optional matches
-                .map(|c| c.as_str()) // Grab the original strings
-                .collect::<Vec<_>>() // Create a vector
-        });
+    /// Maps a sequence or dictionary to the given Rust collection.
+    /// Sequences may use `Vec` or `VecDeque`, dictionaries `HashMap` or
+    /// `BTreeMap`.
+    pub fn with_collection(self, collection: &str) -> Result<IceType, Box<dyn std::error::Error>> {
+        match (self, collection) {
+            (IceType::Optional(type_name, tag), _) => {
+                Ok(IceType::Optional(Box::new(type_name.with_collection(collection)?), tag))
+            },
+            (sequence @ IceType::SequenceType(_), "Vec") => Ok(sequence),
+            (dict @ IceType::DictType(_, _), "HashMap") => Ok(dict),
+            (sequence @ IceType::SequenceType(_), "VecDeque") => Ok(IceType::Collection(String::from(collection), Box::new(sequence))),
+            (dict @ IceType::DictType(_, _), "BTreeMap") => Ok(IceType::Collection(String::from(collection), Box::new(dict))),
+            (type_name, _) => Err(Box::new(crate::errors::ParsingError::new(
+                &format!("rust:type:{} is not supported for {}", collection, type_name.rust_type())
+            )))
+        }
+    }
 
-        match captures.as_ref().map(|c| c.as_slice()) {
-            Some(["void"]) => Ok(IceType::VoidType),
-            Some(["bool"]) => Ok(IceType::BoolType),
-            Some(["byte"]) => Ok(IceType::ByteType),
-            Some(["short"]) => Ok(IceType::ShortType),
-            Some(["int"]) => Ok(IceType::IntType),
-            Some(["long"]) => Ok(IceType::LongType),
-            Some(["float"]) => Ok(IceType::FloatType),
-            Some(["double"]) => Ok(IceType::DoubleType),
-            Some(["string"]) => Ok(IceType::StringType),
-            Some(["sequence", x]) => {
-                Ok(IceType::SequenceType(Box::new(IceType::from(x)?)))
+    /// Replaces the Slice names of custom types by the Rust paths
+    /// returned by `resolve`.
+    pub fn resolve<F>(&mut self, resolve: &F) -> Result<(), Box<dyn std::error::Error>>
+        where F: Fn(&str) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            IceType::SequenceType(type_name) |
+            IceType::Optional(type_name, _) |
+            IceType::Collection(_, type_name) => type_name.resolve(resolve),
+            IceType::DictType(key_type, value_type) => {
+                key_type.resolve(resolve)?;
+                value_type.resolve(resolve)
             },
-            Some(["dictionary", x, y]) => {
-                Ok(IceType::DictType(Box::new(IceType::from(x)?), Box::new(IceType::from(y)?)))
+            IceType::CustomType(type_name) => {
+                *type_name = resolve(type_name)?;
+                Ok(())
+            },
+            IceType::ProxyType(type_name) if !IceType::is_object(type_name) => {
+                *type_name = resolve(type_name)?;
+                Ok(())
             },
-            _ => Ok(IceType::CustomType(text.trim().to_string()))
+            _ => Ok(())
         }
     }
 
+    /// Returns true if `type_name` names the implicit base interface
+    /// `Ice::Object`.
+    fn is_object(type_name: &str) -> bool {
+        matches!(type_name.trim_start_matches("::"), "Object" | "Ice::Object")
+    }
+
     pub fn rust_type(&self) -> String {
         match self {
             IceType::VoidType => String::from("()"),
@@ -81,6 +107,13 @@ impl IceType {
             IceType::DictType(key_type, value_type) => format!("HashMap<{}, {}>", key_type.rust_type(), value_type.rust_type()),
             IceType::Optional(type_name, _) => format!("Option<{}>", type_name.rust_type()),
             IceType::CustomType(type_name) => format!("{}", type_name),
+            IceType::ProxyType(type_name) if IceType::is_object(type_name) => String::from("ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy>"),
+            IceType::ProxyType(type_name) => format!("ice_rs::proxy::TypedProxy<{}Prx>", type_name),
+            IceType::Collection(collection, type_name) => match type_name.as_ref() {
+                IceType::DictType(key_type, value_type) => format!("std::collections::{}<{}, {}>", collection, key_type.rust_type(), value_type.rust_type()),
+                IceType::SequenceType(type_name) => format!("std::collections::{}<{}>", collection, type_name.rust_type()),
+                _ => type_name.rust_type()
+            },
         }
     }
 
@@ -119,8 +152,28 @@ impl IceType {
                 quote!{ Option<#sub_type> }
             },
             IceType::CustomType(type_name) => {
-                let id = format_ident!("{}", type_name);
-                quote!{ #id }
+                let segments = type_name.split("::").map(|segment| format_ident!("{}", segment));
+                quote!{ #(#segments)::* }
+            },
+            IceType::ProxyType(type_name) if IceType::is_object(type_name) => quote! { ice_rs::proxy::TypedProxy<ice_rs::proxy::Proxy> },
+            IceType::ProxyType(type_name) => {
+                let segments = format!("{}Prx", type_name).split("::").filter(|segment| !segment.is_empty()).map(|segment| format_ident!("{}", segment)).collect::<Vec<_>>();
+                quote!{ ice_rs::proxy::TypedProxy<#(#segments)::*> }
+            },
+            IceType::Collection(collection, type_name) => {
+                let collection = format_ident!("{}", collection);
+                match type_name.as_ref() {
+                    IceType::DictType(key_type, value_type) => {
+                        let key = key_type.token();
+                        let value = value_type.token();
+                        quote!{ std::collections::#collection<#key, #value> }
+                    },
+                    IceType::SequenceType(type_name) => {
+                        let sub_type = type_name.token();
+                        quote!{ std::collections::#collection<#sub_type> }
+                    },
+                    _ => type_name.token()
+                }
             },
         }
     }
@@ -130,7 +183,9 @@ impl IceType {
             IceType::StringType |
             IceType::SequenceType(_) |
             IceType::DictType(_, _) |
-            IceType::CustomType(_) => true,
+            IceType::CustomType(_) |
+            IceType::ProxyType(_) |
+            IceType::Collection(_, _) => true,
             _ => false
         }
     }
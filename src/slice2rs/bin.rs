@@ -6,22 +6,31 @@ use std::path::{Path, PathBuf};
 #[derive(Clap)]
 #[clap(version = "1.0")]
 struct Opts {
-    #[clap(short)]
-    include_dir: Option<String>, 
+    /// Directory to search for included Slice files, may be repeated
+    #[clap(short = 'I', long, number_of_values = 1)]
+    include_dir: Vec<String>,
     out_dir: String,
     slice_files: Vec<String>
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opts: Opts = Opts::parse();
-    let include_dir = match opts.include_dir {
-        Some(dir) => dir,
-        None => {
-            let mut inc_dir = PathBuf::from(opts.slice_files.first().unwrap());
-            inc_dir.pop();
-            String::from(inc_dir.to_str().unwrap())
+    let include_dirs = if opts.include_dir.is_empty() {
+        let mut inc_dir = PathBuf::from(opts.slice_files.first().unwrap());
+        inc_dir.pop();
+        vec![String::from(inc_dir.to_str().unwrap())]
+    } else {
+        opts.include_dir
+    };
+    let root = match parser::parse_ice_files_with_include_dirs(&opts.slice_files, &include_dirs) {
+        Ok(root) => root,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
         }
     };
-    let root = parser::parse_ice_files(&opts.slice_files, &include_dir)?;
-    root.generate(Path::new(&opts.out_dir), "")
+    for warning in &root.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    root.generate(Path::new(&opts.out_dir))
 }
\ No newline at end of file
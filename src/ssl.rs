@@ -1,41 +1,119 @@
-
 use tokio::{io::{AsyncRead, AsyncWrite}, net::TcpStream};
-use tokio_openssl::SslStream;
-use openssl::{ssl::{SslConnector, SslConnectorBuilder, SslMethod, SslVerifyMode}};
-use openssl::x509::*;
-use std::path::Path;
+use async_trait::async_trait;
 
-use crate::transport::Transport;
+use crate::transport::{ConnectionInfo, Transport};
 use crate::errors::*;
 use crate::properties::Properties;
+use crate::initdata::InitializationData;
+use crate::trust_manager::TrustManager;
+
+/// A `CertificateVerifier` can be installed on the communicator to
+/// accept or reject peers once the SSL handshake and the `IceSSL.TrustOnly`
+/// checks succeeded.
+pub trait CertificateVerifier: Send + Sync {
+    fn verify(&self, info: &ConnectionInfo) -> bool;
+}
+
+/// A `TlsBackend` implements the TLS handshake for the SSL transport. Which
+/// backend is used is selected with the `openssl-tls` and `rustls-tls`
+/// features. Both configure themselves from the same `IceSSL.*` properties.
+#[async_trait]
+pub trait TlsBackend: Send + Sync {
+    async fn connect(&self, host: &str, port: i32, properties: &Properties) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>>;
+    fn acceptor(&self, properties: &Properties) -> Result<Box<dyn TlsAcceptor>, Box<dyn std::error::Error + Sync + Send>>;
+    /// Returns the subject of a DER encoded certificate as a list of RDNs.
+    fn subject_name(&self, der: &[u8]) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Sync + Send>>;
+}
 
-use crate::ssltools::*;
+#[async_trait]
+pub trait TlsAcceptor: Send + Sync {
+    async fn accept(&self, stream: TcpStream) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>>;
+}
+
+/// An established TLS connection of a `TlsBackend`.
+pub trait TlsStream: AsyncRead + AsyncWrite + Send + Sync + Unpin {
+    fn connection_info(&self, incoming: bool) -> ConnectionInfo;
+}
+
+#[cfg(feature = "openssl-tls")]
+pub fn backend() -> Result<Box<dyn TlsBackend>, Box<dyn std::error::Error + Sync + Send>> {
+    Ok(Box::new(crate::ssl_openssl::OpensslBackend {}))
+}
+
+#[cfg(all(feature = "rustls-tls", not(feature = "openssl-tls")))]
+pub fn backend() -> Result<Box<dyn TlsBackend>, Box<dyn std::error::Error + Sync + Send>> {
+    Ok(Box::new(crate::ssl_rustls::RustlsBackend {}))
+}
+
+#[cfg(not(any(feature = "openssl-tls", feature = "rustls-tls")))]
+pub fn backend() -> Result<Box<dyn TlsBackend>, Box<dyn std::error::Error + Sync + Send>> {
+    Err(Box::new(ProtocolError::new("SSL requires the openssl-tls or rustls-tls feature")))
+}
+
+pub struct SslAcceptor {
+    acceptor: Box<dyn TlsAcceptor>
+}
 
 pub struct SslTransport {
-    stream: SslStream<TcpStream>
+    stream: Box<dyn TlsStream>,
+    incoming: bool
 }
 
 impl SslTransport {
-    pub async fn new(address: &str, properties: &Properties) -> Result<SslTransport, Box<dyn std::error::Error + Sync + Send>>
+    pub async fn new(host: &str, port: i32, init_data: &InitializationData) -> Result<SslTransport, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let stream = backend()?.connect(host, port, init_data.properties()).await?;
+        let transport = SslTransport {
+            stream,
+            incoming: false
+        };
+        transport.verify("", init_data)?;
+        Ok(transport)
+    }
+
+    /// Creates the acceptor used by an adapter to accept SSL connections.
+    /// The server certificate is read from `IceSSL.CertFile` and is required.
+    pub fn acceptor(properties: &Properties) -> Result<SslAcceptor, Box<dyn std::error::Error + Sync + Send>>
     {
-        let mut builder = SslConnector::builder(SslMethod::tls())?;
-        let ssl_dir = Path::new(properties.get("IceSSL.DefaultDir").ok_or(Box::new(PropertyError::new("IceSSL.DefaultDir")))?);
-
-        configure_ca(&ssl_dir, properties, &mut builder)?;
-        configure_client_certs(&ssl_dir, properties, &mut builder)?;
-        configure_peer_verification(properties, &mut builder)?;
-        configure_ciphers(properties, &mut builder)?;
-        configure_protocol_versions(properties, &mut builder)?;
-
-        // connect
-        let connector = builder.build();
-        let stream = TcpStream::connect(address).await?;
-        let mut stream = SslStream::new(connector.configure()?.into_ssl(address)?, stream)?;
-        std::pin::Pin::new(&mut stream).connect().await.unwrap();
-        Ok(SslTransport {
-            stream
+        if !properties.has("IceSSL.CertFile") {
+            return Err(Box::new(PropertyError::new("IceSSL.CertFile")));
+        }
+        Ok(SslAcceptor {
+            acceptor: backend()?.acceptor(properties)?
         })
     }
+
+    /// Accepts an incoming connection of the adapter with the given name. The
+    /// peer has to pass the `IceSSL.TrustOnly` rules and the certificate verifier.
+    pub async fn accept(acceptor: &SslAcceptor, stream: TcpStream, adapter: &str, init_data: &InitializationData) -> Result<SslTransport, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let stream = acceptor.acceptor.accept(stream).await?;
+        let transport = SslTransport {
+            stream,
+            incoming: true
+        };
+        transport.verify(adapter, init_data)?;
+        Ok(transport)
+    }
+
+    fn verify(&self, adapter: &str, init_data: &InitializationData) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+    {
+        let info = self.connection_info();
+        let trust_manager = TrustManager::new(init_data.properties())?;
+        let subject = match info.peer_certificates.first() {
+            Some(cert) => Some(backend()?.subject_name(cert)?),
+            None => None
+        };
+        if !trust_manager.verify(self.incoming, adapter, subject.as_deref()) {
+            return Err(Box::new(SecurityException::new("Peer certificate does not match IceSSL.TrustOnly")));
+        }
+        if let Some(verifier) = init_data.certificate_verifier() {
+            if !verifier.verify(&info) {
+                return Err(Box::new(SecurityException::new("Connection rejected by certificate verifier")));
+            }
+        }
+        Ok(())
+    }
 }
 impl AsyncWrite for SslTransport {
     fn poll_write(
@@ -69,90 +147,8 @@ impl Transport for SslTransport {
     fn transport_type(&self) -> String {
         return String::from("ssl");
     }
-}
 
-
-fn configure_ca(ssl_dir: &Path, properties: &Properties, builder: &mut SslConnectorBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
-    let ca = match properties.get("IceSSL.CAs") {
-        Some(ca_file) => {
-            // PEM
-            read_pem(ca_file, ssl_dir)?.0.unwrap()
-        }
-        _ => {
-            if let Some(ca_file) = properties.get("IceSSL.CertAuthFile") {
-                // PEM [DEPRECATED]
-                println!("[SSL] Use of deprecated property IceSSL.CertAuthFile");
-                read_pem(ca_file, ssl_dir)?.0.unwrap()
-            } else {
-                return Ok(());
-            }
-        }
-    };
-    let mut store_builder = store::X509StoreBuilder::new()?;
-    store_builder.add_cert(ca)?;
-    let store = store_builder.build();
-    builder.set_verify_cert_store(store)?;
-
-    Ok(())
-}
-
-fn configure_client_certs(ssl_dir: &Path, properties: &Properties, builder: &mut SslConnectorBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
-{
-    let (cert, pkey) = match properties.get("IceSSL.CertFile") {
-        Some(cert_file) => {
-            if let Some(key_file) = properties.get("IceSSL.KeyFile") {
-                // PEM [DEPRECATED]
-                println!("[SSL] Use of deprecated property IceSSL.KeyFile");
-                let (cert, _) = read_pem(cert_file, ssl_dir)?;
-                let (_, pkey) = read_pem(key_file, ssl_dir)?;
-                (cert.unwrap(), pkey.unwrap())
-            } else {
-                // PKCS12
-                let password = properties.get("IceSSL.Password").ok_or(Box::new(PropertyError::new("Use of IceSSL.CertFile requires IceSSL.Password to be set")))?;
-                let pkcs12 = read_pkcs12(Path::new(cert_file), password, ssl_dir)?;
-                (pkcs12.cert, pkcs12.pkey)
-            }
-        }
-        _ => {
-            return Ok(());
-        }
-    };
-    builder.set_certificate(&cert)?;
-    builder.set_private_key(&pkey)?;
-    Ok(())
-}
-
-fn configure_peer_verification(properties: &Properties, builder: &mut SslConnectorBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
-{
-    match properties.get("IceSSL.VerifyPeer").unwrap_or(&String::from("1")).parse::<u8>()? {
-        0 => builder.set_verify(SslVerifyMode::NONE),
-        _ => builder.set_verify(SslVerifyMode::PEER)
-    }
-    Ok(())
-}
-
-fn configure_ciphers(properties: &Properties, builder: &mut SslConnectorBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
-{
-    if let Some(ciphers) =  properties.get("IceSSL.Ciphers") {
-        builder.set_cipher_list(ciphers)?;
+    fn connection_info(&self) -> ConnectionInfo {
+        self.stream.connection_info(self.incoming)
     }
-    Ok(())
-}
-
-fn configure_protocol_versions(properties: &Properties, builder: &mut SslConnectorBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
-{
-    let mut min_proto = None;
-    let mut max_proto = None;
-    if let Some(protocols) = properties.get("IceSSL.Protocols") {
-        for protocol in protocols.split(",").collect::<Vec<&str>>() {
-            if let Some(protocol) = parse_protocol(protocol) {
-                max_proto = Some(max_protocol(protocol, max_proto));
-                min_proto = Some(min_protocol(protocol, min_proto));
-            }
-        }
-    }
-
-    builder.set_min_proto_version(min_proto)?;
-    builder.set_max_proto_version(max_proto)?;
-    Ok(())
 }
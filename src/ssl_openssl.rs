@@ -0,0 +1,262 @@
+use tokio::{io::{AsyncRead, AsyncWrite}, net::TcpStream};
+use tokio_openssl::SslStream;
+use openssl::ssl::{Ssl, SslAcceptor, SslConnector, SslContextBuilder, SslMethod, SslVerifyMode, SslVersion};
+use openssl::x509::*;
+use openssl::pkcs12::*;
+use openssl::pkey::*;
+use async_trait::async_trait;
+use std::path::Path;
+
+use crate::transport::ConnectionInfo;
+use crate::tcp::tcp_connection_info;
+use crate::errors::*;
+use crate::properties::Properties;
+use crate::ssl::{TlsAcceptor, TlsBackend, TlsStream};
+use crate::ssltools::*;
+
+/// The `TlsBackend` based on OpenSSL.
+pub struct OpensslBackend {}
+
+#[async_trait]
+impl TlsBackend for OpensslBackend {
+    async fn connect(&self, host: &str, port: i32, properties: &Properties) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut builder = SslConnector::builder(SslMethod::tls())?;
+        let ssl_dir = ssl_dir(properties)?;
+
+        configure_ca(&ssl_dir, properties, &mut builder)?;
+        configure_certs(&ssl_dir, properties, &mut builder)?;
+        configure_peer_verification(properties, false, &mut builder)?;
+        configure_verify_depth(properties, &mut builder)?;
+        configure_ciphers(properties, &mut builder)?;
+        configure_protocol_versions(properties, &mut builder)?;
+
+        // connect
+        let connector = builder.build();
+        let mut config = connector.configure()?;
+        config.set_verify_hostname(check_cert_name(properties)?);
+        let stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+        let mut stream = SslStream::new(config.into_ssl(host)?, stream)?;
+        if let Err(e) = std::pin::Pin::new(&mut stream).connect().await {
+            return Err(Box::new(SecurityException::new(&format!("SSL handshake with {}:{} failed: {}", host, port, e))));
+        }
+        Ok(Box::new(OpensslStream { stream }))
+    }
+
+    fn acceptor(&self, properties: &Properties) -> Result<Box<dyn TlsAcceptor>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server())?;
+        let ssl_dir = ssl_dir(properties)?;
+
+        configure_ca(&ssl_dir, properties, &mut builder)?;
+        configure_certs(&ssl_dir, properties, &mut builder)?;
+        configure_peer_verification(properties, true, &mut builder)?;
+        configure_verify_depth(properties, &mut builder)?;
+        configure_ciphers(properties, &mut builder)?;
+        configure_protocol_versions(properties, &mut builder)?;
+
+        Ok(Box::new(OpensslAcceptor { acceptor: builder.build() }))
+    }
+
+    fn subject_name(&self, der: &[u8]) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Sync + Send>> {
+        let cert = X509::from_der(der)?;
+        let mut subject = vec![];
+        for entry in cert.subject_name().entries() {
+            subject.push((
+                String::from(entry.object().nid().short_name()?),
+                entry.data().as_utf8()?.to_string()
+            ));
+        }
+        Ok(subject)
+    }
+}
+
+struct OpensslAcceptor {
+    acceptor: SslAcceptor
+}
+
+#[async_trait]
+impl TlsAcceptor for OpensslAcceptor {
+    async fn accept(&self, stream: TcpStream) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>> {
+        let mut stream = SslStream::new(Ssl::new(self.acceptor.context())?, stream)?;
+        if let Err(e) = std::pin::Pin::new(&mut stream).accept().await {
+            return Err(Box::new(SecurityException::new(&format!("SSL handshake failed: {}", e))));
+        }
+        Ok(Box::new(OpensslStream { stream }))
+    }
+}
+
+struct OpensslStream {
+    stream: SslStream<TcpStream>
+}
+
+impl AsyncWrite for OpensslStream {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<Result<usize, std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+impl AsyncRead for OpensslStream {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl TlsStream for OpensslStream {
+    fn connection_info(&self, incoming: bool) -> ConnectionInfo {
+        let mut info = tcp_connection_info(self.stream.get_ref(), "ssl", incoming);
+        let ssl = self.stream.ssl();
+        if let Some(cipher) = ssl.current_cipher() {
+            info.cipher = String::from(cipher.name());
+        }
+        // the server side chain does not contain the peer certificate
+        if incoming {
+            if let Some(cert) = ssl.peer_certificate() {
+                if let Ok(der) = cert.to_der() {
+                    info.peer_certificates.push(der);
+                }
+            }
+        }
+        if let Some(chain) = ssl.peer_cert_chain() {
+            for cert in chain {
+                if let Ok(der) = cert.to_der() {
+                    info.peer_certificates.push(der);
+                }
+            }
+        }
+        info.verified = !info.peer_certificates.is_empty() && ssl.verify_result() == X509VerifyResult::OK;
+        info
+    }
+}
+
+fn read_pem(pem_file: &str, dir: &Path) -> Result<(Option<X509>, Option<PKey<Private>>), Box<dyn std::error::Error + Sync + Send>> {
+    let buffer = read_file(pem_file, dir)?;
+    match (X509::from_pem(&buffer), PKey::private_key_from_pem(&buffer)) {
+        (Ok(cert), Ok(pkey)) => {
+            Ok((Some(cert), Some(pkey)))
+        }
+        (Ok(cert), _) => {
+            Ok((Some(cert), None))
+        }
+        (_, Ok(pkey)) => {
+            Ok((None, Some(pkey)))
+        }
+        _ => Err(Box::new(ProtocolError::new(&format!("SSL: Error reading PEM file: {}", pem_file))))
+    }
+}
+
+fn read_pkcs12(pkcs12_file: &str, password: &str, dir: &Path) -> Result<ParsedPkcs12, Box<dyn std::error::Error + Sync + Send>> {
+    let buffer = read_file(pkcs12_file, dir)?;
+    match Pkcs12::from_der(&buffer) {
+        Ok(pkcs12) => {
+            match pkcs12.parse(password) {
+                Ok(parsed) => Ok(parsed),
+                _ => Err(Box::new(ProtocolError::new("SSL: Could not parse pkcs12")))
+            }
+        },
+        _ => Err(Box::new(ProtocolError::new("SSL: Could not read pkcs12")))
+    }
+}
+
+fn configure_ca(ssl_dir: &Path, properties: &Properties, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    let ca = match ca_file(properties) {
+        Some(ca_file) => {
+            // PEM
+            read_pem(ca_file, ssl_dir)?.0.ok_or_else(|| Box::new(ProtocolError::new(&format!("SSL: No certificate in {}", ca_file))))?
+        }
+        _ => return Ok(())
+    };
+    let mut store_builder = store::X509StoreBuilder::new()?;
+    store_builder.add_cert(ca)?;
+    let store = store_builder.build();
+    builder.set_verify_cert_store(store)?;
+
+    Ok(())
+}
+
+fn configure_certs(ssl_dir: &Path, properties: &Properties, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    let (cert, pkey) = match properties.get("IceSSL.CertFile") {
+        Some(cert_file) => {
+            if let Some(key_file) = properties.get("IceSSL.KeyFile") {
+                // PEM [DEPRECATED]
+                println!("[SSL] Use of deprecated property IceSSL.KeyFile");
+                let (cert, _) = read_pem(cert_file, ssl_dir)?;
+                let (_, pkey) = read_pem(key_file, ssl_dir)?;
+                (cert.unwrap(), pkey.unwrap())
+            } else {
+                // PKCS12
+                let password = properties.get("IceSSL.Password").ok_or(Box::new(PropertyError::new("Use of IceSSL.CertFile requires IceSSL.Password to be set")))?;
+                let pkcs12 = read_pkcs12(cert_file, password, ssl_dir)?;
+                (pkcs12.cert, pkcs12.pkey)
+            }
+        }
+        _ => {
+            return Ok(());
+        }
+    };
+    builder.set_certificate(&cert)?;
+    builder.set_private_key(&pkey)?;
+    Ok(())
+}
+
+fn configure_peer_verification(properties: &Properties, incoming: bool, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    match verify_peer(properties, incoming)? {
+        0 => builder.set_verify(SslVerifyMode::NONE),
+        1 => builder.set_verify(SslVerifyMode::PEER),
+        _ if !incoming => builder.set_verify(SslVerifyMode::PEER),
+        _ => builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT)
+    }
+    Ok(())
+}
+
+fn configure_verify_depth(properties: &Properties, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    // IceSSL counts the peer certificate, OpenSSL does not
+    match verify_depth(properties)? {
+        0 => {},
+        depth => builder.set_verify_depth(depth as u32 - 1)
+    }
+    Ok(())
+}
+
+fn configure_ciphers(properties: &Properties, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    if let Some(ciphers) =  properties.get("IceSSL.Ciphers") {
+        builder.set_cipher_list(ciphers)?;
+    }
+    Ok(())
+}
+
+fn configure_protocol_versions(properties: &Properties, builder: &mut SslContextBuilder) -> Result<(), Box<dyn std::error::Error + Sync + Send>>
+{
+    let (min_proto, max_proto) = protocol_range(properties);
+    builder.set_min_proto_version(min_proto.map(ssl_version))?;
+    builder.set_max_proto_version(max_proto.map(ssl_version))?;
+    Ok(())
+}
+
+fn ssl_version(version: TlsVersion) -> SslVersion {
+    match version {
+        TlsVersion::SSL3 => SslVersion::SSL3,
+        TlsVersion::TLS1 => SslVersion::TLS1,
+        TlsVersion::TLS1_1 => SslVersion::TLS1_1,
+        TlsVersion::TLS1_2 => SslVersion::TLS1_2,
+        TlsVersion::TLS1_3 => SslVersion::TLS1_3
+    }
+}
@@ -0,0 +1,342 @@
+use tokio::{io::{AsyncRead, AsyncWrite}, net::TcpStream};
+use tokio_rustls::{TlsConnector, TlsStream as RustlsStream};
+use rustls::{ClientConfig, ServerConfig, RootCertStore, DigitallySignedStruct, SignatureScheme, SupportedProtocolVersion, CertificateError};
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::server::WebPkiClientVerifier;
+use rustls::crypto::{CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use x509_parser::prelude::*;
+use x509_parser::objects::{oid2abbrev, oid_registry};
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use std::convert::TryFrom;
+
+use crate::transport::ConnectionInfo;
+use crate::tcp::tcp_connection_info;
+use crate::errors::*;
+use crate::properties::Properties;
+use crate::ssl::{TlsAcceptor, TlsBackend, TlsStream};
+use crate::ssltools::*;
+
+type CertifiedKey = (Vec<CertificateDer<'static>>, PrivateKeyDer<'static>);
+
+/// The `TlsBackend` based on rustls. rustls only implements TLS 1.2 and 1.3,
+/// `IceSSL.Ciphers` takes rustls cipher suite names like `TLS13_AES_256_GCM_SHA384`.
+pub struct RustlsBackend {}
+
+#[async_trait]
+impl TlsBackend for RustlsBackend {
+    async fn connect(&self, host: &str, port: i32, properties: &Properties) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>> {
+        let ssl_dir = ssl_dir(properties)?;
+        let provider = configure_provider(properties)?;
+        let verify = verify_peer(properties, false)? > 0;
+        let verifier = ServerVerifier {
+            inner: if verify {
+                Some(WebPkiServerVerifier::builder_with_provider(Arc::new(configure_ca(&ssl_dir, properties, true)?), provider.clone()).build()?)
+            } else {
+                None
+            },
+            check_cert_name: check_cert_name(properties)?,
+            provider: provider.clone()
+        };
+
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(&configure_protocol_versions(properties)?)?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+        let config = match configure_certs(&ssl_dir, properties)? {
+            Some((certs, key)) => builder.with_client_auth_cert(certs, key)?,
+            None => builder.with_no_client_auth()
+        };
+
+        // connect
+        let server_name = ServerName::try_from(String::from(host))?;
+        let stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+        let stream = match TlsConnector::from(Arc::new(config)).connect(server_name, stream).await {
+            Ok(stream) => stream,
+            Err(e) => return Err(Box::new(SecurityException::new(&format!("SSL handshake with {}:{} failed: {}", host, port, e))))
+        };
+        let stream = RustlsTlsStream {
+            stream: RustlsStream::from(stream),
+            verified: verify
+        };
+        stream.check_depth(properties)?;
+        Ok(Box::new(stream))
+    }
+
+    fn acceptor(&self, properties: &Properties) -> Result<Box<dyn TlsAcceptor>, Box<dyn std::error::Error + Sync + Send>> {
+        let ssl_dir = ssl_dir(properties)?;
+        let provider = configure_provider(properties)?;
+        let verify = verify_peer(properties, true)?;
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&configure_protocol_versions(properties)?)?;
+        let builder = match verify {
+            0 => builder.with_no_client_auth(),
+            1 => builder.with_client_cert_verifier(WebPkiClientVerifier::builder_with_provider(Arc::new(configure_ca(&ssl_dir, properties, false)?), provider).allow_unauthenticated().build()?),
+            _ => builder.with_client_cert_verifier(WebPkiClientVerifier::builder_with_provider(Arc::new(configure_ca(&ssl_dir, properties, false)?), provider).build()?)
+        };
+        let config = match configure_certs(&ssl_dir, properties)? {
+            Some((certs, key)) => builder.with_single_cert(certs, key)?,
+            None => return Err(Box::new(PropertyError::new("IceSSL.CertFile")))
+        };
+
+        Ok(Box::new(RustlsAcceptor {
+            acceptor: tokio_rustls::TlsAcceptor::from(Arc::new(config)),
+            properties: properties.clone(),
+            verified: verify > 0
+        }))
+    }
+
+    fn subject_name(&self, der: &[u8]) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Sync + Send>> {
+        let (_, cert) = X509Certificate::from_der(der)?;
+        let mut subject = vec![];
+        for entry in cert.subject().iter_attributes() {
+            let key = oid2abbrev(entry.attr_type(), oid_registry()).map(String::from).unwrap_or_else(|_| entry.attr_type().to_id_string());
+            subject.push((key, String::from(entry.as_str()?)));
+        }
+        Ok(subject)
+    }
+}
+
+struct RustlsAcceptor {
+    acceptor: tokio_rustls::TlsAcceptor,
+    properties: Properties,
+    verified: bool
+}
+
+#[async_trait]
+impl TlsAcceptor for RustlsAcceptor {
+    async fn accept(&self, stream: TcpStream) -> Result<Box<dyn TlsStream>, Box<dyn std::error::Error + Sync + Send>> {
+        let stream = match self.acceptor.accept(stream).await {
+            Ok(stream) => stream,
+            Err(e) => return Err(Box::new(SecurityException::new(&format!("SSL handshake failed: {}", e))))
+        };
+        let stream = RustlsTlsStream {
+            stream: RustlsStream::from(stream),
+            verified: self.verified
+        };
+        stream.check_depth(&self.properties)?;
+        Ok(Box::new(stream))
+    }
+}
+
+struct RustlsTlsStream {
+    stream: RustlsStream<TcpStream>,
+    verified: bool
+}
+
+impl RustlsTlsStream {
+    // rustls has no verify depth, so the chain sent by the peer is checked instead
+    fn check_depth(&self, properties: &Properties) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        let depth = verify_depth(properties)?;
+        let chain = self.stream.get_ref().1.peer_certificates().map(|certs| certs.len()).unwrap_or(0);
+        if depth > 0 && chain > depth {
+            return Err(Box::new(SecurityException::new(&format!("Peer certificate chain exceeds IceSSL.VerifyDepthMax ({})", depth))));
+        }
+        Ok(())
+    }
+}
+
+impl AsyncWrite for RustlsTlsStream {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<Result<usize, std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+impl AsyncRead for RustlsTlsStream {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::pin::Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl TlsStream for RustlsTlsStream {
+    fn connection_info(&self, incoming: bool) -> ConnectionInfo {
+        let (stream, state) = self.stream.get_ref();
+        let mut info = tcp_connection_info(stream, "ssl", incoming);
+        if let Some(suite) = state.negotiated_cipher_suite() {
+            info.cipher = format!("{:?}", suite.suite());
+        }
+        if let Some(certs) = state.peer_certificates() {
+            info.peer_certificates = certs.iter().map(|cert| cert.to_vec()).collect();
+        }
+        info.verified = !info.peer_certificates.is_empty() && self.verified;
+        info
+    }
+}
+
+/// Verifies the server certificate unless `IceSSL.VerifyPeer` is 0. The host
+/// name is only checked if `IceSSL.CheckCertName` is set.
+#[derive(Debug)]
+struct ServerVerifier {
+    inner: Option<Arc<WebPkiServerVerifier>>,
+    check_cert_name: bool,
+    provider: Arc<CryptoProvider>
+}
+
+impl ServerCertVerifier for ServerVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return Ok(ServerCertVerified::assertion())
+        };
+        match inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForName)) |
+            Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForNameContext { .. })) if !self.check_cert_name => {
+                Ok(ServerCertVerified::assertion())
+            }
+            result => result
+        }
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn read_certs(pem_file: &str, dir: &Path) -> Result<Vec<CertificateDer<'static>>, Box<dyn std::error::Error + Sync + Send>> {
+    let buffer = read_file(pem_file, dir)?;
+    let certs = rustls_pemfile::certs(&mut buffer.as_slice()).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(Box::new(ProtocolError::new(&format!("SSL: Error reading PEM file: {}", pem_file))));
+    }
+    Ok(certs)
+}
+
+fn read_key(pem_file: &str, dir: &Path) -> Result<PrivateKeyDer<'static>, Box<dyn std::error::Error + Sync + Send>> {
+    let buffer = read_file(pem_file, dir)?;
+    match rustls_pemfile::private_key(&mut buffer.as_slice())? {
+        Some(key) => Ok(key),
+        None => Err(Box::new(ProtocolError::new(&format!("SSL: Error reading PEM file: {}", pem_file))))
+    }
+}
+
+fn read_pkcs12(pkcs12_file: &str, password: &str, dir: &Path) -> Result<CertifiedKey, Box<dyn std::error::Error + Sync + Send>> {
+    let buffer = read_file(pkcs12_file, dir)?;
+    let pfx = match p12::PFX::parse(&buffer) {
+        Ok(pfx) => pfx,
+        _ => return Err(Box::new(ProtocolError::new("SSL: Could not read pkcs12")))
+    };
+    if !pfx.verify_mac(password) {
+        return Err(Box::new(ProtocolError::new("SSL: Could not parse pkcs12")));
+    }
+    match (pfx.cert_x509_bags(password), pfx.key_bags(password)) {
+        (Ok(certs), Ok(mut keys)) if !certs.is_empty() && !keys.is_empty() => {
+            let certs = certs.into_iter().map(CertificateDer::from).collect();
+            Ok((certs, PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(keys.remove(0)))))
+        }
+        _ => Err(Box::new(ProtocolError::new("SSL: Could not parse pkcs12")))
+    }
+}
+
+fn configure_provider(properties: &Properties) -> Result<Arc<CryptoProvider>, Box<dyn std::error::Error + Sync + Send>> {
+    let mut provider = ring::default_provider();
+    if let Some(ciphers) = properties.get("IceSSL.Ciphers") {
+        let ciphers = ciphers.split(&[':', ',', ' '][..]).map(|cipher| cipher.to_uppercase()).collect::<Vec<String>>();
+        provider.cipher_suites.retain(|suite| ciphers.contains(&format!("{:?}", suite.suite())));
+        if provider.cipher_suites.is_empty() {
+            return Err(Box::new(ProtocolError::new("SSL: No supported cipher in IceSSL.Ciphers")));
+        }
+    }
+    Ok(Arc::new(provider))
+}
+
+/// Without `IceSSL.CAs` clients trust the platform CAs, like the openssl
+/// connector does.
+fn configure_ca(ssl_dir: &Path, properties: &Properties, platform_cas: bool) -> Result<RootCertStore, Box<dyn std::error::Error + Sync + Send>> {
+    let mut store = RootCertStore::empty();
+    if let Some(ca_file) = ca_file(properties) {
+        // PEM
+        for cert in read_certs(ca_file, ssl_dir)? {
+            store.add(cert)?;
+        }
+    } else if platform_cas {
+        store.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        if store.is_empty() {
+            return Err(Box::new(ProtocolError::new("SSL: No platform CA certificates found, set IceSSL.CAs")));
+        }
+    }
+    Ok(store)
+}
+
+fn configure_certs(ssl_dir: &Path, properties: &Properties) -> Result<Option<CertifiedKey>, Box<dyn std::error::Error + Sync + Send>> {
+    match properties.get("IceSSL.CertFile") {
+        Some(cert_file) => {
+            if let Some(key_file) = properties.get("IceSSL.KeyFile") {
+                // PEM [DEPRECATED]
+                println!("[SSL] Use of deprecated property IceSSL.KeyFile");
+                Ok(Some((read_certs(cert_file, ssl_dir)?, read_key(key_file, ssl_dir)?)))
+            } else {
+                // PKCS12
+                let password = properties.get("IceSSL.Password").ok_or(Box::new(PropertyError::new("Use of IceSSL.CertFile requires IceSSL.Password to be set")))?;
+                Ok(Some(read_pkcs12(cert_file, password, ssl_dir)?))
+            }
+        }
+        _ => Ok(None)
+    }
+}
+
+fn configure_protocol_versions(properties: &Properties) -> Result<Vec<&'static SupportedProtocolVersion>, Box<dyn std::error::Error + Sync + Send>> {
+    let (min_proto, max_proto) = protocol_range(properties);
+    let versions = [(TlsVersion::TLS1_2, &rustls::version::TLS12), (TlsVersion::TLS1_3, &rustls::version::TLS13)]
+        .iter()
+        .filter(|(version, _)| min_proto.unwrap_or(TlsVersion::SSL3) <= *version && *version <= max_proto.unwrap_or(TlsVersion::TLS1_3))
+        .map(|(_, version)| *version)
+        .collect::<Vec<&'static SupportedProtocolVersion>>();
+    if versions.is_empty() {
+        return Err(Box::new(ProtocolError::new("SSL: rustls only supports TLS 1.2 and TLS 1.3")));
+    }
+    Ok(versions)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_configure_ca() {
+        let ssl_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/certs");
+        let mut properties = Properties::new();
+        assert!(configure_ca(&ssl_dir, &properties, false).unwrap().is_empty());
+
+        std::env::set_var("SSL_CERT_FILE", ssl_dir.join("cacert.pem"));
+        assert!(!configure_ca(&ssl_dir, &properties, true).unwrap().is_empty());
+
+        properties.set("IceSSL.CAs", "cacert.pem");
+        assert_eq!(1, configure_ca(&ssl_dir, &properties, false).unwrap().len());
+    }
+}
@@ -1,64 +1,67 @@
+// the helpers are only used by the TLS backends
+#![cfg_attr(not(any(feature = "openssl-tls", feature = "rustls-tls")), allow(dead_code))]
+
 use std::io::prelude::*;
-use openssl::ssl::SslVersion;
-use openssl::x509::*;
-use openssl::pkcs12::*;
-use openssl::pkey::*;
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::errors::*;
+use crate::properties::Properties;
 
-pub (crate) fn read_pem(pem_file: &str, dir: &Path) ->Result<(Option<X509>, Option<PKey<Private>>), Box<dyn std::error::Error + Sync + Send>> {
-    let mut buffer = vec![];
-    let mut file = File::open(&dir.join(pem_file))?;
-    file.read_to_end(&mut buffer)?;
+/// Protocol versions that can be configured with `IceSSL.Protocols`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    SSL3,
+    TLS1,
+    TLS1_1,
+    TLS1_2,
+    TLS1_3
+}
 
-    match (X509::from_pem(&buffer), PKey::private_key_from_pem(&buffer)) {
-        (Ok(cert), Ok(pkey)) => {
-            Ok((Some(cert), Some(pkey)))
-        }
-        (Ok(cert), _) => {
-            Ok((Some(cert), None))
-        }
-        (_, Ok(pkey)) => {
-            Ok((None, Some(pkey)))
-        }
-        _ => Err(Box::new(ProtocolError::new(&format!("SSL: Error reading PEM file: {}", pem_file))))
+pub (crate) fn ssl_dir(properties: &Properties) -> Result<PathBuf, Box<dyn std::error::Error + Sync + Send>> {
+    match properties.get("IceSSL.DefaultDir") {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Err(Box::new(PropertyError::new("IceSSL.DefaultDir")))
     }
 }
 
-pub (crate) fn read_pkcs12(pkcs12_path: &Path, password: &str, dir: &Path) -> Result<ParsedPkcs12, Box<dyn std::error::Error + Sync + Send>> {
+pub (crate) fn read_file(file: &str, dir: &Path) -> Result<Vec<u8>, Box<dyn std::error::Error + Sync + Send>> {
     let mut buffer = vec![];
-    let mut file = File::open(&dir.join(pkcs12_path))?;
+    let mut file = File::open(dir.join(file))?;
     file.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
 
-    match Pkcs12::from_der(&buffer) {
-        Ok(pkcs12) => {
-            match pkcs12.parse(password) {
-                Ok(parsed) => Ok(parsed),
-                _ => Err(Box::new(ProtocolError::new("SSL: Could not parse pkcs12")))
+/// Returns the file holding the certificate authorities, if any.
+pub (crate) fn ca_file(properties: &Properties) -> Option<&String> {
+    match properties.get("IceSSL.CAs") {
+        Some(ca_file) => Some(ca_file),
+        _ => {
+            let ca_file = properties.get("IceSSL.CertAuthFile");
+            if ca_file.is_some() {
+                println!("[SSL] Use of deprecated property IceSSL.CertAuthFile");
             }
-        },
-        _ => Err(Box::new(ProtocolError::new("SSL: Could not read pkcs12")))
+            ca_file
+        }
     }
 }
 
-pub (crate) fn parse_protocol(protocol: &str) -> Option<SslVersion> {
-    match protocol.to_lowercase().as_ref() {
+pub (crate) fn parse_protocol(protocol: &str) -> Option<TlsVersion> {
+    match protocol.trim().to_lowercase().as_ref() {
         "ssl3" | "sslv3" => {
-            Some(SslVersion::SSL3)
+            Some(TlsVersion::SSL3)
         },
         "tls1_3" | "tlsv1_3" => {
-            Some(SslVersion::TLS1_3)
+            Some(TlsVersion::TLS1_3)
         }
         "tls1_2" | "tlsv1_2" => {
-            Some(SslVersion::TLS1_2)
+            Some(TlsVersion::TLS1_2)
         }
         "tls1_1" | "tlsv1_1" => {
-            Some(SslVersion::TLS1_1)
+            Some(TlsVersion::TLS1_1)
         }
         "tls1" | "tlsv1" | "tls1_0" | "tlsv1_0" => {
-            Some(SslVersion::TLS1)
+            Some(TlsVersion::TLS1)
         }
         _ => {
             None
@@ -66,40 +69,33 @@ pub (crate) fn parse_protocol(protocol: &str) -> Option<SslVersion> {
     }
 }
 
-fn protocol_to_num(version: SslVersion) -> i32 {
-    match version {
-        SslVersion::TLS1 => 1,
-        SslVersion::TLS1_1 => 2,
-        SslVersion::TLS1_2 => 3,
-        SslVersion::TLS1_3 => 4,
-        _ => 0
+/// Returns the lowest and highest protocol version enabled by `IceSSL.Protocols`.
+pub (crate) fn protocol_range(properties: &Properties) -> (Option<TlsVersion>, Option<TlsVersion>) {
+    let mut min_proto = None;
+    let mut max_proto = None;
+    if let Some(protocols) = properties.get("IceSSL.Protocols") {
+        for protocol in protocols.split(',') {
+            if let Some(protocol) = parse_protocol(protocol) {
+                max_proto = Some(max_proto.map_or(protocol, |current| std::cmp::max(current, protocol)));
+                min_proto = Some(min_proto.map_or(protocol, |current| std::cmp::min(current, protocol)));
+            }
+        }
     }
+    (min_proto, max_proto)
 }
 
-fn num_to_protocol(num: i32) -> SslVersion {
-    match num {
-        4 => SslVersion::TLS1_3,
-        3 => SslVersion::TLS1_2,
-        2 => SslVersion::TLS1_1,
-        1 => SslVersion::TLS1,
-        _ => SslVersion::SSL3,
-    }
+/// Returns `IceSSL.VerifyDepthMax`, the maximum length of the peer
+/// certificate chain including the peer certificate. 0 means unlimited.
+pub (crate) fn verify_depth(properties: &Properties) -> Result<usize, Box<dyn std::error::Error + Sync + Send>> {
+    Ok(properties.get("IceSSL.VerifyDepthMax").unwrap_or(&String::from("3")).parse::<usize>()?)
 }
 
-pub (crate) fn max_protocol(new_value: SslVersion, current_max: Option<SslVersion>) -> SslVersion {
-    let new_numeric = protocol_to_num(new_value);
-    if let Some(current_max) = current_max {
-        num_to_protocol(std::cmp::max(new_numeric, protocol_to_num(current_max)))
-    } else {
-        new_value
-    }
+/// Returns `IceSSL.VerifyPeer`. Clients default to 1, servers to 2.
+pub (crate) fn verify_peer(properties: &Properties, incoming: bool) -> Result<u8, Box<dyn std::error::Error + Sync + Send>> {
+    let default = if incoming { "2" } else { "1" };
+    Ok(properties.get("IceSSL.VerifyPeer").map(|value| value.as_str()).unwrap_or(default).parse::<u8>()?)
 }
 
-pub (crate) fn min_protocol(new_value: SslVersion, current_min: Option<SslVersion>) -> SslVersion {
-    let new_numeric = protocol_to_num(new_value);
-    if let Some(current_min) = current_min {
-        num_to_protocol(std::cmp::min(new_numeric, protocol_to_num(current_min)))
-    } else {
-        new_value
-    }
-}
\ No newline at end of file
+pub (crate) fn check_cert_name(properties: &Properties) -> Result<bool, Box<dyn std::error::Error + Sync + Send>> {
+    Ok(properties.get("IceSSL.CheckCertName").unwrap_or(&String::from("0")).parse::<u8>()? > 0)
+}
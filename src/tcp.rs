@@ -1,20 +1,49 @@
 use tokio::{io::{AsyncRead, AsyncWrite}, net::TcpStream};
 
-use crate::transport::Transport;
+use crate::transport::{ConnectionInfo, Transport};
 
 pub struct TcpTransport {
-    stream: TcpStream
+    stream: TcpStream,
+    incoming: bool
 }
 
 impl TcpTransport {
     pub async fn new(address: &str) -> Result<TcpTransport, Box<dyn std::error::Error + Sync + Send>>
     {
         Ok(TcpTransport {
-            stream: TcpStream::connect(address).await?
+            stream: TcpStream::connect(address).await?,
+            incoming: false
         })
     }
 }
 
+/// Wraps an accepted stream, the transport is incoming.
+impl From<TcpStream> for TcpTransport {
+    fn from(stream: TcpStream) -> TcpTransport {
+        TcpTransport {
+            stream,
+            incoming: true
+        }
+    }
+}
+
+pub (crate) fn tcp_connection_info(stream: &TcpStream, transport: &str, incoming: bool) -> ConnectionInfo {
+    let mut info = ConnectionInfo {
+        transport: String::from(transport),
+        incoming,
+        ..Default::default()
+    };
+    if let Ok(local) = stream.local_addr() {
+        info.local_address = local.ip().to_string();
+        info.local_port = local.port() as i32;
+    }
+    if let Ok(remote) = stream.peer_addr() {
+        info.remote_address = remote.ip().to_string();
+        info.remote_port = remote.port() as i32;
+    }
+    info
+}
+
 impl AsyncWrite for TcpTransport {
     fn poll_write(
         self: std::pin::Pin<&mut Self>,
@@ -43,4 +72,8 @@ impl Transport for TcpTransport {
     fn transport_type(&self) -> String {
         return String::from("tcp");
     }
-}
\ No newline at end of file
+
+    fn connection_info(&self) -> ConnectionInfo {
+        tcp_connection_info(&self.stream, "tcp", self.incoming)
+    }
+}
@@ -1,5 +1,24 @@
 use tokio::io::{AsyncRead, AsyncWrite};
 
+/// The `ConnectionInfo` describes the connection a request
+/// was received on or a proxy is connected with.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionInfo {
+    pub transport: String,
+    pub incoming: bool,
+    pub local_address: String,
+    pub local_port: i32,
+    pub remote_address: String,
+    pub remote_port: i32,
+    pub cipher: String,
+    /// True if the peer certificate chain has been verified successfully.
+    pub verified: bool,
+    /// DER encoded certificate chain of the peer, starting with the peer
+    /// certificate. Only filled for SSL connections.
+    pub peer_certificates: Vec<Vec<u8>>,
+}
+
 pub trait Transport: AsyncRead + AsyncWrite + Send {
     fn transport_type(&self) -> String;
+    fn connection_info(&self) -> ConnectionInfo;
 }
@@ -0,0 +1,199 @@
+use crate::properties::Properties;
+use std::collections::HashMap;
+
+type DistinguishedName = Vec<(String, String)>;
+
+/// The `TrustManager` implements the `IceSSL.TrustOnly` properties. Each
+/// property holds a `;` separated list of distinguished names. A name prefixed
+/// with `accept:` (the default) or `reject:` is matched against the subject
+/// of the peer certificate and matches if all its RDNs are part of the subject.
+pub struct TrustManager {
+    accept_all: Vec<DistinguishedName>,
+    reject_all: Vec<DistinguishedName>,
+    accept_client: Vec<DistinguishedName>,
+    reject_client: Vec<DistinguishedName>,
+    accept_server: Vec<DistinguishedName>,
+    reject_server: Vec<DistinguishedName>,
+    adapters: HashMap<String, (Vec<DistinguishedName>, Vec<DistinguishedName>)>
+}
+
+impl TrustManager {
+    pub fn new(properties: &Properties) -> Result<TrustManager, Box<dyn std::error::Error + Sync + Send>> {
+        let (accept_all, reject_all) = parse_rules(properties.get("IceSSL.TrustOnly"))?;
+        let (accept_client, reject_client) = parse_rules(properties.get("IceSSL.TrustOnly.Client"))?;
+        let (accept_server, reject_server) = parse_rules(properties.get("IceSSL.TrustOnly.Server"))?;
+        let mut adapters = HashMap::new();
+        for (key, value) in properties.get_for_prefix("IceSSL.TrustOnly.Server.") {
+            let adapter = &key["IceSSL.TrustOnly.Server.".len()..];
+            adapters.insert(String::from(adapter), parse_rules(Some(value))?);
+        }
+        Ok(TrustManager {
+            accept_all,
+            reject_all,
+            accept_client,
+            reject_client,
+            accept_server,
+            reject_server,
+            adapters
+        })
+    }
+
+    /// Verifies the subject of the peer certificate. `subject` is `None` if the
+    /// peer did not send a certificate. Incoming connections also consider
+    /// `IceSSL.TrustOnly.Server.<AdapterName>`.
+    pub fn verify(&self, incoming: bool, adapter: &str, subject: Option<&[(String, String)]>) -> bool {
+        let rules = self.rules(incoming, adapter);
+        if rules.is_empty() {
+            return true;
+        }
+        let subject = match subject {
+            Some(subject) => subject,
+            None => return false
+        };
+
+        let mut has_accept = false;
+        let mut accepted = false;
+        for (accept, rule) in rules {
+            if accept {
+                has_accept = true;
+                accepted = accepted || matches(&rule, subject);
+            } else if matches(&rule, subject) {
+                return false;
+            }
+        }
+        !has_accept || accepted
+    }
+
+    fn rules(&self, incoming: bool, adapter: &str) -> Vec<(bool, DistinguishedName)> {
+        let mut rules = vec![];
+        let (accept, reject) = if incoming {
+            (&self.accept_server, &self.reject_server)
+        } else {
+            (&self.accept_client, &self.reject_client)
+        };
+        rules.extend(self.accept_all.iter().chain(accept.iter()).map(|rule| (true, rule.clone())));
+        rules.extend(self.reject_all.iter().chain(reject.iter()).map(|rule| (false, rule.clone())));
+
+        if incoming {
+            if let Some((accept, reject)) = self.adapters.get(adapter) {
+                rules.extend(accept.iter().map(|rule| (true, rule.clone())));
+                rules.extend(reject.iter().map(|rule| (false, rule.clone())));
+            }
+        }
+        rules
+    }
+}
+
+fn matches(rule: &[(String, String)], subject: &[(String, String)]) -> bool {
+    rule.iter().all(|(key, value)| {
+        subject.iter().any(|(subject_key, subject_value)| {
+            normalize_key(key) == normalize_key(subject_key) && value == subject_value
+        })
+    })
+}
+
+fn normalize_key(key: &str) -> String {
+    match key.to_uppercase().as_ref() {
+        "EMAILADDRESS" | "EMAIL" => String::from("E"),
+        key => String::from(key)
+    }
+}
+
+fn parse_rules(value: Option<&String>) -> Result<(Vec<DistinguishedName>, Vec<DistinguishedName>), Box<dyn std::error::Error + Sync + Send>> {
+    let mut accept = vec![];
+    let mut reject = vec![];
+    if let Some(value) = value {
+        for entry in split_unquoted(value, ';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            if let Some(rule) = entry.strip_prefix("reject:") {
+                reject.push(parse_dn(rule)?);
+            } else {
+                accept.push(parse_dn(entry.strip_prefix("accept:").unwrap_or(entry))?);
+            }
+        }
+    }
+    Ok((accept, reject))
+}
+
+/// Parses a distinguished name like `CN=client, O="ZeroC, Inc."` into its RDNs.
+pub fn parse_dn(dn: &str) -> Result<DistinguishedName, Box<dyn std::error::Error + Sync + Send>> {
+    let mut result = vec![];
+    for rdn in split_unquoted(dn, ',') {
+        let rdn = rdn.trim();
+        if rdn.is_empty() {
+            continue;
+        }
+        match rdn.find('=') {
+            Some(index) => {
+                let key = rdn[0..index].trim();
+                let value = rdn[index + 1..].trim().trim_matches('"');
+                result.push((String::from(key), String::from(value)));
+            }
+            None => return Err(Box::new(crate::errors::ParsingError::new(&format!("Invalid distinguished name: {}", dn))))
+        }
+    }
+    Ok(result)
+}
+
+fn split_unquoted(value: &str, separator: char) -> Vec<String> {
+    let mut result = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        if c == separator && !quoted {
+            result.push(current);
+            current = String::new();
+        } else {
+            current.push(c);
+        }
+    }
+    result.push(current);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn subject() -> DistinguishedName {
+        parse_dn("C=US, ST=Florida, L=Jupiter, O=\"ZeroC, Inc.\", OU=Ice, CN=client").expect("Cannot parse subject")
+    }
+
+    #[test]
+    fn test_no_rules() {
+        let trust_manager = TrustManager::new(&Properties::new()).expect("Cannot create trust manager");
+        assert!(trust_manager.verify(false, "", None));
+        assert!(trust_manager.verify(true, "adapter", Some(&subject())));
+    }
+
+    #[test]
+    fn test_accept_and_reject() {
+        let mut properties = Properties::new();
+        properties.set("IceSSL.TrustOnly", "CN=server; CN=client, O=\"ZeroC, Inc.\"");
+        properties.set("IceSSL.TrustOnly.Server", "reject:OU=Ice");
+        let trust_manager = TrustManager::new(&properties).expect("Cannot create trust manager");
+        assert!(trust_manager.verify(false, "", Some(&subject())));
+        assert!(!trust_manager.verify(false, "", None));
+        assert!(!trust_manager.verify(true, "", Some(&subject())));
+
+        properties.set("IceSSL.TrustOnly", "CN=server");
+        properties.set("IceSSL.TrustOnly.Server", "");
+        properties.set("IceSSL.TrustOnly.Server.Hello", "CN=client");
+        let trust_manager = TrustManager::new(&properties).expect("Cannot create trust manager");
+        assert!(!trust_manager.verify(false, "", Some(&subject())));
+        assert!(trust_manager.verify(true, "Hello", Some(&subject())));
+    }
+
+    #[test]
+    fn test_invalid_adapter_rule() {
+        let mut properties = Properties::new();
+        properties.set("IceSSL.TrustOnly.Server.Hello", "CN=client; reject:invalid");
+        assert!(TrustManager::new(&properties).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use tokio::{io::{AsyncRead, AsyncWrite}, net::UdpSocket};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use crate::errors::*;
+use crate::protocol::UdpEndpointData;
+use crate::transport::{ConnectionInfo, Transport};
+
+/// The `UdpTransport` sends every write as a single datagram. It is used
+/// by datagram proxies, which never receive a reply.
+pub struct UdpTransport {
+    socket: UdpSocket,
+    remote: SocketAddr
+}
+
+impl UdpTransport {
+    pub async fn new(endpoint: &UdpEndpointData) -> Result<UdpTransport, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let remote = resolve(&endpoint.host, endpoint.port).await?;
+        let socket = Socket::new(Domain::for_address(remote), Type::DGRAM, Some(Protocol::UDP))?;
+        if remote.ip().is_multicast() {
+            if !endpoint.interface.is_empty() {
+                socket.set_multicast_if_v4(&endpoint.interface.parse::<Ipv4Addr>()?)?;
+            }
+            if endpoint.ttl >= 0 {
+                socket.set_multicast_ttl_v4(endpoint.ttl as u32)?;
+            }
+        }
+        let local: SocketAddr = match remote {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        socket.bind(&local.into())?;
+        socket.set_nonblocking(true)?;
+
+        let socket = UdpSocket::from_std(socket.into())?;
+        socket.connect(remote).await?;
+        Ok(UdpTransport {
+            socket,
+            remote
+        })
+    }
+
+    /// Binds the socket an adapter receives datagrams on. Multicast
+    /// endpoints join the group on `--interface` or on all interfaces.
+    pub async fn bind(endpoint: &UdpEndpointData) -> Result<UdpSocket, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let address = resolve(&endpoint.host, endpoint.port).await?;
+        let socket = Socket::new(Domain::for_address(address), Type::DGRAM, Some(Protocol::UDP))?;
+        match address.ip() {
+            IpAddr::V4(group) if group.is_multicast() => {
+                socket.set_reuse_address(true)?;
+                socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, endpoint.port as u16)).into())?;
+                let interface = if endpoint.interface.is_empty() {
+                    Ipv4Addr::UNSPECIFIED
+                } else {
+                    endpoint.interface.parse::<Ipv4Addr>()?
+                };
+                socket.join_multicast_v4(&group, &interface)?;
+            }
+            _ => socket.bind(&address.into())?
+        }
+        socket.set_nonblocking(true)?;
+        Ok(UdpSocket::from_std(socket.into())?)
+    }
+}
+
+async fn resolve(host: &str, port: i32) -> Result<SocketAddr, Box<dyn std::error::Error + Sync + Send>> {
+    match tokio::net::lookup_host(format!("{}:{}", host, port)).await?.next() {
+        Some(address) => Ok(address),
+        None => Err(Box::new(ProtocolError::new(&format!("UDP: Cannot resolve {}", host))))
+    }
+}
+
+pub (crate) fn udp_connection_info(socket: &UdpSocket, remote: SocketAddr, incoming: bool) -> ConnectionInfo {
+    let mut info = ConnectionInfo {
+        transport: String::from("udp"),
+        incoming,
+        ..Default::default()
+    };
+    if let Ok(local) = socket.local_addr() {
+        info.local_address = local.ip().to_string();
+        info.local_port = local.port() as i32;
+    }
+    info.remote_address = remote.ip().to_string();
+    info.remote_port = remote.port() as i32;
+    info
+}
+
+impl AsyncWrite for UdpTransport {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<Result<usize, std::io::Error>> {
+        self.get_mut().socket.poll_send(cx, buf)
+    }
+
+    fn poll_flush(self: std::pin::Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: std::pin::Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for UdpTransport {
+    fn poll_read(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>, buf: &mut tokio::io::ReadBuf<'_>) -> std::task::Poll<std::io::Result<()>> {
+        self.get_mut().socket.poll_recv(cx, buf)
+    }
+}
+
+impl Transport for UdpTransport {
+    fn transport_type(&self) -> String {
+        String::from("udp")
+    }
+
+    fn connection_info(&self) -> ConnectionInfo {
+        udp_connection_info(&self.socket, self.remote, false)
+    }
+}
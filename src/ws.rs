@@ -0,0 +1,476 @@
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use std::convert::TryFrom;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use base64::Engine;
+use sha1::{Digest, Sha1};
+
+use crate::errors::*;
+use crate::initdata::InitializationData;
+use crate::properties::Properties;
+use crate::transport::{ConnectionInfo, Transport};
+
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const WS_PROTOCOL: &str = "ice.zeroc.com";
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+// close status sent for frames violating RFC 6455, like unmasked client frames
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+// close status sent for messages larger than `Ice.MessageSizeMax`
+const CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
+
+/// The `WsTransport` implements Ice over WebSocket on top of a TCP (`ws`) or
+/// SSL (`wss`) transport. Every Ice message is sent as a binary frame, frames
+/// sent by the client are masked as required by RFC 6455.
+pub struct WsTransport<T: Transport + Unpin> {
+    stream: T,
+    incoming: bool,
+    read_buffer: Vec<u8>,
+    payload: Vec<u8>,
+    write_buffer: Vec<u8>,
+    pending: usize,
+    closed: bool,
+    message_size: usize,
+    max_message_size: usize
+}
+
+impl<T: Transport + Unpin> WsTransport<T> {
+    /// Performs the client side handshake requesting `resource`.
+    pub async fn connect(mut stream: T, host: &str, port: i32, resource: &str, init_data: &InitializationData) -> Result<WsTransport<T>, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let key = base64::engine::general_purpose::STANDARD.encode(rand::random::<[u8; 16]>());
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}:{}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Protocol: {}\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            resource, host, port, WS_PROTOCOL, key
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        let (response, read_buffer) = read_http_header(&mut stream).await?;
+        let mut lines = response.lines();
+        let status = lines.next().unwrap_or_default();
+        if status.split_whitespace().nth(1) != Some("101") {
+            return Err(Box::new(ProtocolError::new(&format!("WS: Unexpected response: {}", status))));
+        }
+        let headers = parse_headers(lines);
+        if header_value(&headers, "sec-websocket-accept") != Some(&accept_key(&key)) {
+            return Err(Box::new(ProtocolError::new("WS: Invalid Sec-WebSocket-Accept")));
+        }
+        if header_value(&headers, "sec-websocket-protocol").map(|value| value.as_str()) != Some(WS_PROTOCOL) {
+            return Err(Box::new(ProtocolError::new("WS: Server does not support the ice.zeroc.com protocol")));
+        }
+
+        Ok(WsTransport::with_stream(stream, false, read_buffer, message_size_max(init_data.properties())?))
+    }
+
+    /// Performs the server side handshake of an accepted connection.
+    pub async fn accept(mut stream: T, init_data: &InitializationData) -> Result<WsTransport<T>, Box<dyn std::error::Error + Sync + Send>>
+    {
+        let (request, read_buffer) = read_http_header(&mut stream).await?;
+        let mut lines = request.lines();
+        let request_line = lines.next().unwrap_or_default();
+        if !request_line.starts_with("GET ") {
+            return Err(Box::new(ProtocolError::new(&format!("WS: Unexpected request: {}", request_line))));
+        }
+        let headers = parse_headers(lines);
+        let upgrade = header_value(&headers, "upgrade").map(|value| value.to_lowercase());
+        if upgrade.as_deref() != Some("websocket") {
+            return Err(Box::new(ProtocolError::new("WS: Missing Upgrade: websocket")));
+        }
+        if header_value(&headers, "sec-websocket-version").map(|value| value.as_str()) != Some("13") {
+            return Err(Box::new(ProtocolError::new("WS: Unsupported WebSocket version")));
+        }
+        let protocols = header_value(&headers, "sec-websocket-protocol").cloned().unwrap_or_default();
+        if !protocols.split(',').any(|protocol| protocol.trim() == WS_PROTOCOL) {
+            return Err(Box::new(ProtocolError::new("WS: Client does not support the ice.zeroc.com protocol")));
+        }
+        let key = header_value(&headers, "sec-websocket-key").ok_or_else(|| Box::new(ProtocolError::new("WS: Missing Sec-WebSocket-Key")))?;
+
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Protocol: {}\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            WS_PROTOCOL, accept_key(key)
+        );
+        stream.write_all(response.as_bytes()).await?;
+
+        Ok(WsTransport::with_stream(stream, true, read_buffer, message_size_max(init_data.properties())?))
+    }
+
+    fn with_stream(stream: T, incoming: bool, read_buffer: Vec<u8>, max_message_size: usize) -> WsTransport<T> {
+        WsTransport {
+            stream,
+            incoming,
+            read_buffer,
+            payload: vec![],
+            write_buffer: vec![],
+            pending: 0,
+            closed: false,
+            message_size: 0,
+            max_message_size
+        }
+    }
+
+    // frames sent by the client must be masked, frames sent by the server must not
+    fn encode_frame(&self, opcode: u8, data: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x80 | opcode];
+        let mask_bit = if self.incoming { 0x00 } else { 0x80 };
+        if data.len() < 126 {
+            frame.push(mask_bit | data.len() as u8);
+        } else if data.len() <= u16::MAX as usize {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        } else {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(data.len() as u64).to_be_bytes());
+        }
+        if self.incoming {
+            frame.extend_from_slice(data);
+        } else {
+            let mask = rand::random::<[u8; 4]>();
+            frame.extend_from_slice(&mask);
+            frame.extend(data.iter().enumerate().map(|(i, byte)| byte ^ mask[i % 4]));
+        }
+        frame
+    }
+
+    /// Decodes the next complete frame of the read buffer. Data frames are
+    /// appended to the payload, pings are answered and a close frame ends the
+    /// stream. Messages larger than the maximum message size are rejected as
+    /// soon as the header of the frame exceeding it is read.
+    fn decode_frame(&mut self) -> std::io::Result<bool> {
+        let buffer = &self.read_buffer;
+        if buffer.len() < 2 {
+            return Ok(false);
+        }
+        let fin = buffer[0] & 0x80 != 0;
+        let opcode = buffer[0] & 0x0F;
+        let masked = buffer[1] & 0x80 != 0;
+        // frames sent by the client must be masked, frames sent by the server must not
+        if masked != self.incoming {
+            self.close(CLOSE_PROTOCOL_ERROR);
+            let message = if self.incoming { "WS: Unmasked frame from client" } else { "WS: Masked frame from server" };
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, message));
+        }
+        let buffer = &self.read_buffer;
+        let mut offset = 2;
+        let length = match buffer[1] & 0x7F {
+            126 => {
+                if buffer.len() < offset + 2 {
+                    return Ok(false);
+                }
+                offset += 2;
+                u16::from_be_bytes([buffer[2], buffer[3]]) as usize
+            }
+            127 => {
+                if buffer.len() < offset + 8 {
+                    return Ok(false);
+                }
+                offset += 8;
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&buffer[2..10]);
+                // the most significant bit of a 64 bit length must be 0
+                if bytes[0] & 0x80 != 0 {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "WS: Invalid frame length"));
+                }
+                usize::try_from(u64::from_be_bytes(bytes)).unwrap_or(usize::MAX)
+            }
+            length => length as usize
+        };
+        // the frames of a fragmented message count towards its size
+        let data_frame = opcode == OP_BINARY || opcode == OP_CONTINUATION;
+        let message_size = if data_frame { self.message_size.saturating_add(length) } else { length };
+        if message_size > self.max_message_size {
+            self.close(CLOSE_MESSAGE_TOO_BIG);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "WS: Message exceeds Ice.MessageSizeMax"));
+        }
+        let buffer = &self.read_buffer;
+        let mask = if masked {
+            if buffer.len() < offset + 4 {
+                return Ok(false);
+            }
+            offset += 4;
+            Some([buffer[offset - 4], buffer[offset - 3], buffer[offset - 2], buffer[offset - 1]])
+        } else {
+            None
+        };
+        let end = offset.checked_add(length).ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "WS: Invalid frame length"))?;
+        if buffer.len() < end {
+            return Ok(false);
+        }
+
+        let mut data: Vec<u8> = self.read_buffer.drain(0..end).skip(offset).collect();
+        if let Some(mask) = mask {
+            data.iter_mut().enumerate().for_each(|(i, byte)| *byte ^= mask[i % 4]);
+        }
+        match opcode {
+            OP_BINARY | OP_CONTINUATION => {
+                self.payload.extend(data);
+                self.message_size = if fin { 0 } else { message_size };
+            }
+            OP_PING => {
+                let frame = self.encode_frame(OP_PONG, &data);
+                self.write_buffer.extend(frame);
+            }
+            OP_PONG => {}
+            OP_CLOSE => {
+                if !self.closed {
+                    let frame = self.encode_frame(OP_CLOSE, &data);
+                    self.write_buffer.extend(frame);
+                    self.closed = true;
+                }
+            }
+            OP_TEXT => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "WS: Text frames are not supported")),
+            _ => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "WS: Unknown opcode"))
+        }
+        Ok(true)
+    }
+
+    /// Queues a close frame with `status` unless the stream is closed.
+    fn close(&mut self, status: u16) {
+        if !self.closed {
+            let frame = self.encode_frame(OP_CLOSE, &status.to_be_bytes());
+            self.write_buffer.extend(frame);
+            self.closed = true;
+        }
+    }
+
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while !self.write_buffer.is_empty() {
+            match Pin::new(&mut self.stream).poll_write(cx, &self.write_buffer) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(written)) => { self.write_buffer.drain(0..written); }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn read_http_header<T: Transport + Unpin>(stream: &mut T) -> Result<(String, Vec<u8>), Box<dyn std::error::Error + Sync + Send>> {
+    let mut buffer = vec![];
+    let mut chunk = [0u8; 1024];
+    loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            let header = String::from_utf8(buffer[0..end].to_vec())?;
+            return Ok((header, buffer[end + 4..].to_vec()));
+        }
+        if buffer.len() > 16384 {
+            return Err(Box::new(ProtocolError::new("WS: HTTP header too large")));
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(Box::new(ProtocolError::new("WS: Connection closed during handshake")));
+        }
+        buffer.extend_from_slice(&chunk[0..read]);
+    }
+}
+
+/// Returns `Ice.MessageSizeMax` in bytes. The property is given in
+/// kilobytes and defaults to 1024, values below 1 disable the limit.
+fn message_size_max(properties: &Properties) -> Result<usize, Box<dyn std::error::Error + Sync + Send>> {
+    let size = properties.get("Ice.MessageSizeMax").unwrap_or(&String::from("1024")).parse::<i64>()?;
+    if size < 1 {
+        return Ok(usize::MAX);
+    }
+    Ok(usize::try_from(size)?.saturating_mul(1024))
+}
+
+fn parse_headers<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<(String, String)> {
+    lines.filter_map(|line| {
+        line.find(':').map(|index| (line[0..index].trim().to_lowercase(), String::from(line[index + 1..].trim())))
+    }).collect()
+}
+
+fn header_value<'a>(headers: &'a [(String, String)], key: &str) -> Option<&'a String> {
+    headers.iter().find(|(header, _)| header == key).map(|(_, value)| value)
+}
+
+fn accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WS_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+}
+
+impl<T: Transport + Unpin> AsyncWrite for WsTransport<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        let transport = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        // a frame still in the write buffer belongs to a previous call with the same data
+        if transport.pending == 0 {
+            let frame = transport.encode_frame(OP_BINARY, buf);
+            transport.write_buffer.extend(frame);
+            transport.pending = buf.len();
+        }
+        match transport.poll_drain(cx) {
+            Poll::Ready(Ok(())) => {
+                let written = transport.pending;
+                transport.pending = 0;
+                Poll::Ready(Ok(written))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        let transport = self.get_mut();
+        match transport.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut transport.stream).poll_flush(cx),
+            result => result
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        let transport = self.get_mut();
+        transport.close(1000);
+        match transport.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut transport.stream).poll_shutdown(cx),
+            result => result
+        }
+    }
+}
+
+impl<T: Transport + Unpin> AsyncRead for WsTransport<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let transport = self.get_mut();
+        loop {
+            if !transport.payload.is_empty() {
+                let size = std::cmp::min(buf.remaining(), transport.payload.len());
+                buf.put_slice(&transport.payload[0..size]);
+                transport.payload.drain(0..size);
+                return Poll::Ready(Ok(()));
+            }
+            let decoded = match transport.decode_frame() {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    // the close frame is sent on a best effort basis before the error ends the connection
+                    let _ = transport.poll_drain(cx);
+                    return Poll::Ready(Err(e));
+                }
+            };
+            if decoded {
+                // answers to pings and close frames are sent on a best effort basis
+                if let Poll::Ready(Err(e)) = transport.poll_drain(cx) {
+                    return Poll::Ready(Err(e));
+                }
+                continue;
+            }
+            if transport.closed {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut chunk = [0u8; 4096];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut transport.stream).poll_read(cx, &mut chunk_buf) {
+                Poll::Ready(Ok(())) => {
+                    if chunk_buf.filled().is_empty() {
+                        return Poll::Ready(Ok(()));
+                    }
+                    transport.read_buffer.extend_from_slice(chunk_buf.filled());
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T: Transport + Unpin> Transport for WsTransport<T> {
+    fn transport_type(&self) -> String {
+        match self.stream.transport_type().as_ref() {
+            "ssl" => String::from("wss"),
+            _ => String::from("ws")
+        }
+    }
+
+    fn connection_info(&self) -> ConnectionInfo {
+        let mut info = self.stream.connection_info();
+        info.transport = self.transport_type();
+        info
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mem::MemTransport;
+
+    async fn server_transport(name: &str, max_message_size: usize) -> (WsTransport<MemTransport>, MemTransport) {
+        let mut listener = MemTransport::bind(name).expect("Cannot bind");
+        let client = MemTransport::new(name).await.expect("Cannot connect");
+        let server = listener.accept().await.expect("Cannot accept");
+        (WsTransport::with_stream(server, true, vec![], max_message_size), client)
+    }
+
+    #[tokio::test]
+    async fn test_frame_size_limit() {
+        let (mut transport, mut client) = server_transport("wsframetest", 1024).await;
+        transport.read_buffer = vec![0x82, 0xFE, 0x04, 0x01];
+        assert!(transport.decode_frame().is_err());
+        let mut close = [0u8; 4];
+        transport.flush().await.expect("Cannot send close frame");
+        client.read_exact(&mut close).await.expect("Cannot read close frame");
+        assert_eq!([0x88, 2, 0x03, 0xF1], close);
+
+        let (mut transport, _client) = server_transport("wsframetest", usize::MAX).await;
+        transport.read_buffer = vec![0x82, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert!(transport.decode_frame().is_err());
+
+        let (mut transport, _client) = server_transport("wsframetest", 1024).await;
+        transport.read_buffer = vec![0x82, 0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert!(transport.decode_frame().is_err());
+
+        let (mut transport, _client) = server_transport("wsframetest", 1024).await;
+        transport.read_buffer = vec![0x82, 0x83, 0, 0, 0, 0, 1, 2, 3];
+        assert!(transport.decode_frame().expect("Cannot decode frame"));
+        assert_eq!(vec![1, 2, 3], transport.payload);
+
+        // a continuation frame exceeding the limit with the frames before it
+        let (mut transport, _client) = server_transport("wsframetest", 4).await;
+        transport.read_buffer = vec![0x02, 0x83, 0, 0, 0, 0, 1, 2, 3, 0x80, 0x82, 0, 0, 0, 0, 4, 5];
+        assert!(transport.decode_frame().expect("Cannot decode frame"));
+        assert!(transport.decode_frame().is_err());
+
+        let (mut transport, _client) = server_transport("wsframetest", 4).await;
+        transport.read_buffer = vec![0x82, 0x83, 0, 0, 0, 0, 1, 2, 3, 0x82, 0x82, 0, 0, 0, 0, 4, 5];
+        assert!(transport.decode_frame().expect("Cannot decode frame"));
+        assert!(transport.decode_frame().expect("Cannot decode frame"));
+    }
+
+    #[tokio::test]
+    async fn test_unmasked_client_frame() {
+        let (mut transport, mut client) = server_transport("wsmasktest", 1024).await;
+        transport.read_buffer = vec![0x82, 3, 1, 2, 3];
+        assert!(transport.decode_frame().is_err());
+        let mut close = [0u8; 4];
+        transport.flush().await.expect("Cannot send close frame");
+        client.read_exact(&mut close).await.expect("Cannot read close frame");
+        assert_eq!([0x88, 2, 0x03, 0xEA], close);
+    }
+
+    #[test]
+    fn test_message_size_max() {
+        let mut properties = Properties::new();
+        assert_eq!(1024 * 1024, message_size_max(&properties).unwrap());
+        properties.set("Ice.MessageSizeMax", "1");
+        assert_eq!(1024, message_size_max(&properties).unwrap());
+        properties.set("Ice.MessageSizeMax", "0");
+        assert_eq!(usize::MAX, message_size_max(&properties).unwrap());
+    }
+}